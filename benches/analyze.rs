@@ -0,0 +1,93 @@
+use check_react_boundary::native;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use oxc::allocator::Allocator;
+use oxc::parser::Parser;
+use oxc::span::{SourceType, Span};
+use std::hint::black_box;
+
+const SMALL: &str = r#"
+"use client";
+import { Button } from "./button";
+
+export const App = () => {
+  return (
+    <div>
+      <Button />
+    </div>
+  );
+};
+"#;
+
+/// The compiled radix-ui separator, repeated to reach the size of a vendored bundle
+const BUNDLE_CHUNK: &str = include_str!("../fixtures/npm/@radix-ui/react-separator/index.js");
+const BUNDLE_REPEAT: usize = 200;
+
+/// A hand-written style file with many components, each rendering imported components
+fn medium_source() -> String {
+    let mut source = String::from("\"use client\";\nimport { Button, Card } from \"./ui\";\n");
+    for i in 0..200 {
+        source.push_str(&format!(
+            "\nexport const Component{i} = ({{ label }}) => {{\n  return (\n    <Card>\n      <Button>{{label}}</Button>\n    </Card>\n  );\n}};\n"
+        ));
+    }
+    source
+}
+
+fn bundle_source() -> String {
+    BUNDLE_CHUNK.repeat(BUNDLE_REPEAT)
+}
+
+fn inputs() -> Vec<(&'static str, String, &'static str)> {
+    vec![
+        ("small", SMALL.to_string(), "tsx"),
+        ("medium", medium_source(), "tsx"),
+        ("bundle", bundle_source(), "js"),
+    ]
+}
+
+fn bench_analyze(c: &mut Criterion) {
+    let mut group = c.benchmark_group("analyze");
+    for (name, source, extension) in inputs() {
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &source, |b, source| {
+            b.iter(|| native::analyze(black_box(source.as_bytes()), extension).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_span_to_range(c: &mut Criterion) {
+    let mut group = c.benchmark_group("span_to_range");
+    for (name, source, _) in inputs() {
+        // Worst case: a span at the very end of the file
+        let end = source.len() as u32;
+        let span = Span::new(end.saturating_sub(1), end);
+        group.bench_with_input(BenchmarkId::from_parameter(name), &source, |b, source| {
+            b.iter(|| native::span_to_range(black_box(source), span))
+        });
+    }
+    group.finish();
+}
+
+fn bench_collect_jsx_usages(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collect_jsx_usages");
+    for (name, source, extension) in inputs() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_extension(extension).unwrap();
+        let program = Parser::new(&allocator, &source, source_type)
+            .parse()
+            .program;
+        group.bench_function(BenchmarkId::from_parameter(name), |b| {
+            b.iter(|| native::collect_jsx_usages(black_box(&program.body)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_analyze,
+    bench_span_to_range,
+    bench_collect_jsx_usages
+);
+criterion_main!(benches);
@@ -0,0 +1,47 @@
+import {useContextProps as $64fa3d84918910a7$export$2881499e37b75b9a} from "./utils.mjs";
+import {createLeafComponent as $fqjVJ$createLeafComponent} from "@react-aria/collections";
+import {useSeparator as $fqjVJ$useSeparator} from "react-aria";
+import {filterDOMProps as $fqjVJ$filterDOMProps, mergeProps as $fqjVJ$mergeProps} from "@react-aria/utils";
+import $fqjVJ$react, {createContext as $fqjVJ$createContext} from "react";
+
+/*
+ * Copyright 2022 Adobe. All rights reserved.
+ * This file is licensed to you under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License. You may obtain a copy
+ * of the License at http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software distributed under
+ * the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+ * OF ANY KIND, either express or implied. See the License for the specific language
+ * governing permissions and limitations under the License.
+ */ 
+
+
+
+
+const $431f98aba6844401$export$6615d83f6de245ce = /*#__PURE__*/ (0, $fqjVJ$createContext)({});
+class $431f98aba6844401$export$1ff3c3f08ae963c0 {
+    static type = 'separator';
+}
+const $431f98aba6844401$export$1ff3c3f08ae963c0_1 = /*#__PURE__*/ (0, $fqjVJ$createLeafComponent)($431f98aba6844401$export$1ff3c3f08ae963c0, function Separator(props, ref) {
+    [props, ref] = (0, $64fa3d84918910a7$export$2881499e37b75b9a)(props, ref, $431f98aba6844401$export$6615d83f6de245ce);
+    let { elementType: elementType, orientation: orientation, style: style, className: className, slot: slot, ...otherProps } = props;
+    let Element = elementType || 'hr';
+    if (Element === 'hr' && orientation === 'vertical') Element = 'div';
+    let { separatorProps: separatorProps } = (0, $fqjVJ$useSeparator)({
+        ...(0, $fqjVJ$filterDOMProps)(props),
+        elementType: elementType,
+        orientation: orientation
+    });
+    return /*#__PURE__*/ (0, $fqjVJ$react).createElement(Element, {
+        ...(0, $fqjVJ$mergeProps)(separatorProps, otherProps),
+        style: style,
+        className: className !== null && className !== void 0 ? className : 'react-aria-Separator',
+        ref: ref,
+        slot: slot || undefined
+    });
+});
+
+
+export {$431f98aba6844401$export$6615d83f6de245ce as SeparatorContext, $431f98aba6844401$export$1ff3c3f08ae963c0_1 as Separator};
+//# sourceMappingURL=Separator.module.js.map
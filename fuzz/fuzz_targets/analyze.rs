@@ -0,0 +1,30 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use check_react_boundary::native;
+use libfuzzer_sys::fuzz_target;
+
+/// Extensions the host actually sends, plus arbitrary ones to exercise the error path
+const EXTENSIONS: &[&str] = &["js", "jsx", "ts", "tsx", "mjs", "cjs", "mts", "cts", "d.ts"];
+
+#[derive(Arbitrary, Debug)]
+enum Extension {
+    Known(u8),
+    Arbitrary(String),
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    extension: Extension,
+    content: Vec<u8>,
+}
+
+fuzz_target!(|input: Input| {
+    let extension = match &input.extension {
+        Extension::Known(index) => EXTENSIONS[*index as usize % EXTENSIONS.len()],
+        Extension::Arbitrary(extension) => extension.as_str(),
+    };
+
+    // Errors are fine, panics and traps are not
+    let _ = native::analyze(&input.content, extension);
+});
@@ -0,0 +1,742 @@
+use oxc::ast::ast::{
+    BindingPatternKind, CallExpression, Declaration, Expression, FormalParameter, FormalParameters,
+    FunctionBody, ObjectPropertyKind, Program, Statement, TSType, TSTypeName,
+};
+use oxc::ast_visit::{Visit, walk};
+use oxc::semantic::SemanticBuilder;
+use oxc::span::{GetSpan, Span};
+use std::collections::HashSet;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// A server action found in a module
+pub(crate) struct ServerAction {
+    pub(crate) name: String,
+    /// Span of the action's name
+    pub(crate) span: Span,
+    pub(crate) parameter_count: u32,
+    /// Whether a parameter is a `FormData`, by type annotation or by the conventional `formData`
+    /// name in untyped code
+    pub(crate) uses_form_data: bool,
+    /// Nearest component whose body declares the action, for inline actions
+    pub(crate) component: Option<String>,
+    /// `action.bind(null, ...)` calls anywhere in the module, as (call span, bound argument count)
+    pub(crate) binds: Vec<(Span, u32)>,
+    /// Variables of the enclosing functions the action closes over, with the span of their first
+    /// reference in the action, in order of that reference. Always empty for module-level actions
+    pub(crate) captures: Vec<(String, Span)>,
+}
+
+/// Collect server actions: exported async functions of a "use server" module, and functions
+/// declared anywhere with their own "use server" directive. Functions of a "use server" module
+/// exported after their declaration (`export { create }`, `export default create`) count too.
+///
+/// Inline actions are found at any depth: in nested blocks and callbacks, in object literals
+/// (named after their property) and returned by action factories (named after the factory).
+pub(crate) fn collect_server_actions(
+    program: &Program,
+    module_use_server: bool,
+    components: &HashSet<&str>,
+) -> Vec<ServerAction> {
+    let mut collector = Collector {
+        components,
+        exported_locals: if module_use_server {
+            exported_locals(&program.body)
+        } else {
+            HashSet::new()
+        },
+        depth: 0,
+        actions: Vec::new(),
+        nested: Vec::new(),
+    };
+
+    for statement in program.body.iter() {
+        match statement {
+            Statement::ExportNamedDeclaration(export_decl) => {
+                if let Some(declaration) = &export_decl.declaration {
+                    collector.collect_from_declaration(declaration, module_use_server, None);
+                }
+            }
+            Statement::ExportDefaultDeclaration(export_decl) => {
+                if let oxc::ast::ast::ExportDefaultDeclarationKind::FunctionDeclaration(func) =
+                    &export_decl.declaration
+                {
+                    let name = func
+                        .id
+                        .as_ref()
+                        .map_or("default".to_string(), |id| id.name.to_string());
+                    if let Some(body) = &func.body {
+                        collector.visit_function(
+                            name,
+                            export_decl.span,
+                            func.r#async && module_use_server,
+                            &func.params,
+                            body,
+                            None,
+                        );
+                    }
+                }
+            }
+            statement => {
+                if let Some(declaration) = statement.as_declaration() {
+                    collector.collect_from_declaration(declaration, false, None);
+                }
+            }
+        }
+    }
+
+    let binds = collect_binds(&program.body);
+    for action in collector.actions.iter_mut() {
+        action.binds = binds
+            .iter()
+            .filter(|(name, _, _)| *name == action.name)
+            .map(|(_, span, argument_count)| (*span, *argument_count))
+            .collect();
+    }
+
+    if !collector.nested.is_empty() {
+        collect_captures(program, &mut collector.actions, &collector.nested);
+    }
+
+    collector.actions
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+struct Collector<'c> {
+    /// Names of the module's components, which inline actions are linked to
+    components: &'c HashSet<&'c str>,
+    /// Top-level bindings a "use server" module exports separately from their declaration
+    exported_locals: HashSet<String>,
+    /// Number of functions enclosing the one being visited
+    depth: usize,
+    actions: Vec<ServerAction>,
+    /// Actions declared inside another function, as (index in `actions`, span of the function),
+    /// whose captures are resolved once the whole module is collected
+    nested: Vec<(usize, Span)>,
+}
+
+impl Collector<'_> {
+    /// Visit the functions of a declaration. `exported_action` marks async functions as actions
+    /// because they are exported from a "use server" module
+    fn collect_from_declaration(
+        &mut self,
+        declaration: &Declaration,
+        exported_action: bool,
+        component: Option<&str>,
+    ) {
+        match declaration {
+            Declaration::FunctionDeclaration(func) => {
+                if let (Some(id), Some(body)) = (&func.id, &func.body) {
+                    let exported_action = exported_action || self.exports_local(&id.name);
+                    self.visit_function(
+                        id.name.to_string(),
+                        id.span,
+                        func.r#async && exported_action,
+                        &func.params,
+                        body,
+                        component,
+                    );
+                }
+            }
+            Declaration::VariableDeclaration(var_decl) => {
+                for declarator in var_decl.declarations.iter() {
+                    let (BindingPatternKind::BindingIdentifier(ident), Some(init)) =
+                        (&declarator.id.kind, &declarator.init)
+                    else {
+                        continue;
+                    };
+                    self.collect_from_expression(
+                        ident.name.as_str(),
+                        ident.span,
+                        init,
+                        exported_action || self.exports_local(&ident.name),
+                        component,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether `name` is a top-level binding of a "use server" module exported by name
+    fn exports_local(&self, name: &str) -> bool {
+        self.depth == 0 && self.exported_locals.contains(name)
+    }
+
+    /// Visit the functions an expression evaluates to or passes along, naming them `name` unless
+    /// they sit in an object property
+    fn collect_from_expression(
+        &mut self,
+        name: &str,
+        span: Span,
+        expr: &Expression,
+        exported_action: bool,
+        component: Option<&str>,
+    ) {
+        match expr.get_inner_expression() {
+            Expression::ArrowFunctionExpression(arrow) => self.visit_function(
+                name.to_string(),
+                span,
+                arrow.r#async && exported_action,
+                &arrow.params,
+                &arrow.body,
+                component,
+            ),
+            Expression::FunctionExpression(func) => {
+                if let Some(body) = &func.body {
+                    self.visit_function(
+                        name.to_string(),
+                        span,
+                        func.r#async && exported_action,
+                        &func.params,
+                        body,
+                        component,
+                    );
+                }
+            }
+            // Wrappers (`useCallback(async () => {})`) and callbacks (`items.map(item => {})`)
+            Expression::CallExpression(call) => {
+                for argument in call.arguments.iter() {
+                    if let Some(argument) = argument.as_expression() {
+                        self.collect_from_expression(name, span, argument, false, component);
+                    }
+                }
+            }
+            Expression::ObjectExpression(object) => {
+                for property in object.properties.iter() {
+                    if let ObjectPropertyKind::ObjectProperty(property) = property
+                        && let Some(key) = property.key.static_name()
+                    {
+                        self.collect_from_expression(
+                            &key,
+                            property.key.span(),
+                            &property.value,
+                            false,
+                            component,
+                        );
+                    }
+                }
+            }
+            Expression::ConditionalExpression(conditional) => {
+                self.collect_from_expression(name, span, &conditional.consequent, false, component);
+                self.collect_from_expression(name, span, &conditional.alternate, false, component);
+            }
+            Expression::LogicalExpression(logical) => {
+                self.collect_from_expression(name, span, &logical.right, false, component);
+            }
+            _ => {}
+        }
+    }
+
+    /// Record the function if it's an action, then look for inline actions declared in its body
+    fn visit_function(
+        &mut self,
+        name: String,
+        span: Span,
+        exported_action: bool,
+        params: &FormalParameters,
+        body: &FunctionBody,
+        component: Option<&str>,
+    ) {
+        let has_directive = body
+            .directives
+            .iter()
+            .any(|directive| directive.directive == "use server");
+
+        if exported_action || has_directive {
+            if self.depth > 0 {
+                self.nested.push((
+                    self.actions.len(),
+                    Span::new(params.span.start, body.span.end),
+                ));
+            }
+            self.actions.push(ServerAction {
+                name: name.clone(),
+                span,
+                parameter_count: params.items.len() as u32 + u32::from(params.rest.is_some()),
+                uses_form_data: params.items.iter().any(is_form_data_param),
+                component: component.map(str::to_string),
+                binds: Vec::new(),
+                captures: Vec::new(),
+            });
+        }
+
+        // Actions declared in a component's body belong to it, however deep they're nested
+        let component = if self.components.contains(name.as_str()) {
+            Some(name.as_str())
+        } else {
+            component
+        };
+        self.depth += 1;
+        self.collect_from_statements(&body.statements, &name, span, component);
+        self.depth -= 1;
+    }
+
+    /// Look for inline actions in a function body. Functions that are returned or make up an
+    /// arrow's expression body are named after the enclosing function (`owner`), which is the
+    /// factory making them
+    fn collect_from_statements(
+        &mut self,
+        statements: &[Statement],
+        owner: &str,
+        owner_span: Span,
+        component: Option<&str>,
+    ) {
+        for statement in statements {
+            if let Some(declaration) = statement.as_declaration() {
+                self.collect_from_declaration(declaration, false, component);
+                continue;
+            }
+            match statement {
+                Statement::ReturnStatement(stmt) => {
+                    if let Some(argument) = &stmt.argument {
+                        self.collect_from_expression(owner, owner_span, argument, false, component);
+                    }
+                }
+                Statement::ExpressionStatement(stmt) => {
+                    self.collect_from_expression(
+                        owner,
+                        owner_span,
+                        &stmt.expression,
+                        false,
+                        component,
+                    );
+                }
+                Statement::BlockStatement(block) => {
+                    self.collect_from_statements(&block.body, owner, owner_span, component)
+                }
+                Statement::IfStatement(stmt) => {
+                    self.collect_from_statements(
+                        std::slice::from_ref(&stmt.consequent),
+                        owner,
+                        owner_span,
+                        component,
+                    );
+                    if let Some(alternate) = &stmt.alternate {
+                        self.collect_from_statements(
+                            std::slice::from_ref(alternate),
+                            owner,
+                            owner_span,
+                            component,
+                        );
+                    }
+                }
+                Statement::TryStatement(stmt) => {
+                    self.collect_from_statements(&stmt.block.body, owner, owner_span, component);
+                    if let Some(handler) = &stmt.handler {
+                        self.collect_from_statements(
+                            &handler.body.body,
+                            owner,
+                            owner_span,
+                            component,
+                        );
+                    }
+                    if let Some(finalizer) = &stmt.finalizer {
+                        self.collect_from_statements(&finalizer.body, owner, owner_span, component);
+                    }
+                }
+                Statement::ForStatement(stmt) => self.collect_from_statements(
+                    std::slice::from_ref(&stmt.body),
+                    owner,
+                    owner_span,
+                    component,
+                ),
+                Statement::ForInStatement(stmt) => self.collect_from_statements(
+                    std::slice::from_ref(&stmt.body),
+                    owner,
+                    owner_span,
+                    component,
+                ),
+                Statement::ForOfStatement(stmt) => self.collect_from_statements(
+                    std::slice::from_ref(&stmt.body),
+                    owner,
+                    owner_span,
+                    component,
+                ),
+                Statement::WhileStatement(stmt) => self.collect_from_statements(
+                    std::slice::from_ref(&stmt.body),
+                    owner,
+                    owner_span,
+                    component,
+                ),
+                Statement::SwitchStatement(stmt) => {
+                    for case in stmt.cases.iter() {
+                        self.collect_from_statements(
+                            &case.consequent,
+                            owner,
+                            owner_span,
+                            component,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Find `name.bind(...)` calls, as (name, call span, bound argument count). The first argument is
+/// the `this` value, not a bound argument
+fn collect_binds(statements: &[Statement]) -> Vec<(String, Span, u32)> {
+    let mut collector = BindCollector { binds: Vec::new() };
+    for statement in statements {
+        collector.visit_statement(statement);
+    }
+    collector.binds
+}
+
+struct BindCollector {
+    binds: Vec<(String, Span, u32)>,
+}
+
+impl<'a> Visit<'a> for BindCollector {
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        if let Expression::StaticMemberExpression(member) = &call.callee
+            && member.property.name == "bind"
+            && let Expression::Identifier(ident) = &member.object
+        {
+            self.binds.push((
+                ident.name.to_string(),
+                call.span,
+                call.arguments.len().saturating_sub(1) as u32,
+            ));
+        }
+        walk::walk_call_expression(self, call);
+    }
+}
+
+/// Local bindings exported through `export { name }` or `export default name`
+fn exported_locals(statements: &[Statement]) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for statement in statements {
+        match statement {
+            Statement::ExportNamedDeclaration(export_decl)
+                if export_decl.source.is_none() && !export_decl.export_kind.is_type() =>
+            {
+                names.extend(
+                    export_decl
+                        .specifiers
+                        .iter()
+                        .filter(|specifier| !specifier.export_kind.is_type())
+                        .map(|specifier| crate::module_export_name(&specifier.local)),
+                );
+            }
+            Statement::ExportDefaultDeclaration(export_decl) => {
+                if let Some(Expression::Identifier(ident)) = export_decl.declaration.as_expression()
+                {
+                    names.insert(ident.name.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Fill in the variables each nested action closes over: bindings of the enclosing functions,
+/// declared outside the action and referenced as values inside it. References are resolved
+/// through scopes, so a parameter or local of the action, or of a function nested in it,
+/// shadowing an outer name doesn't hide that name's other references
+fn collect_captures(program: &Program, actions: &mut [ServerAction], nested: &[(usize, Span)]) {
+    let semantic = SemanticBuilder::new().build(program).semantic;
+    let scoping = semantic.scoping();
+    let root_scope = scoping.root_scope_id();
+
+    for &(index, action_span) in nested {
+        let mut captures = scoping
+            .symbol_ids()
+            .filter(|&symbol| {
+                scoping.symbol_scope_id(symbol) != root_scope
+                    && !action_span.contains_inclusive(scoping.symbol_span(symbol))
+            })
+            .filter_map(|symbol| {
+                let first_reference = scoping
+                    .get_resolved_reference_ids(symbol)
+                    .iter()
+                    .map(|&reference| scoping.get_reference(reference))
+                    .filter(|reference| reference.is_value())
+                    .map(|reference| semantic.nodes().kind(reference.node_id()).span())
+                    .filter(|&span| action_span.contains_inclusive(span))
+                    .min_by_key(|span| span.start)?;
+                Some((scoping.symbol_name(symbol).to_string(), first_reference))
+            })
+            .collect::<Vec<_>>();
+        captures.sort_by_key(|(_, span)| span.start);
+        actions[index].captures = captures;
+    }
+}
+
+fn is_form_data_param(param: &FormalParameter) -> bool {
+    let annotated = param
+        .pattern
+        .type_annotation
+        .as_ref()
+        .is_some_and(|annotation| match &annotation.type_annotation {
+            TSType::TSTypeReference(reference) => matches!(
+                &reference.type_name,
+                TSTypeName::IdentifierReference(ident) if ident.name == "FormData"
+            ),
+            _ => false,
+        });
+    let named = matches!(
+        &param.pattern.kind,
+        BindingPatternKind::BindingIdentifier(ident) if ident.name == "formData"
+    );
+    annotated || named
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    fn collect_actions(source: &str, components: &[&str]) -> Vec<ServerAction> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let module_use_server = ret
+            .program
+            .directives
+            .iter()
+            .any(|directive| directive.directive == "use server");
+        let components = components.iter().copied().collect::<HashSet<_>>();
+        collect_server_actions(&ret.program, module_use_server, &components)
+    }
+
+    fn collect(source: &str) -> Vec<(String, u32, bool)> {
+        collect_actions(source, &[])
+            .into_iter()
+            .map(|action| (action.name, action.parameter_count, action.uses_form_data))
+            .collect()
+    }
+
+    #[test]
+    fn test_collect_module_level_actions() {
+        let source = r#"
+"use server";
+export async function createPost(formData: FormData) {}
+export const deletePost = async (id: string, ...rest: unknown[]) => {};
+export function helper() {}
+async function internal() {}
+        "#;
+
+        assert_eq!(
+            collect(source),
+            vec![
+                ("createPost".to_string(), 1, true),
+                ("deletePost".to_string(), 2, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_actions_exported_by_name() {
+        let source = r#"
+"use server";
+async function create(formData: FormData) {}
+const update = async (id: string, formData: FormData) => {};
+async function internal() {}
+function sync() {}
+export { create, update as updatePost, sync };
+        "#;
+
+        assert_eq!(
+            collect(source),
+            vec![
+                ("create".to_string(), 1, true),
+                ("update".to_string(), 2, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_action_exported_as_default() {
+        let source = r#"
+"use server";
+async function create(formData: FormData) {}
+export default create;
+        "#;
+
+        assert_eq!(collect(source), vec![("create".to_string(), 1, true)]);
+        // Only in "use server" modules
+        assert!(collect(&source.replace(r#""use server";"#, "")).is_empty());
+    }
+
+    #[test]
+    fn test_collect_inline_actions() {
+        let source = r#"
+export default function Page() {
+  async function save(prevState, formData) {
+    "use server";
+  }
+  const reset = async () => {
+    "use server";
+  };
+  return <form action={save} />;
+}
+        "#;
+
+        assert_eq!(
+            collect(source),
+            vec![
+                ("save".to_string(), 2, true),
+                ("reset".to_string(), 0, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_no_actions_without_directive() {
+        let source = "export async function createPost(formData: FormData) {}";
+
+        assert!(collect(source).is_empty());
+    }
+
+    #[test]
+    fn test_collect_nested_inline_actions() {
+        let source = r#"
+export default function Page({ items }) {
+  if (items.length > 0) {
+    const clear = async () => {
+      "use server";
+    };
+  }
+  const rows = items.map((item) => {
+    const remove = useCallback(async (formData) => {
+      "use server";
+    }, []);
+    return <Row remove={remove} />;
+  });
+  const makeRename = (id) => async (formData: FormData) => {
+    "use server";
+  };
+  const handlers = {
+    archive: async function () {
+      "use server";
+    },
+  };
+  return <List rows={rows} />;
+}
+
+function helper() {
+  return async () => {
+    "use server";
+  };
+}
+        "#;
+
+        let actions = collect_actions(source, &["Page"])
+            .into_iter()
+            .map(|action| (action.name, action.component, action.uses_form_data))
+            .collect::<Vec<_>>();
+
+        let page = Some("Page".to_string());
+        assert_eq!(
+            actions,
+            vec![
+                ("clear".to_string(), page.clone(), false),
+                ("remove".to_string(), page.clone(), true),
+                ("makeRename".to_string(), page.clone(), true),
+                ("archive".to_string(), page, false),
+                ("helper".to_string(), None, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_binds_and_captures() {
+        let source = r#"
+"use server";
+const table = "posts";
+export async function publish(id: string) {}
+
+export default function Post({ post, author }) {
+  const [draft, setDraft] = useState(post.body);
+  async function save(formData) {
+    "use server";
+    const body = formData.get("body");
+    const tags = post.tags.map((tag) => tag.trim());
+    await db.update(table, post.id, { body, author, tags });
+  }
+  const publishPost = publish.bind(null, post.id);
+  return <form action={save.bind(null)} />;
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let components = HashSet::from(["Post"]);
+        let actions = collect_server_actions(&ret.program, true, &components);
+
+        let publish = &actions[0];
+        assert_eq!(publish.name, "publish");
+        assert!(publish.captures.is_empty());
+        assert_eq!(
+            publish
+                .binds
+                .iter()
+                .map(|(span, count)| (&source[span.start as usize..span.end as usize], *count))
+                .collect::<Vec<_>>(),
+            vec![("publish.bind(null, post.id)", 1)]
+        );
+
+        let save = &actions[1];
+        assert_eq!(save.name, "save");
+        assert_eq!(save.binds.len(), 1);
+        assert_eq!(save.binds[0].1, 0);
+        // Locals, nested function parameters and module-level bindings aren't captures
+        assert_eq!(
+            save.captures
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["post", "author"]
+        );
+    }
+
+    #[test]
+    fn test_captures_resolve_through_scopes() {
+        let source = r#"
+export default function Page({ secret, id }) {
+  async function reveal() {
+    "use server";
+    const mask = (secret: string) => secret.replace(/./g, "*");
+    await log(mask(secret));
+    {
+      const id = "local";
+      await log(id);
+    }
+  }
+  return <form action={reveal} />;
+}
+        "#;
+
+        let actions = collect_actions(source, &["Page"]);
+
+        // The arrow's `secret` parameter shadows only inside the arrow, and the block's `id`
+        // makes no reference to the outer one
+        assert_eq!(
+            actions[0]
+                .captures
+                .iter()
+                .map(|(name, span)| (
+                    name.as_str(),
+                    &source[span.start as usize..span.end as usize]
+                ))
+                .collect::<Vec<_>>(),
+            vec![("secret", "secret")]
+        );
+        let span = actions[0].captures[0].1;
+        assert!(source[..span.start as usize].ends_with("log(mask("));
+    }
+}
@@ -0,0 +1,201 @@
+use crate::analyze_react_boundary::check::types;
+use oxc::ast::Comment;
+use oxc::ast::ast::{BindingPatternKind, Declaration, ExportDefaultDeclarationKind, Statement};
+use oxc::span::{GetSpan, Span};
+
+/// Tag of the comments that override detection for the declaration below them
+const ANNOTATION_TAG: &str = "@react-boundary";
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// A top-level declaration annotated with `/** @react-boundary <value> */` or
+/// `// @react-boundary <value>`
+pub(crate) struct Annotation {
+    /// Local name of the declaration
+    pub(crate) name: String,
+    /// Span of the declaration identifier
+    pub(crate) span: Span,
+    pub(crate) kind: types::BoundaryAnnotation,
+    /// How the declaration exports itself (`export const`, `export default function`), with the
+    /// span of the export statement
+    pub(crate) export: Option<(types::ExportKind, Span)>,
+}
+
+/// Collect the declarations annotated by the leading comment of their statement. `client` marks
+/// the declaration as a client component whether or not the heuristics detect it, and `ignore`
+/// suppresses it. Unknown values are ignored
+pub(crate) fn collect_annotations(
+    source_text: &str,
+    comments: &[Comment],
+    statements: &[Statement],
+) -> Vec<Annotation> {
+    let mut annotations = Vec::new();
+    if !source_text.contains(ANNOTATION_TAG) {
+        return annotations;
+    }
+
+    for statement in statements {
+        let start = statement.span().start;
+        // The comment closest to the statement wins
+        let Some(kind) = comments
+            .iter()
+            .filter(|comment| comment.is_leading() && comment.attached_to == start)
+            .filter_map(|comment| annotation_kind(comment.content_span().source_text(source_text)))
+            .next_back()
+        else {
+            continue;
+        };
+
+        let (declaration, export) = match statement {
+            Statement::ExportNamedDeclaration(export_decl) => (
+                export_decl.declaration.as_ref(),
+                Some((types::ExportKind::Named, export_decl.span)),
+            ),
+            Statement::ExportDefaultDeclaration(export_decl) => {
+                let id = match &export_decl.declaration {
+                    ExportDefaultDeclarationKind::FunctionDeclaration(func) => func.id.as_ref(),
+                    ExportDefaultDeclarationKind::ClassDeclaration(class) => class.id.as_ref(),
+                    _ => None,
+                };
+                if let Some(id) = id {
+                    annotations.push(Annotation {
+                        name: id.name.to_string(),
+                        span: id.span,
+                        kind,
+                        export: Some((types::ExportKind::Default, export_decl.span)),
+                    });
+                }
+                continue;
+            }
+            statement => (statement.as_declaration(), None),
+        };
+
+        let mut push = |name: &str, span: Span| {
+            annotations.push(Annotation {
+                name: name.to_string(),
+                span,
+                kind,
+                export,
+            });
+        };
+        match declaration {
+            Some(Declaration::VariableDeclaration(var_decl)) => {
+                for declarator in var_decl.declarations.iter() {
+                    if let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind {
+                        push(&ident.name, ident.span);
+                    }
+                }
+            }
+            Some(Declaration::FunctionDeclaration(func)) => {
+                if let Some(id) = &func.id {
+                    push(&id.name, id.span);
+                }
+            }
+            Some(Declaration::ClassDeclaration(class)) => {
+                if let Some(id) = &class.id {
+                    push(&id.name, id.span);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    annotations
+}
+
+/// Annotation of the declaration named `name` declared at `span`
+pub(crate) fn annotation_for(
+    annotations: &[Annotation],
+    name: &str,
+    span: Span,
+) -> Option<types::BoundaryAnnotation> {
+    annotations
+        .iter()
+        .find(|annotation| annotation.name == name && annotation.span == span)
+        .map(|annotation| annotation.kind)
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Parse the value following the tag in a comment's text
+fn annotation_kind(text: &str) -> Option<types::BoundaryAnnotation> {
+    let (_, rest) = text.split_once(ANNOTATION_TAG)?;
+    match rest.split_whitespace().next()? {
+        "client" => Some(types::BoundaryAnnotation::Client),
+        "ignore" => Some(types::BoundaryAnnotation::Ignore),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_collect_annotations() {
+        let source = r#"
+/** @react-boundary client */
+const Chart = lazyChart();
+
+/**
+ * Renders on the server only.
+ * @react-boundary ignore
+ */
+export function Skeleton() {
+  return <div />;
+}
+
+// @react-boundary client
+export default function Page() {
+  return <main />;
+}
+
+/** @react-boundary unknown */
+export const Card = () => <div />;
+
+/** Not an annotation */
+export const Badge = () => <span />;
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let annotations = collect_annotations(source, &ret.program.comments, &ret.program.body)
+            .into_iter()
+            .map(|annotation| {
+                (
+                    annotation.name,
+                    annotation.kind,
+                    annotation.export.map(|(kind, _)| kind),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            annotations,
+            vec![
+                ("Chart".to_string(), types::BoundaryAnnotation::Client, None),
+                (
+                    "Skeleton".to_string(),
+                    types::BoundaryAnnotation::Ignore,
+                    Some(types::ExportKind::Named)
+                ),
+                (
+                    "Page".to_string(),
+                    types::BoundaryAnnotation::Client,
+                    Some(types::ExportKind::Default)
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use crate::analyze_react_boundary::check::types;
+use crate::rules::Rule;
+use oxc::ast::ast::Statement;
+use oxc::span::Span;
+
+/// Asset types by extension. Stylesheets include CSS modules (`.module.css`)
+const ASSET_EXTENSIONS: [(&str, types::AssetKind); 24] = [
+    ("css", types::AssetKind::Stylesheet),
+    ("scss", types::AssetKind::Stylesheet),
+    ("sass", types::AssetKind::Stylesheet),
+    ("less", types::AssetKind::Stylesheet),
+    ("styl", types::AssetKind::Stylesheet),
+    ("pcss", types::AssetKind::Stylesheet),
+    ("svg", types::AssetKind::Image),
+    ("png", types::AssetKind::Image),
+    ("jpg", types::AssetKind::Image),
+    ("jpeg", types::AssetKind::Image),
+    ("gif", types::AssetKind::Image),
+    ("webp", types::AssetKind::Image),
+    ("avif", types::AssetKind::Image),
+    ("ico", types::AssetKind::Image),
+    ("bmp", types::AssetKind::Image),
+    ("json", types::AssetKind::Json),
+    ("woff", types::AssetKind::Font),
+    ("woff2", types::AssetKind::Font),
+    ("ttf", types::AssetKind::Font),
+    ("otf", types::AssetKind::Font),
+    ("mp4", types::AssetKind::Media),
+    ("webm", types::AssetKind::Media),
+    ("mp3", types::AssetKind::Media),
+    ("wav", types::AssetKind::Media),
+];
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Type of the asset an import specifier points at, judging by its extension, or `None` for
+/// modules. Vite's `?raw` and `?url` queries import any file as its contents or its URL, and
+/// `virtual:` specifiers name modules generated by plugins
+pub(crate) fn asset_kind(specifier: &str) -> Option<types::AssetKind> {
+    if specifier.starts_with("virtual:") {
+        return Some(types::AssetKind::Virtual);
+    }
+    if let Some((_, query)) = specifier.split_once('?') {
+        for parameter in query.split('&') {
+            match parameter {
+                "raw" => return Some(types::AssetKind::Raw),
+                "url" => return Some(types::AssetKind::Url),
+                _ => {}
+            }
+        }
+    }
+    let extension = extension(specifier)?;
+    ASSET_EXTENSIONS
+        .iter()
+        .find(|(candidate, _)| extension.eq_ignore_ascii_case(candidate))
+        .map(|(_, kind)| *kind)
+}
+
+/// Whether an import specifier points at a stylesheet, judging by its extension
+pub(crate) fn is_stylesheet(specifier: &str) -> bool {
+    asset_kind(specifier) == Some(types::AssetKind::Stylesheet)
+}
+
+/// Diagnose the stylesheet imports of a "use server" module. Server actions run on the server
+/// only, so the styles would either be dropped or pulled into the server bundle for nothing.
+/// Returns the messages with the span of the import source
+pub(crate) fn collect_server_stylesheet_diagnostics(
+    statements: &[Statement],
+) -> Vec<(Rule, String, Span)> {
+    statements
+        .iter()
+        .filter_map(|statement| {
+            let Statement::ImportDeclaration(import_decl) = statement else {
+                return None;
+            };
+            let source = import_decl.source.value.as_str();
+            is_stylesheet(source).then(|| {
+                (
+                    Rule::StylesheetInServerAction,
+                    format!(
+                        "Stylesheet \"{}\" is imported in a \"use server\" module. Server actions \
+                         should not pull in client assets",
+                        source
+                    ),
+                    import_decl.source.span,
+                )
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Extension of the last path segment, ignoring query strings and fragments
+/// (`./icon.svg?react`, `./styles.css#theme`). A leading `#` starts a subpath import, not a
+/// fragment
+fn extension(specifier: &str) -> Option<&str> {
+    let path = specifier.split('?').next().unwrap_or(specifier);
+    let path = match path.char_indices().skip(1).find(|(_, c)| *c == '#') {
+        Some((index, _)) => &path[..index],
+        None => path,
+    };
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let (stem, extension) = file_name.rsplit_once('.')?;
+    (!stem.is_empty()).then_some(extension)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_is_stylesheet() {
+        assert!(is_stylesheet("./globals.css"));
+        assert!(is_stylesheet("./button.module.scss"));
+        assert!(is_stylesheet("@acme/ui/styles.CSS"));
+        assert!(is_stylesheet("./theme.less?inline"));
+        assert!(is_stylesheet("#styles/theme.css"));
+        assert!(!is_stylesheet("./css"));
+        assert!(!is_stylesheet("./.css"));
+        assert!(!is_stylesheet("styled-components"));
+        assert!(!is_stylesheet("./actions.ts"));
+    }
+
+    #[test]
+    fn test_asset_kind() {
+        assert_eq!(
+            asset_kind("./logo.svg?react"),
+            Some(types::AssetKind::Image)
+        );
+        assert_eq!(
+            asset_kind("../public/hero.JPG"),
+            Some(types::AssetKind::Image)
+        );
+        assert_eq!(
+            asset_kind("./messages/en.json"),
+            Some(types::AssetKind::Json)
+        );
+        assert_eq!(
+            asset_kind("@fontsource/inter/files/inter.woff2"),
+            Some(types::AssetKind::Font)
+        );
+        assert_eq!(asset_kind("./intro.mp4"), Some(types::AssetKind::Media));
+        assert_eq!(
+            asset_kind("./button.module.css"),
+            Some(types::AssetKind::Stylesheet)
+        );
+        assert_eq!(
+            asset_kind("./shaders/blur.glsl?raw"),
+            Some(types::AssetKind::Raw)
+        );
+        assert_eq!(asset_kind("./globals.css?url"), Some(types::AssetKind::Url));
+        assert_eq!(
+            asset_kind("./worker.ts?worker&url"),
+            Some(types::AssetKind::Url)
+        );
+        assert_eq!(
+            asset_kind("virtual:pwa-register/react"),
+            Some(types::AssetKind::Virtual)
+        );
+        assert_eq!(asset_kind("./button"), None);
+        assert_eq!(asset_kind("./button.tsx"), None);
+        assert_eq!(asset_kind("lodash.debounce"), None);
+    }
+
+    #[test]
+    fn test_collect_server_stylesheet_diagnostics() {
+        let source = r#"
+"use server";
+import "./globals.css";
+import styles from "./form.module.css";
+import { db } from "./db";
+
+export async function save(data) {
+  await db.insert(data);
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let diagnostics = collect_server_stylesheet_diagnostics(&ret.program.body);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].1,
+            "Stylesheet \"./globals.css\" is imported in a \"use server\" module. Server actions \
+             should not pull in client assets"
+        );
+        assert!(diagnostics[1].1.contains("./form.module.css"));
+    }
+}
@@ -0,0 +1,260 @@
+use oxc::span::Span;
+
+/// Extension of Astro components
+pub(crate) const ASTRO_EXTENSION: &str = "astro";
+
+/// Fence opening and closing the component script
+const FENCE: &str = "---";
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// A framework component rendered with a `client:*` directive, which hydrates it in the browser
+/// as an island
+pub(crate) struct Island {
+    /// Tag name of the component, dotted for namespaced components (`UI.Counter`)
+    pub(crate) name: String,
+    /// `client:load`, `client:visible`, ...
+    pub(crate) directive: String,
+    pub(crate) directive_span: Span,
+    /// Span of the opening tag
+    pub(crate) span: Span,
+}
+
+/// Blank out everything of an Astro component but its component script, the TypeScript between
+/// the leading `---` fences, so the rest of the analysis can parse it as a module. Like
+/// `strip_flow_syntax`, replacements keep the length of the source and its line breaks, so spans
+/// of the parsed program still point into the original text
+pub(crate) fn frontmatter_source(source: &str) -> String {
+    let script = frontmatter(source);
+
+    source
+        .char_indices()
+        .map(|(i, ch)| match script {
+            Some((start, end)) if (start..end).contains(&i) => ch.to_string(),
+            _ if matches!(ch, '\n' | '\r') => ch.to_string(),
+            // Keep multi-byte characters as long as they were
+            _ => " ".repeat(ch.len_utf8()),
+        })
+        .collect()
+}
+
+/// Collect the components of the template rendered with a `client:*` directive
+/// (`<Counter client:load />`). Components are told apart from HTML elements by their capitalized
+/// or dotted tag names. HTML comments and the contents of `<script>` and `<style>` elements are
+/// skipped
+pub(crate) fn collect_islands(source: &str) -> Vec<Island> {
+    let bytes = source.as_bytes();
+    let mut islands = Vec::new();
+    let mut i = frontmatter(source).map_or(0, |(_, end)| end + FENCE.len());
+
+    while let Some(offset) = source[i..].find('<') {
+        let start = i + offset;
+        let rest = &source[start + 1..];
+        if rest.starts_with("!--") {
+            i = rest
+                .find("-->")
+                .map_or(source.len(), |end| start + 1 + end + 3);
+            continue;
+        }
+
+        let name_len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '.' | '-')))
+            .unwrap_or(rest.len());
+        let name = &rest[..name_len];
+        let end = tag_end(bytes, start + 1 + name_len);
+
+        if matches!(name, "script" | "style") {
+            let closing = format!("</{}", name);
+            i = source[end..]
+                .find(&closing)
+                .map_or(source.len(), |offset| end + offset);
+            continue;
+        }
+
+        let is_component = name.starts_with(|c: char| c.is_ascii_uppercase()) || name.contains('.');
+        if is_component
+            && let Some((directive, directive_start)) =
+                attribute_names(source, start + 1 + name_len, end)
+                    .into_iter()
+                    .find(|(attribute, _)| attribute.starts_with("client:"))
+        {
+            islands.push(Island {
+                name: name.to_string(),
+                directive: directive.to_string(),
+                directive_span: Span::new(
+                    directive_start as u32,
+                    (directive_start + directive.len()) as u32,
+                ),
+                span: Span::new(start as u32, end as u32),
+            });
+        }
+        i = end.max(start + 1);
+    }
+
+    islands
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Byte range of the component script: from after the opening fence's line to the start of the
+/// closing fence's line
+fn frontmatter(source: &str) -> Option<(usize, usize)> {
+    let trimmed = source.trim_start_matches('\u{feff}').trim_start();
+    if !trimmed.starts_with(FENCE) {
+        return None;
+    }
+    let after_fence = source.len() - trimmed.len() + FENCE.len();
+    let start = after_fence + source[after_fence..].find('\n')? + 1;
+
+    let mut line_start = start;
+    for line in source[start..].split_inclusive('\n') {
+        if line.trim_end() == FENCE {
+            return Some((start, line_start));
+        }
+        line_start += line.len();
+    }
+    None
+}
+
+/// Offset just past the `>` closing the tag whose attributes start at `from`, skipping quoted
+/// values and `{expression}` values
+fn tag_end(bytes: &[u8], from: usize) -> usize {
+    let mut i = from;
+    let mut depth = 0;
+    let mut quote = None;
+
+    while i < bytes.len() {
+        match (quote, bytes[i]) {
+            (Some(open), byte) if byte == open => quote = None,
+            (Some(_), _) => {}
+            (None, byte @ (b'"' | b'\'' | b'`')) => quote = Some(byte),
+            (None, b'{') => depth += 1,
+            (None, b'}') => depth -= 1,
+            (None, b'>') if depth <= 0 => return i + 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// Names of the attributes between `from` and `to`, with their offsets
+fn attribute_names(source: &str, from: usize, to: usize) -> Vec<(&str, usize)> {
+    let bytes = source.as_bytes();
+    let mut names = Vec::new();
+    let mut i = from;
+
+    while i < to {
+        match bytes[i] {
+            b'"' | b'\'' | b'`' => {
+                let quote = bytes[i];
+                i += 1;
+                while i < to && bytes[i] != quote {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'{' => {
+                let mut depth = 0;
+                while i < to {
+                    match bytes[i] {
+                        b'{' => depth += 1,
+                        b'}' => depth -= 1,
+                        _ => {}
+                    }
+                    i += 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+            }
+            byte if byte.is_ascii_whitespace() || matches!(byte, b'=' | b'/' | b'>') => i += 1,
+            _ => {
+                let start = i;
+                while i < to
+                    && !bytes[i].is_ascii_whitespace()
+                    && !matches!(bytes[i], b'=' | b'/' | b'>' | b'{' | b'"' | b'\'')
+                {
+                    i += 1;
+                }
+                names.push((&source[start..i], start));
+            }
+        }
+    }
+
+    names
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"---
+import Counter from "../components/Counter";
+import * as UI from "../components/ui";
+const { title } = Astro.props;
+---
+<!-- <Counter client:load /> -->
+<Layout title={title}>
+  <Counter initial={1 > 0 ? 1 : 0} client:load />
+  <UI.Dialog client:visible={{ rootMargin: "200px" }}>
+    <p>{title}</p>
+  </UI.Dialog>
+  <Static />
+  <button class="primary">Go</button>
+</Layout>
+<script>
+  document.querySelector("<Fake client:load>");
+</script>
+"#;
+
+    #[test]
+    fn test_frontmatter_source() {
+        let masked = frontmatter_source(SOURCE);
+
+        assert_eq!(masked.len(), SOURCE.len());
+        assert_eq!(masked.lines().count(), SOURCE.lines().count());
+        assert!(masked.starts_with("   \nimport Counter from"));
+        assert!(masked.contains("const { title } = Astro.props;\n   \n"));
+        assert!(!masked.contains("Layout"));
+
+        assert!(frontmatter_source("<div />").trim().is_empty());
+    }
+
+    #[test]
+    fn test_collect_islands() {
+        let islands = collect_islands(SOURCE);
+
+        let summary = islands
+            .iter()
+            .map(|island| {
+                (
+                    island.name.as_str(),
+                    island.directive.as_str(),
+                    &SOURCE
+                        [island.directive_span.start as usize..island.directive_span.end as usize],
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("Counter", "client:load", "client:load"),
+                ("UI.Dialog", "client:visible", "client:visible"),
+            ]
+        );
+        let span = islands[0].span;
+        assert_eq!(
+            &SOURCE[span.start as usize..span.end as usize],
+            "<Counter initial={1 > 0 ? 1 : 0} client:load />"
+        );
+    }
+}
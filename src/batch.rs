@@ -0,0 +1,203 @@
+use crate::analyze_react_boundary::check::types;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Analyze every file in order. A failure in one file doesn't stop the batch, it is reported in
+/// that file's result instead. Cancellation is checked before each file; once the host cancels,
+/// the remaining files are reported as cancelled without being analyzed.
+///
+/// `report_progress` is called with the number of files done, the size of the batch and the path
+/// of the file about to be analyzed before each file, so hosts indexing a workspace can show how
+/// far along they are.
+pub(crate) fn analyze_batch(
+    files: Vec<types::SourceFile>,
+    is_cancelled: &dyn Fn() -> bool,
+    report_progress: &dyn Fn(u32, u32, &str),
+) -> Vec<types::FileAnalysis> {
+    analyze_batch_with_options(
+        files,
+        &crate::default_options(),
+        is_cancelled,
+        report_progress,
+    )
+}
+
+/// `analyze_batch` with the same options applied to every file
+pub(crate) fn analyze_batch_with_options(
+    files: Vec<types::SourceFile>,
+    options: &types::AnalyzeOptions,
+    is_cancelled: &dyn Fn() -> bool,
+    report_progress: &dyn Fn(u32, u32, &str),
+) -> Vec<types::FileAnalysis> {
+    let total = files.len() as u32;
+    let mut cancelled = None;
+    files
+        .into_iter()
+        .enumerate()
+        .map(|(done, file)| {
+            if cancelled.is_none() {
+                cancelled = crate::check_cancelled(is_cancelled).err();
+            }
+            let analysis = if let Some(error) = &cancelled {
+                Err(error.clone())
+            } else {
+                report_progress(done as u32, total, &file.path);
+                crate::analyze_source_with_options(
+                    file.content,
+                    file.extension,
+                    options,
+                    is_cancelled,
+                )
+            };
+            types::FileAnalysis {
+                analysis,
+                path: file.path,
+            }
+        })
+        .collect()
+}
+
+/// Split a batch into at most `workers` partitions of roughly equal total size, so the host can
+/// feed each partition to its own component instance.
+///
+/// Returns the indices of the files in each partition, in their original order. Empty partitions
+/// are dropped.
+pub(crate) fn partition_batch(sizes: &[u64], workers: u32) -> Vec<Vec<u32>> {
+    let workers = (workers.max(1) as usize).min(sizes.len());
+    let mut partitions: Vec<(u64, Vec<u32>)> = vec![(0, Vec::new()); workers];
+
+    // Largest files first, each going to the least loaded partition
+    let mut order = (0..sizes.len() as u32).collect::<Vec<_>>();
+    order.sort_by_key(|&index| std::cmp::Reverse(sizes[index as usize]));
+
+    for index in order {
+        if let Some((load, partition)) = partitions.iter_mut().min_by_key(|(load, _)| *load) {
+            *load += sizes[index as usize];
+            partition.push(index);
+        }
+    }
+
+    partitions
+        .into_iter()
+        .filter(|(_, partition)| !partition.is_empty())
+        .map(|(_, mut partition)| {
+            partition.sort_unstable();
+            partition
+        })
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::source_file;
+
+    #[test]
+    fn test_analyze_batch_keeps_order_and_errors() {
+        let files = vec![
+            source_file("a.tsx", "export const A = () => <div />;"),
+            source_file("broken.tsx", "const x = {{{"),
+            source_file("b.tsx", "export const B = () => <div />;"),
+        ];
+
+        let results = analyze_batch(files, &|| false, &|_, _, _| {});
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].path, "a.tsx");
+        assert_eq!(
+            results[0].analysis.as_ref().unwrap().components[0].name,
+            "A"
+        );
+        assert!(
+            results[1].analysis.is_err(),
+            "Broken file should not stop the batch"
+        );
+        assert_eq!(
+            results[2].analysis.as_ref().unwrap().components[0].name,
+            "B"
+        );
+    }
+
+    #[test]
+    fn test_analyze_batch_reports_progress() {
+        let files = vec![
+            source_file("a.tsx", "export const A = () => <div />;"),
+            source_file("b.tsx", "export const B = () => <div />;"),
+        ];
+        let progress = std::cell::RefCell::new(Vec::new());
+
+        analyze_batch(files, &|| false, &|done, total, path| {
+            progress.borrow_mut().push((done, total, path.to_string()))
+        });
+
+        assert_eq!(
+            progress.into_inner(),
+            vec![(0, 2, "a.tsx".to_string()), (1, 2, "b.tsx".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_analyze_batch_stops_when_cancelled() {
+        let files = vec![
+            source_file("a.tsx", "export const A = () => <div />;"),
+            source_file("b.tsx", "export const B = () => <div />;"),
+            source_file("c.tsx", "export const C = () => <div />;"),
+        ];
+        let cancelled = std::cell::Cell::new(false);
+        let progress = std::cell::RefCell::new(Vec::new());
+
+        // The host cancels once the second file starts
+        let results = analyze_batch(files, &|| cancelled.get(), &|done, _, path| {
+            progress.borrow_mut().push(path.to_string());
+            cancelled.set(done == 1);
+        });
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].analysis.is_ok());
+        for result in &results[1..] {
+            assert_eq!(result.analysis.as_ref().unwrap_err(), "Analysis cancelled");
+        }
+        // The remaining file is skipped without being started
+        assert_eq!(progress.into_inner(), vec!["a.tsx", "b.tsx"]);
+    }
+
+    #[test]
+    fn test_partition_batch_balances_sizes() {
+        let partitions = partition_batch(&[100, 10, 10, 60, 40], 2);
+
+        assert_eq!(partitions.len(), 2);
+        // 100 + 10 vs 60 + 40 + 10
+        assert_eq!(partitions[0], vec![0, 1]);
+        assert_eq!(partitions[1], vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_partition_batch_covers_every_file_once() {
+        let sizes = (0..50).map(|i| (i * 37 % 11) as u64).collect::<Vec<_>>();
+
+        let partitions = partition_batch(&sizes, 4);
+
+        let mut indices = partitions.into_iter().flatten().collect::<Vec<_>>();
+        indices.sort_unstable();
+        assert_eq!(indices, (0..50).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_partition_batch_more_workers_than_files() {
+        let partitions = partition_batch(&[5, 5], 8);
+
+        // Should not return empty partitions
+        assert_eq!(partitions, vec![vec![0], vec![1]]);
+    }
+
+    #[test]
+    fn test_partition_batch_empty() {
+        assert!(partition_batch(&[], 4).is_empty());
+    }
+}
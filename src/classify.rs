@@ -0,0 +1,116 @@
+use crate::analyze_react_boundary::check::types;
+use oxc::allocator::Allocator;
+use oxc::parser::Parser;
+use oxc::span::SourceType;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Classify a file by its syntax only: whether it is an ES module or a script, whether it runs in
+/// strict mode, and whether it uses JSX or TypeScript syntax.
+///
+/// None of the analysis passes run, so this is much cheaper than `analyze` for hosts that only
+/// need to route files. Whether a module or script is decided by the presence of import/export
+/// syntax, not by the extension.
+pub(crate) fn classify_module(
+    content: Vec<u8>,
+    extension: &str,
+) -> Result<types::ModuleClassification, String> {
+    let source_text =
+        String::from_utf8(content).map_err(|e| format!("Invalid UTF-8 source: {}", e))?;
+    let source_type = SourceType::from_extension(extension)
+        .map_err(|e| format!("{}: {}", e.to_string().replace("\"", ""), extension))?
+        .with_unambiguous(true);
+
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, &source_text, source_type).parse();
+    if ret.panicked
+        && let Some(error) = ret.errors.first()
+    {
+        return Err(format!("Error: {}", error.message));
+    }
+    let program = ret.program;
+
+    let source_kind = if program.source_type.is_module() {
+        types::SourceKind::Module
+    } else {
+        types::SourceKind::Script
+    };
+    let directives = program
+        .directives
+        .iter()
+        .map(|directive| directive.directive.to_string())
+        .collect::<Vec<_>>();
+
+    Ok(types::ModuleClassification {
+        source_kind,
+        strict: program.source_type.is_module()
+            || directives.iter().any(|directive| directive == "use strict"),
+        has_jsx: crate::jsx_runtime::has_jsx_syntax(&program.body),
+        has_typescript: source_type.is_typescript()
+            && ret.errors.is_empty()
+            && has_typescript(&source_text, source_type),
+        directives,
+    })
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Whether a TypeScript file uses syntax of its own. Such a file doesn't parse as JavaScript, which
+/// covers every TypeScript-only construct without listing them
+fn has_typescript(source_text: &str, source_type: SourceType) -> bool {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type.with_javascript(true)).parse();
+    !ret.errors.is_empty()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(source: &str, extension: &str) -> types::ModuleClassification {
+        classify_module(source.as_bytes().to_vec(), extension).unwrap()
+    }
+
+    #[test]
+    fn test_classify_module_syntax() {
+        let result = classify(
+            "\"use client\";\nimport { Button } from \"./button\";\nexport const App = () => <Button />;",
+            "jsx",
+        );
+        assert_eq!(result.source_kind, types::SourceKind::Module);
+        assert!(result.strict);
+        assert!(result.has_jsx);
+        assert!(!result.has_typescript);
+        assert_eq!(result.directives, vec!["use client"]);
+
+        let result = classify("const x: number = 1;\nexport default x;", "ts");
+        assert_eq!(result.source_kind, types::SourceKind::Module);
+        assert!(!result.has_jsx);
+        assert!(result.has_typescript);
+
+        // A .tsx file without TypeScript-only syntax
+        let result = classify("export function App() { return <main />; }", "tsx");
+        assert!(result.has_jsx);
+        assert!(!result.has_typescript);
+    }
+
+    #[test]
+    fn test_classify_script() {
+        let result = classify("\"use strict\";\nmodule.exports = require(\"./a\");", "js");
+        assert_eq!(result.source_kind, types::SourceKind::Script);
+        assert!(result.strict);
+
+        let result = classify("window.x = 1;", "js");
+        assert_eq!(result.source_kind, types::SourceKind::Script);
+        assert!(!result.strict);
+        assert!(result.directives.is_empty());
+    }
+}
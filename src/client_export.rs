@@ -0,0 +1,324 @@
+use crate::analyze_react_boundary::check::types;
+use crate::rules::Rule;
+use oxc::ast::ast::{
+    BindingPatternKind, Declaration, ExportDefaultDeclarationKind, Expression,
+    ImportDeclarationSpecifier, Statement,
+};
+use oxc::ast_visit::{Visit, walk};
+use oxc::span::{GetSpan, Span};
+use std::collections::HashMap;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Exports of a "use client" module, which become the roots of the client boundary
+#[derive(Default)]
+pub(crate) struct ClientExports {
+    /// (exported name, kind, span of the exported name)
+    pub(crate) exports: Vec<(String, types::ClientExportKind, Span)>,
+    pub(crate) diagnostics: Vec<(Rule, String, Span)>,
+}
+
+/// Classify the exports of a "use client" module as components, plain values or re-exports.
+///
+/// `components` are the exported names of the components found in the module. Only components
+/// and serializable values should cross the boundary, so exports that reference bindings imported
+/// from server-only entry points are diagnosed.
+pub(crate) fn collect_client_exports(
+    statements: &[Statement],
+    components: &[String],
+) -> ClientExports {
+    let server_bindings = server_only_bindings(statements);
+    let local_values = local_values(statements);
+    let mut result = ClientExports::default();
+
+    // Name of the server-only module the expression references, if any
+    let server_reference = |expr: &Expression| -> Option<String> {
+        let mut finder = ServerReferenceFinder {
+            server_bindings: &server_bindings,
+            found: None,
+        };
+        finder.visit_expression(expr);
+        finder.found
+    };
+
+    let kind_of = |name: &str| {
+        if components.iter().any(|component| component == name) {
+            types::ClientExportKind::Component
+        } else {
+            types::ClientExportKind::Value
+        }
+    };
+
+    let mut push =
+        |name: String, kind: types::ClientExportKind, span: Span, server_module: Option<String>| {
+            if kind != types::ClientExportKind::Component
+                && let Some(module) = server_module
+            {
+                result.diagnostics.push((
+                    Rule::ClientExportServerReference,
+                    format!(
+                        "Export \"{}\" of a \"use client\" module references server-only \"{}\"",
+                        name, module
+                    ),
+                    span,
+                ));
+            }
+            result.exports.push((name, kind, span));
+        };
+
+    for statement in statements {
+        match statement {
+            Statement::ExportNamedDeclaration(export_decl) => {
+                if export_decl.export_kind.is_type() {
+                    continue;
+                }
+                match (&export_decl.declaration, &export_decl.source) {
+                    (Some(Declaration::FunctionDeclaration(func)), _) => {
+                        if let Some(id) = &func.id {
+                            push(id.name.to_string(), kind_of(&id.name), id.span, None);
+                        }
+                    }
+                    (Some(Declaration::ClassDeclaration(class)), _) => {
+                        if let Some(id) = &class.id {
+                            push(id.name.to_string(), kind_of(&id.name), id.span, None);
+                        }
+                    }
+                    (Some(Declaration::VariableDeclaration(var_decl)), _) => {
+                        for declarator in var_decl.declarations.iter() {
+                            if let BindingPatternKind::BindingIdentifier(ident) =
+                                &declarator.id.kind
+                            {
+                                push(
+                                    ident.name.to_string(),
+                                    kind_of(&ident.name),
+                                    ident.span,
+                                    declarator.init.as_ref().and_then(server_reference),
+                                );
+                            }
+                        }
+                    }
+                    (Some(_), _) => {}
+                    (None, Some(source)) => {
+                        let server_module = crate::signal::is_server_only_module(&source.value)
+                            .then(|| source.value.to_string());
+                        for specifier in export_decl.specifiers.iter() {
+                            if !specifier.export_kind.is_type() {
+                                push(
+                                    crate::module_export_name(&specifier.exported),
+                                    types::ClientExportKind::ReExport,
+                                    specifier.exported.span(),
+                                    server_module.clone(),
+                                );
+                            }
+                        }
+                    }
+                    (None, None) => {
+                        for specifier in export_decl.specifiers.iter() {
+                            if specifier.export_kind.is_type() {
+                                continue;
+                            }
+                            let local = crate::module_export_name(&specifier.local);
+                            let exported = crate::module_export_name(&specifier.exported);
+                            let server_module =
+                                server_bindings.get(&local).cloned().or_else(|| {
+                                    local_values
+                                        .get(&local)
+                                        .and_then(|init| server_reference(init))
+                                });
+                            push(
+                                exported.clone(),
+                                kind_of(&exported),
+                                specifier.exported.span(),
+                                server_module,
+                            );
+                        }
+                    }
+                }
+            }
+            Statement::ExportAllDeclaration(export_decl) => {
+                if export_decl.export_kind.is_type() {
+                    continue;
+                }
+                let (name, span) = match &export_decl.exported {
+                    Some(exported) => (crate::module_export_name(exported), exported.span()),
+                    None => ("*".to_string(), export_decl.source.span),
+                };
+                push(
+                    name,
+                    types::ClientExportKind::ReExport,
+                    span,
+                    crate::signal::is_server_only_module(&export_decl.source.value)
+                        .then(|| export_decl.source.value.to_string()),
+                );
+            }
+            Statement::ExportDefaultDeclaration(export_decl) => {
+                let server_module = match &export_decl.declaration {
+                    ExportDefaultDeclarationKind::FunctionDeclaration(_)
+                    | ExportDefaultDeclarationKind::ClassDeclaration(_) => None,
+                    declaration => declaration.as_expression().and_then(server_reference),
+                };
+                push(
+                    "default".to_string(),
+                    kind_of("default"),
+                    export_decl.span,
+                    server_module,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    result
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Finds the first identifier bound to an import of a server-only module
+struct ServerReferenceFinder<'c> {
+    /// Server-only module of each local binding
+    server_bindings: &'c HashMap<String, String>,
+    found: Option<String>,
+}
+
+impl<'a> Visit<'a> for ServerReferenceFinder<'_> {
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        if self.found.is_some() {
+            return;
+        }
+        if let Expression::Identifier(ident) = expr {
+            self.found = self.server_bindings.get(ident.name.as_str()).cloned();
+        }
+        walk::walk_expression(self, expr);
+    }
+}
+
+/// Local bindings imported from server-only entry points, mapped to the module they come from
+fn server_only_bindings(statements: &[Statement]) -> HashMap<String, String> {
+    let mut bindings = HashMap::new();
+
+    for statement in statements {
+        let Statement::ImportDeclaration(import_decl) = statement else {
+            continue;
+        };
+        if !crate::signal::is_server_only_module(&import_decl.source.value) {
+            continue;
+        }
+        for specifier in import_decl.specifiers.iter().flatten() {
+            let local = match specifier {
+                ImportDeclarationSpecifier::ImportSpecifier(spec) => &spec.local,
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => &spec.local,
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => &spec.local,
+            };
+            bindings.insert(local.name.to_string(), import_decl.source.value.to_string());
+        }
+    }
+
+    bindings
+}
+
+/// Initializers of top-level variables, for exports listed in `export { ... }`
+fn local_values<'s, 'a>(statements: &'s [Statement<'a>]) -> HashMap<String, &'s Expression<'a>> {
+    let mut values = HashMap::new();
+
+    for statement in statements {
+        let Statement::VariableDeclaration(var_decl) = statement else {
+            continue;
+        };
+        for declarator in var_decl.declarations.iter() {
+            if let (BindingPatternKind::BindingIdentifier(ident), Some(init)) =
+                (&declarator.id.kind, &declarator.init)
+            {
+                values.insert(ident.name.to_string(), init);
+            }
+        }
+    }
+
+    values
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    fn collect(source: &str, components: &[&str]) -> ClientExports {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let components = components.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        collect_client_exports(&ret.program.body, &components)
+    }
+
+    fn kinds(exports: &ClientExports) -> Vec<(&str, types::ClientExportKind)> {
+        exports
+            .exports
+            .iter()
+            .map(|(name, kind, _)| (name.as_str(), *kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_collect_components_values_and_re_exports() {
+        let source = r#"
+"use client";
+export function Button() { return <button />; }
+export const sizes = ["sm", "lg"];
+export type Size = "sm" | "lg";
+export { Icon } from "./icon";
+export * as utils from "./utils";
+const Card = () => <div />;
+export { Card as default };
+        "#;
+
+        let exports = collect(source, &["Button", "default"]);
+
+        assert_eq!(
+            kinds(&exports),
+            vec![
+                ("Button", types::ClientExportKind::Component),
+                ("sizes", types::ClientExportKind::Value),
+                ("Icon", types::ClientExportKind::ReExport),
+                ("utils", types::ClientExportKind::ReExport),
+                ("default", types::ClientExportKind::Component),
+            ]
+        );
+        assert!(exports.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_diagnose_values_referencing_server_code() {
+        let source = r#"
+"use client";
+import { renderToString } from "react-dom/server";
+export const render = (node) => renderToString(node);
+const config = { render: renderToString };
+export { config };
+export * from "server-only";
+        "#;
+
+        let exports = collect(source, &[]);
+
+        let messages = exports
+            .diagnostics
+            .iter()
+            .map(|(_, message, _)| message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "Export \"render\" of a \"use client\" module references server-only \"react-dom/server\"",
+                "Export \"config\" of a \"use client\" module references server-only \"react-dom/server\"",
+                "Export \"*\" of a \"use client\" module references server-only \"server-only\"",
+            ]
+        );
+    }
+}
@@ -1,6 +1,27 @@
 use oxc::ast::ast::TSTypeName::IdentifierReference;
-use oxc::ast::ast::{BindingPattern, Expression, Statement, TSType};
-use oxc::span::Span;
+use oxc::ast::ast::{
+    BindingPattern, BindingPatternKind, CallExpression, ChainElement, Declaration, Expression,
+    FormalParameters, FunctionBody, LogicalOperator, ObjectPropertyKind, Statement, TSType,
+    TSTypeName,
+};
+use oxc::ast_visit::{Visit, walk};
+use oxc::span::{GetSpan, Span};
+
+/// Type names that mark a binding or a return value as a React component
+const REACT_COMPONENT_TYPES: [&str; 12] = [
+    "FC",
+    "FunctionComponent",
+    "VFC",
+    "ReactElement",
+    "ReactNode",
+    "Component",
+    "ComponentType",
+    "ElementType",
+    "ForwardRefExoticComponent",
+    "MemoExoticComponent",
+    "NamedExoticComponent",
+    "LazyExoticComponent",
+];
 
 // ============================================================================
 // PUBLIC API
@@ -11,6 +32,7 @@ use oxc::span::Span;
 pub(crate) fn analyze_function_declaration(
     func_decl: &oxc::ast::ast::Function,
     jsx_runtime_identifiers: &std::collections::HashSet<String>,
+    component_types: &[String],
 ) -> Option<(String, Span)> {
     if let Some(id) = &func_decl.id {
         let name = id.name.to_string();
@@ -21,6 +43,7 @@ pub(crate) fn analyze_function_declaration(
             &func_decl.return_type,
             &func_decl.body,
             jsx_runtime_identifiers,
+            component_types,
         ) {
             return Some((name, id.span));
         }
@@ -28,12 +51,163 @@ pub(crate) fn analyze_function_declaration(
     None
 }
 
+/// Analyze a class declaration and return its name and span if it's a React class component:
+/// a PascalCase class extending `Component` or `PureComponent`, directly or through a namespace
+/// (`React.Component`). Decorators (`@observer class Foo extends Component`) don't matter.
+pub(crate) fn analyze_class_declaration(class: &oxc::ast::ast::Class) -> Option<(String, Span)> {
+    let id = class.id.as_ref()?;
+    if !id.name.chars().next().is_some_and(|c| c.is_uppercase()) {
+        return None;
+    }
+
+    let super_class = match class.super_class.as_ref()?.get_inner_expression() {
+        Expression::Identifier(ident) => ident.name.as_str(),
+        Expression::StaticMemberExpression(member) => member.property.name.as_str(),
+        _ => return None,
+    };
+    matches!(super_class, "Component" | "PureComponent").then(|| (id.name.to_string(), id.span))
+}
+
+/// Value of a default-exported expression, looking through the parentheses and sequence
+/// expressions compiled output wraps it in (`export default (0, function Page() {})`)
+pub(crate) fn default_export_value<'s, 'a>(expr: &'s Expression<'a>) -> &'s Expression<'a> {
+    let mut expr = expr.get_inner_expression();
+    while let Expression::SequenceExpression(sequence) = expr {
+        match sequence.expressions.last() {
+            Some(last) => expr = last.get_inner_expression(),
+            None => break,
+        }
+    }
+    expr
+}
+
+/// Collect the identifiers an expression selects between, as in `isTouch ? MobileNav : DesktopNav`
+/// or `CustomNav ?? DefaultNav`. Conditions are not candidates, only the selected branches are.
+pub(crate) fn conditional_candidates(expr: &Expression, candidates: &mut Vec<String>) {
+    match expr.get_inner_expression() {
+        Expression::Identifier(ident) => candidates.push(ident.name.to_string()),
+        Expression::ConditionalExpression(conditional) => {
+            conditional_candidates(&conditional.consequent, candidates);
+            conditional_candidates(&conditional.alternate, candidates);
+        }
+        Expression::LogicalExpression(logical) => {
+            // `a && B` selects B or a falsy value, `a || B` and `a ?? B` may select either side
+            if logical.operator != oxc::ast::ast::LogicalOperator::And {
+                conditional_candidates(&logical.left, candidates);
+            }
+            conditional_candidates(&logical.right, candidates);
+        }
+        _ => {}
+    }
+}
+
+/// Collect the names of the higher-order components defined at the top level: non-PascalCase
+/// functions whose first parameter is rendered by the component they build, as in
+/// `const withAuth = (C) => (props) => <C {...props} />`
+pub(crate) fn collect_local_hocs(
+    statements: &[Statement],
+    jsx_runtime_identifiers: &std::collections::HashSet<String>,
+) -> std::collections::HashSet<String> {
+    let mut hocs = std::collections::HashSet::new();
+    let mut visit = |name: &str, params: &FormalParameters, body: &FunctionBody| {
+        if !name.starts_with(|c: char| c.is_uppercase())
+            && renders_first_parameter(params, body, jsx_runtime_identifiers)
+        {
+            hocs.insert(name.to_string());
+        }
+    };
+
+    for statement in statements {
+        let declaration = match statement {
+            Statement::ExportNamedDeclaration(export_decl) => export_decl.declaration.as_ref(),
+            statement => statement.as_declaration(),
+        };
+        match declaration {
+            Some(Declaration::FunctionDeclaration(func)) => {
+                if let (Some(id), Some(body)) = (&func.id, &func.body) {
+                    visit(&id.name, &func.params, body);
+                }
+            }
+            Some(Declaration::VariableDeclaration(var_decl)) => {
+                for declarator in var_decl.declarations.iter() {
+                    let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind else {
+                        continue;
+                    };
+                    match declarator
+                        .init
+                        .as_ref()
+                        .map(|init| init.get_inner_expression())
+                    {
+                        Some(Expression::ArrowFunctionExpression(arrow)) => {
+                            visit(&ident.name, &arrow.params, &arrow.body);
+                        }
+                        Some(Expression::FunctionExpression(func)) => {
+                            if let Some(body) = &func.body {
+                                visit(&ident.name, &func.params, body);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    hocs
+}
+
+/// The PascalCase identifier a local higher-order component is applied to, as `Dashboard` in
+/// `withAuth(Dashboard)`
+pub(crate) fn hoc_application<'s, 'a>(
+    expr: &'s Expression<'a>,
+    hocs: &std::collections::HashSet<String>,
+) -> Option<&'s oxc::ast::ast::IdentifierReference<'a>> {
+    let Expression::CallExpression(call) = expr.get_inner_expression() else {
+        return None;
+    };
+    let Expression::Identifier(callee) = call.callee.get_inner_expression() else {
+        return None;
+    };
+    if !hocs.contains(callee.name.as_str()) {
+        return None;
+    }
+    match call
+        .arguments
+        .first()?
+        .as_expression()?
+        .get_inner_expression()
+    {
+        Expression::Identifier(ident) if ident.name.starts_with(|c: char| c.is_uppercase()) => {
+            Some(ident)
+        }
+        _ => None,
+    }
+}
+
+/// Every application of a local higher-order component in the module, as (wrapped component,
+/// span of its identifier)
+pub(crate) fn hoc_applications(
+    statements: &[Statement],
+    hocs: &std::collections::HashSet<String>,
+) -> Vec<(String, Span)> {
+    let mut finder = HocApplicationFinder {
+        hocs,
+        applications: Vec::new(),
+    };
+    for statement in statements {
+        finder.visit_statement(statement);
+    }
+    finder.applications
+}
+
 /// Main function to check if a variable declaration is a React component
 pub(crate) fn is_react_component(
     name: &str,
     binding: &BindingPattern,
     init: &Option<Expression>,
     jsx_runtime_identifiers: &std::collections::HashSet<String>,
+    component_types: &[String],
 ) -> bool {
     // Check 1: PascalCase naming convention (the first letter is uppercase)
     let is_pascal_case = name.chars().next().is_some_and(|c| c.is_uppercase());
@@ -43,7 +217,7 @@ pub(crate) fn is_react_component(
     }
 
     // Check 2: Has React type annotation
-    if has_react_type(binding) {
+    if has_react_type(binding, component_types) {
         return true;
     }
 
@@ -55,12 +229,74 @@ pub(crate) fn is_react_component(
     false
 }
 
+/// Function entries of a lookup table that render JSX, as (entry key, key span): object
+/// properties (`{ home: () => <Home /> }`) and `[key, value]` pairs, including those passed to a
+/// constructor (`new Map([["home", () => <Home />]])`). Entries holding elements or component
+/// references aren't components themselves
+pub(crate) fn config_entry_components(
+    init: &Expression,
+    jsx_runtime_identifiers: &std::collections::HashSet<String>,
+) -> Vec<(String, Span)> {
+    let renders = |value: &Expression| {
+        matches!(
+            value.get_inner_expression(),
+            Expression::ArrowFunctionExpression(_) | Expression::FunctionExpression(_)
+        ) && contains_jsx(value.get_inner_expression(), jsx_runtime_identifiers)
+    };
+
+    let mut entries = Vec::new();
+    match init.get_inner_expression() {
+        Expression::NewExpression(new) => {
+            for argument in new.arguments.iter() {
+                if let Some(argument) = argument.as_expression() {
+                    entries.extend(config_entry_components(argument, jsx_runtime_identifiers));
+                }
+            }
+        }
+        Expression::ObjectExpression(object) => {
+            for property in object.properties.iter() {
+                if let ObjectPropertyKind::ObjectProperty(property) = property
+                    && let Some(key) = property.key.static_name()
+                    && renders(&property.value)
+                {
+                    entries.push((key.to_string(), property.key.span()));
+                }
+            }
+        }
+        Expression::ArrayExpression(array) => {
+            for element in array.elements.iter() {
+                if let Some(Expression::ArrayExpression(pair)) = element.as_expression()
+                    && let [key, value] = pair.elements.as_slice()
+                    && let Some(Expression::StringLiteral(key)) = key.as_expression()
+                    && value.as_expression().is_some_and(renders)
+                {
+                    entries.push((key.value.to_string(), key.span));
+                }
+            }
+        }
+        _ => {}
+    }
+    entries
+}
+
+/// Check if a value assigned to a binding after its declaration (`var App; App = () => <div />`)
+/// is a React component. There is no annotation to go by, so the value must contain JSX
+pub(crate) fn is_react_component_assignment(
+    name: &str,
+    value: &Expression,
+    jsx_runtime_identifiers: &std::collections::HashSet<String>,
+) -> bool {
+    name.chars().next().is_some_and(|c| c.is_uppercase())
+        && contains_jsx(value, jsx_runtime_identifiers)
+}
+
 /// Check if a function declaration is a React component
 pub(crate) fn is_react_function_component(
     name: &str,
     return_type: &Option<oxc::allocator::Box<oxc::ast::ast::TSTypeAnnotation>>,
     body: &Option<oxc::allocator::Box<oxc::ast::ast::FunctionBody>>,
     jsx_runtime_identifiers: &std::collections::HashSet<String>,
+    component_types: &[String],
 ) -> bool {
     // Check 1: PascalCase naming convention
     let is_pascal_case = name.chars().next().is_some_and(|c| c.is_uppercase());
@@ -71,7 +307,7 @@ pub(crate) fn is_react_function_component(
 
     // Check 2: Has React return type annotation
     if let Some(type_annotation) = return_type
-        && is_react_type_annotation(&type_annotation.type_annotation)
+        && is_react_type_annotation(&type_annotation.type_annotation, component_types)
     {
         return true;
     }
@@ -88,28 +324,58 @@ pub(crate) fn is_react_function_component(
 // Helper Functions: Type Checking
 // ============================================================================
 
-/// Check if a type annotation is a React component type
-fn is_react_type_annotation(ts_type: &TSType) -> bool {
+/// Check if a type annotation is a React component type: one of `REACT_COMPONENT_TYPES` or of the
+/// host's `component_types`, directly or through a namespace (`React.FC`, `JSX.Element`), or a
+/// function type returning one (`(props: PropsWithChildren<Props>) => ReactNode`). Unions and
+/// intersections count when any of their members does
+fn is_react_type_annotation(ts_type: &TSType, component_types: &[String]) -> bool {
     match ts_type {
         TSType::TSTypeReference(type_ref) => {
             // Check if the type name is a React component type
-            if let IdentifierReference(ident) = &type_ref.type_name {
-                matches!(
-                    ident.name.as_str(),
-                    "FC" | "FunctionComponent" | "VFC" | "ReactElement" | "ReactNode" | "Component"
-                )
-            } else {
-                false
-            }
+            let name = match &type_ref.type_name {
+                IdentifierReference(ident) => ident.name.as_str(),
+                TSTypeName::QualifiedName(qualified) => {
+                    // `JSX.Element` and `React.JSX.Element`
+                    let in_jsx_namespace = match &qualified.left {
+                        IdentifierReference(ident) => ident.name == "JSX",
+                        TSTypeName::QualifiedName(left) => left.right.name == "JSX",
+                        TSTypeName::ThisExpression(_) => false,
+                    };
+                    if in_jsx_namespace && qualified.right.name == "Element" {
+                        return true;
+                    }
+                    qualified.right.name.as_str()
+                }
+                TSTypeName::ThisExpression(_) => return false,
+            };
+            REACT_COMPONENT_TYPES.contains(&name)
+                || component_types
+                    .iter()
+                    .any(|component_type| component_type == name)
+        }
+        TSType::TSFunctionType(function_type) => {
+            is_react_type_annotation(&function_type.return_type.type_annotation, component_types)
+        }
+        // `FC<Props> | null`, `FC<Props> & { Skeleton: FC }`
+        TSType::TSUnionType(union) => union
+            .types
+            .iter()
+            .any(|member| is_react_type_annotation(member, component_types)),
+        TSType::TSIntersectionType(intersection) => intersection
+            .types
+            .iter()
+            .any(|member| is_react_type_annotation(member, component_types)),
+        TSType::TSParenthesizedType(parenthesized) => {
+            is_react_type_annotation(&parenthesized.type_annotation, component_types)
         }
         _ => false,
     }
 }
 
 /// Check if a binding pattern has React type annotation
-fn has_react_type(binding: &BindingPattern) -> bool {
+fn has_react_type(binding: &BindingPattern, component_types: &[String]) -> bool {
     if let Some(type_annotation) = &binding.type_annotation {
-        is_react_type_annotation(&type_annotation.type_annotation)
+        is_react_type_annotation(&type_annotation.type_annotation, component_types)
     } else {
         false
     }
@@ -149,10 +415,7 @@ fn contains_jsx(
                     matches!(
                         stmt,
                         Statement::ExpressionStatement(expr_stmt)
-                            if matches!(
-                                &expr_stmt.expression,
-                                Expression::JSXElement(_) | Expression::JSXFragment(_)
-                            ) || is_jsx_runtime_call(&expr_stmt.expression, jsx_runtime_identifiers)
+                            if is_jsx_evidence(&expr_stmt.expression, jsx_runtime_identifiers)
                     )
                 })
             } else {
@@ -176,8 +439,7 @@ fn has_jsx_return(
     statements.iter().any(|stmt| {
         if let Statement::ReturnStatement(ret) = stmt {
             if let Some(arg) = &ret.argument {
-                matches!(arg, Expression::JSXElement(_) | Expression::JSXFragment(_))
-                    || is_jsx_runtime_call(arg, jsx_runtime_identifiers)
+                is_jsx_evidence(arg, jsx_runtime_identifiers)
             } else {
                 false
             }
@@ -187,6 +449,52 @@ fn has_jsx_return(
     })
 }
 
+/// Whether a returned expression is JSX or a jsx runtime call, looking through `await` and either
+/// side of `??`. Calls, optional or not, count when they call the jsx runtime or a render
+/// function (`props.render?.()`, `await maybeRender()`, `children()`), which only ever returns
+/// elements
+fn is_jsx_evidence(
+    expr: &Expression,
+    jsx_runtime_identifiers: &std::collections::HashSet<String>,
+) -> bool {
+    match expr {
+        Expression::JSXElement(_) | Expression::JSXFragment(_) => true,
+        Expression::ParenthesizedExpression(paren) => {
+            is_jsx_evidence(&paren.expression, jsx_runtime_identifiers)
+        }
+        Expression::AwaitExpression(await_expr) => {
+            is_jsx_evidence(&await_expr.argument, jsx_runtime_identifiers)
+        }
+        Expression::LogicalExpression(logical) if logical.operator == LogicalOperator::Coalesce => {
+            is_jsx_evidence(&logical.left, jsx_runtime_identifiers)
+                || is_jsx_evidence(&logical.right, jsx_runtime_identifiers)
+        }
+        Expression::ChainExpression(chain) => match &chain.expression {
+            ChainElement::CallExpression(call) => is_render_call(call, jsx_runtime_identifiers),
+            _ => false,
+        },
+        Expression::CallExpression(call) if is_render_call(call, jsx_runtime_identifiers) => true,
+        expr => is_jsx_runtime_call(expr, jsx_runtime_identifiers),
+    }
+}
+
+/// Whether a call goes to the jsx runtime or to a render function by name: `children`,
+/// `render*` or `*Render*` (`renderRow`, `maybeRender`)
+fn is_render_call(
+    call: &CallExpression,
+    jsx_runtime_identifiers: &std::collections::HashSet<String>,
+) -> bool {
+    let name = match call.callee.get_inner_expression() {
+        Expression::Identifier(callee) => callee.name.as_str(),
+        Expression::StaticMemberExpression(member) => member.property.name.as_str(),
+        _ => return false,
+    };
+    jsx_runtime_identifiers.contains(name)
+        || name == "children"
+        || name.starts_with("render")
+        || name.contains("Render")
+}
+
 /// Check if an expression is a jsx/jsxs runtime call (for bundled code)
 /// Bundled code uses jsx("div", {...}) instead of <div>
 ///
@@ -242,6 +550,75 @@ fn is_jsx_runtime_call(
     false
 }
 
+/// Whether the function renders its first parameter as an element anywhere in its body, either as
+/// JSX (`<C />`) or through a jsx runtime call (`jsx(C, props)`)
+fn renders_first_parameter(
+    params: &FormalParameters,
+    body: &FunctionBody,
+    jsx_runtime_identifiers: &std::collections::HashSet<String>,
+) -> bool {
+    let Some(BindingPatternKind::BindingIdentifier(param)) =
+        params.items.first().map(|param| &param.pattern.kind)
+    else {
+        return false;
+    };
+
+    if crate::jsx::collect_jsx_usages(&body.statements)
+        .iter()
+        .any(|(name, _)| name == param.name.as_str())
+    {
+        return true;
+    }
+
+    let mut finder = RuntimeCallFinder {
+        jsx_runtime_identifiers,
+        element_type: param.name.as_str(),
+        found: false,
+    };
+    finder.visit_function_body(body);
+    finder.found
+}
+
+/// Finds a jsx runtime call creating an element of the given type (`jsx(Component, props)`)
+struct RuntimeCallFinder<'c> {
+    jsx_runtime_identifiers: &'c std::collections::HashSet<String>,
+    element_type: &'c str,
+    found: bool,
+}
+
+impl<'a> Visit<'a> for RuntimeCallFinder<'_> {
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        if let Expression::CallExpression(call) = expr
+            && is_jsx_runtime_call(expr, self.jsx_runtime_identifiers)
+            && matches!(
+                call.arguments.first().and_then(|arg| arg.as_expression()),
+                Some(Expression::Identifier(ident)) if ident.name == self.element_type
+            )
+        {
+            self.found = true;
+        }
+        walk::walk_expression(self, expr);
+    }
+}
+
+/// Finds the applications of local higher-order components (`withAuth(Dashboard)`)
+struct HocApplicationFinder<'c> {
+    hocs: &'c std::collections::HashSet<String>,
+    applications: Vec<(String, Span)>,
+}
+
+impl<'a> Visit<'a> for HocApplicationFinder<'_> {
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        if let Expression::CallExpression(_) = expr
+            && let Some(wrapped) = hoc_application(expr, self.hocs)
+        {
+            self.applications
+                .push((wrapped.name.to_string(), wrapped.span));
+        }
+        walk::walk_expression(self, expr);
+    }
+}
+
 /// Check if a CallExpression is React.forwardRef or similar HOC patterns
 fn is_react_hoc(call_expr: &oxc::ast::ast::CallExpression) -> bool {
     use oxc::ast::ast::Expression;
@@ -271,7 +648,6 @@ fn is_react_hoc(call_expr: &oxc::ast::ast::CallExpression) -> bool {
 mod tests {
     use super::*;
     use oxc::allocator::Allocator;
-    use oxc::ast::ast::{BindingPatternKind, Statement};
     use oxc::parser::{ParseOptions, Parser};
     use oxc::span::SourceType;
 
@@ -287,6 +663,7 @@ mod tests {
         let source_type = SourceType::tsx();
         let ret = Parser::new(&allocator, source, source_type)
             .with_options(ParseOptions {
+                #[cfg(feature = "regular-expression")]
                 parse_regular_expression: true,
                 ..ParseOptions::default()
             })
@@ -303,6 +680,7 @@ mod tests {
                     &declarator.id,
                     &declarator.init,
                     &jsx_runtime_identifiers,
+                    &[],
                 );
                 assert!(result, "PascalCase component with JSX should be detected");
             }
@@ -329,6 +707,7 @@ mod tests {
                     &declarator.id,
                     &declarator.init,
                     &std::collections::HashSet::new(),
+                    &[],
                 );
                 assert!(!result, "camelCase should not be detected as component");
             }
@@ -355,6 +734,7 @@ mod tests {
                     &declarator.id,
                     &declarator.init,
                     &std::collections::HashSet::new(),
+                    &[],
                 );
                 assert!(
                     result,
@@ -384,6 +764,7 @@ mod tests {
                     &declarator.id,
                     &declarator.init,
                     &std::collections::HashSet::new(),
+                    &[],
                 );
                 assert!(
                     !result,
@@ -393,6 +774,85 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_react_component_with_component_types() {
+        let source = r#"
+            const Button: ComponentType<ButtonProps> = createButton();
+            const Slot: ForwardRefExoticComponent<SlotProps> = createSlot();
+            const Chart: LazyExoticComponent<ChartType> = lazyChart;
+            const Layout: (props: PropsWithChildren<Props>) => ReactNode = createLayout();
+            const Icon: IconComponent = createIcon();
+            const Config: ButtonProps = createConfig();
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let component_types = ["IconComponent".to_string()];
+
+        let detected = ret
+            .program
+            .body
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::VariableDeclaration(var_decl) => var_decl.declarations.first(),
+                _ => None,
+            })
+            .filter_map(|declarator| {
+                let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind else {
+                    return None;
+                };
+                is_react_component(
+                    ident.name.as_ref(),
+                    &declarator.id,
+                    &declarator.init,
+                    &std::collections::HashSet::new(),
+                    &component_types,
+                )
+                .then(|| ident.name.as_str())
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(detected, vec!["Button", "Slot", "Chart", "Layout", "Icon"]);
+    }
+
+    #[test]
+    fn test_is_react_component_with_union_and_intersection_types() {
+        let source = r#"
+            const Button: FC<Props> & { Skeleton: FC } = createButton();
+            const Card: (FC<Props> | null) = createCard();
+            const Menu: React.FC<Props> & typeof menuStatics = createMenu();
+            const Label: string | number = createLabel();
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+
+        let detected = ret
+            .program
+            .body
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::VariableDeclaration(var_decl) => var_decl.declarations.first(),
+                _ => None,
+            })
+            .filter_map(|declarator| {
+                let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind else {
+                    return None;
+                };
+                is_react_component(
+                    ident.name.as_ref(),
+                    &declarator.id,
+                    &declarator.init,
+                    &std::collections::HashSet::new(),
+                    &[],
+                )
+                .then(|| ident.name.as_str())
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(detected, vec!["Button", "Card", "Menu"]);
+    }
+
     #[test]
     fn test_arrow_function_with_jsx() {
         let source = r#"
@@ -411,6 +871,7 @@ mod tests {
                     &declarator.id,
                     &declarator.init,
                     &std::collections::HashSet::new(),
+                    &[],
                 );
                 assert!(
                     result,
@@ -442,6 +903,7 @@ mod tests {
                 &func_decl.return_type,
                 &func_decl.body,
                 &jsx_runtime_identifiers,
+                &[],
             );
             assert!(
                 result,
@@ -450,6 +912,94 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_function_declaration_wrapped_jsx_returns() {
+        let source = r#"
+            async function Awaited() { return await (<Suspense />); }
+            function RenderProp(props) { return props.render?.(); }
+            function Fallback({ content }) { return content ?? <Empty />; }
+            function Maybe(props) { return props.value?.toString(); }
+            async function Page() { return await maybeRender(); }
+            function Rows(props) { return props.renderRows(); }
+            function Slot({ children }) { return children(); }
+            async function Total() { return await fetchTotal(); }
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let jsx_runtime_identifiers = std::collections::HashSet::new();
+
+        let detected = ret
+            .program
+            .body
+            .iter()
+            .filter_map(|statement| {
+                let Statement::FunctionDeclaration(func_decl) = statement else {
+                    return None;
+                };
+                let id = func_decl.id.as_ref()?;
+                Some((
+                    id.name.as_str(),
+                    is_react_function_component(
+                        id.name.as_ref(),
+                        &func_decl.return_type,
+                        &func_decl.body,
+                        &jsx_runtime_identifiers,
+                        &[],
+                    ),
+                ))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            detected,
+            vec![
+                ("Awaited", true),
+                ("RenderProp", true),
+                ("Fallback", true),
+                ("Maybe", false),
+                ("Page", true),
+                ("Rows", true),
+                ("Slot", true),
+                ("Total", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_function_declaration_return_types() {
+        let source = r#"
+            function Page(): JSX.Element { return build(); }
+            function Layout(): React.JSX.Element { return build(); }
+            function Header(): React.ReactNode { return build(); }
+            function Banner(): ReactElement | null { return build(); }
+            function Footer(): React.FC | undefined { return build(); }
+            function Title(): string | null { return build(); }
+            function Empty(): null { return null; }
+            function Tree(): Tree.Element { return build(); }
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+
+        let detected = ret
+            .program
+            .body
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::FunctionDeclaration(func_decl) => {
+                    analyze_function_declaration(func_decl, &std::collections::HashSet::new(), &[])
+                }
+                _ => None,
+            })
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            detected,
+            vec!["Page", "Layout", "Header", "Banner", "Footer"]
+        );
+    }
+
     #[test]
     fn test_function_declaration_camelcase_should_fail() {
         let source = r#"
@@ -472,6 +1022,7 @@ mod tests {
                 &func_decl.return_type,
                 &func_decl.body,
                 &jsx_runtime_identifiers,
+                &[],
             );
             assert!(
                 !result,
@@ -502,6 +1053,7 @@ mod tests {
                 &func_decl.return_type,
                 &func_decl.body,
                 &jsx_runtime_identifiers,
+                &[],
             );
             assert!(
                 !result,
@@ -509,4 +1061,78 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_class_declaration_components() {
+        let source = r#"
+            @observer
+            class Counter extends React.Component {}
+            class Store extends PureComponent {}
+            class Model extends Base {}
+            class lowercase extends Component {}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+
+        let names = ret
+            .program
+            .body
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::ClassDeclaration(class) => analyze_class_declaration(class),
+                _ => None,
+            })
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Counter", "Store"]);
+    }
+
+    #[test]
+    fn test_conditional_candidates() {
+        let source = "(isTouch ? MobileNav : (Custom ?? DesktopNav)) || (ready && Fallback);";
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+
+        let mut candidates = Vec::new();
+        if let Statement::ExpressionStatement(statement) = &ret.program.body[0] {
+            conditional_candidates(&statement.expression, &mut candidates);
+        }
+        assert_eq!(
+            candidates,
+            vec!["MobileNav", "Custom", "DesktopNav", "Fallback"]
+        );
+    }
+
+    #[test]
+    fn test_collect_local_hocs() {
+        let source = r#"
+            import { jsx } from "react/jsx-runtime";
+            const withAuth = (C) => (props) => <Guard><C {...props} /></Guard>;
+            export function withTheme(Inner) {
+                function Themed(props) { return jsx(Inner, props); }
+                return Themed;
+            }
+            const withLogger = (fn) => (...args) => fn(...args);
+            const Layout = (Child) => <Child />;
+            withAuth(Dashboard);
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let jsx_runtime_identifiers = std::collections::HashSet::from(["jsx".to_string()]);
+
+        let hocs = collect_local_hocs(&ret.program.body, &jsx_runtime_identifiers);
+        let mut names = hocs.iter().map(String::as_str).collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["withAuth", "withTheme"]);
+
+        let Some(Statement::ExpressionStatement(statement)) = ret.program.body.last() else {
+            panic!("expected the HOC application");
+        };
+        let wrapped =
+            hoc_application(&statement.expression, &hocs).map(|ident| ident.name.as_str());
+        assert_eq!(wrapped, Some("Dashboard"));
+    }
 }
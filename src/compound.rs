@@ -0,0 +1,215 @@
+use oxc::ast::ast::{
+    Argument, BindingPatternKind, Declaration, ExportDefaultDeclarationKind, Expression,
+    ObjectPropertyKind, PropertyKey, Statement,
+};
+use oxc::span::Span;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// A subcomponent attached to a compound component, rendered as `<Parent.Member>`
+pub(crate) struct Subcomponent {
+    /// Binding the subcomponent is attached to (`Dialog`)
+    pub(crate) parent: String,
+    /// Property the subcomponent is attached as (`Trigger`)
+    pub(crate) member: String,
+    /// Local binding assigned to the property, when it is one
+    pub(crate) local: Option<String>,
+    /// Span of the property or of the assignment attaching the subcomponent
+    pub(crate) span: Span,
+}
+
+/// Collect the subcomponents attached at the top level of a module, either through
+/// `Object.assign(Dialog, { Trigger, Content: DialogContent })` or through static member
+/// assignments (`Dialog.Trigger = Trigger`). Only PascalCase members are collected, so
+/// `Dialog.displayName = ...` is not mistaken for one
+pub(crate) fn collect_subcomponents(statements: &[Statement]) -> Vec<Subcomponent> {
+    let mut subcomponents = Vec::new();
+
+    for statement in statements {
+        let declaration = match statement {
+            Statement::ExportNamedDeclaration(export_decl) => export_decl.declaration.as_ref(),
+            Statement::ExportDefaultDeclaration(export_decl) => {
+                // `export default Object.assign(Root, { ... })` is used through the target
+                if let ExportDefaultDeclarationKind::CallExpression(call) = &export_decl.declaration
+                    && let Some(Expression::Identifier(target)) = call
+                        .arguments
+                        .first()
+                        .and_then(Argument::as_expression)
+                        .map(Expression::get_inner_expression)
+                {
+                    collect_assigned_members(
+                        export_decl.declaration.to_expression(),
+                        &target.name,
+                        &mut subcomponents,
+                    );
+                }
+                continue;
+            }
+            Statement::ExpressionStatement(expr_stmt) => {
+                collect_member_assignment(&expr_stmt.expression, &mut subcomponents);
+                continue;
+            }
+            statement => statement.as_declaration(),
+        };
+
+        if let Some(Declaration::VariableDeclaration(var_decl)) = declaration {
+            for declarator in var_decl.declarations.iter() {
+                if let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind
+                    && let Some(init) = &declarator.init
+                {
+                    collect_assigned_members(init, &ident.name, &mut subcomponents);
+                }
+            }
+        }
+    }
+
+    subcomponents
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn is_pascal_case(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_uppercase())
+}
+
+/// Members of the objects merged by `Object.assign(target, { ... })`, attached to `parent`
+fn collect_assigned_members(
+    expr: &Expression,
+    parent: &str,
+    subcomponents: &mut Vec<Subcomponent>,
+) {
+    let Expression::CallExpression(call) = expr.get_inner_expression() else {
+        return;
+    };
+    let is_object_assign = matches!(call.callee.get_inner_expression(),
+        Expression::StaticMemberExpression(member)
+            if member.property.name == "assign"
+                && matches!(&member.object, Expression::Identifier(object) if object.name == "Object"));
+    if !is_object_assign {
+        return;
+    }
+
+    for argument in call.arguments.iter().skip(1) {
+        let Some(Expression::ObjectExpression(object)) = argument
+            .as_expression()
+            .map(Expression::get_inner_expression)
+        else {
+            continue;
+        };
+        for property in object.properties.iter() {
+            let ObjectPropertyKind::ObjectProperty(property) = property else {
+                continue;
+            };
+            let member = match &property.key {
+                PropertyKey::StaticIdentifier(key) => key.name.as_str(),
+                PropertyKey::StringLiteral(key) => key.value.as_str(),
+                _ => continue,
+            };
+            if !is_pascal_case(member) {
+                continue;
+            }
+            subcomponents.push(Subcomponent {
+                parent: parent.to_string(),
+                member: member.to_string(),
+                local: local_name(&property.value),
+                span: property.span,
+            });
+        }
+    }
+}
+
+/// `Parent.Member = value`
+fn collect_member_assignment(expr: &Expression, subcomponents: &mut Vec<Subcomponent>) {
+    let Expression::AssignmentExpression(assignment) = expr else {
+        return;
+    };
+    let Some(member) = assignment.left.as_member_expression() else {
+        return;
+    };
+    let (Expression::Identifier(parent), Some((_, name))) =
+        (member.object(), member.static_property_info())
+    else {
+        return;
+    };
+    if !is_pascal_case(&parent.name) || !is_pascal_case(name) {
+        return;
+    }
+    subcomponents.push(Subcomponent {
+        parent: parent.name.to_string(),
+        member: name.to_string(),
+        local: local_name(&assignment.right),
+        span: assignment.span,
+    });
+}
+
+fn local_name(expr: &Expression) -> Option<String> {
+    match expr.get_inner_expression() {
+        Expression::Identifier(ident) => Some(ident.name.to_string()),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_collect_subcomponents() {
+        let source = r#"
+function DialogRoot() { return <div />; }
+function Trigger() { return <button />; }
+function DialogContent() { return <section />; }
+
+export const Dialog = Object.assign(DialogRoot, {
+  Trigger,
+  Content: DialogContent,
+  Title: () => <h2 />,
+  config: {},
+});
+
+function Card() { return <article />; }
+Card.Header = CardHeader;
+Card["Footer"] = CardFooter;
+Card.displayName = "Card";
+
+export default Object.assign(Tabs, { List: TabList });
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let subcomponents = collect_subcomponents(&ret.program.body)
+            .into_iter()
+            .map(|sub| (sub.parent, sub.member, sub.local))
+            .collect::<Vec<_>>();
+
+        let entry = |parent: &str, member: &str, local: Option<&str>| {
+            (
+                parent.to_string(),
+                member.to_string(),
+                local.map(str::to_string),
+            )
+        };
+        assert_eq!(
+            subcomponents,
+            vec![
+                entry("Dialog", "Trigger", Some("Trigger")),
+                entry("Dialog", "Content", Some("DialogContent")),
+                entry("Dialog", "Title", None),
+                entry("Card", "Header", Some("CardHeader")),
+                entry("Card", "Footer", Some("CardFooter")),
+                entry("Tabs", "List", Some("TabList")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use crate::analyze_react_boundary::check::types;
+use oxc::ast::ast::{Argument, Expression, IfStatement, Statement};
+use oxc::ast_visit::{Visit, walk};
+use oxc::span::{GetSpan, Span};
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// A `require(...)` or `import(...)` of a module that only runs when a condition holds
+pub(crate) struct ConditionalImport {
+    pub(crate) kind: types::ConditionalImportKind,
+    /// Specifier of the loaded module
+    pub(crate) source: String,
+    pub(crate) source_span: Span,
+    /// Span of the condition guarding the load (`typeof window !== "undefined"`)
+    pub(crate) guard_span: Span,
+    /// Span of the call
+    pub(crate) span: Span,
+}
+
+/// Collect the modules loaded under a condition: in a branch of an `if` statement or of a
+/// `?:` expression, or on the right of `&&`, `||` or `??`
+/// (`if (isBrowser) { require("./client-thing") }`). Such loads are edges of the module graph
+/// that may never be taken, typically only on one side of the client boundary, so they are
+/// reported apart from static imports. Specifiers computed at runtime are skipped
+pub(crate) fn collect_conditional_imports(statements: &[Statement]) -> Vec<ConditionalImport> {
+    let mut collector = ConditionalImportCollector::default();
+    for statement in statements {
+        collector.visit_statement(statement);
+    }
+    let ConditionalImportCollector {
+        mut branches,
+        expression_branches,
+        loads,
+    } = collector;
+    branches.extend(expression_branches);
+
+    loads
+        .into_iter()
+        .filter_map(|(kind, source, source_span, span)| {
+            // The innermost branch around the call decides which condition guards it
+            let (_, guard_span) = branches
+                .iter()
+                .filter(|(branch, _)| branch.start <= span.start && span.end <= branch.end)
+                .min_by_key(|(branch, _)| branch.size())?;
+            Some(ConditionalImport {
+                kind,
+                source,
+                source_span,
+                guard_span: *guard_span,
+                span,
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+#[derive(Default)]
+struct ConditionalImportCollector {
+    /// Branches of `if` statements, with the spans of their conditions
+    branches: Vec<(Span, Span)>,
+    /// Branches of `?:`, `&&`, `||` and `??` expressions, with the spans of their conditions
+    expression_branches: Vec<(Span, Span)>,
+    loads: Vec<(types::ConditionalImportKind, String, Span, Span)>,
+}
+
+impl<'a> Visit<'a> for ConditionalImportCollector {
+    fn visit_if_statement(&mut self, if_stmt: &IfStatement<'a>) {
+        let test = if_stmt.test.span();
+        self.branches.push((if_stmt.consequent.span(), test));
+        if let Some(alternate) = &if_stmt.alternate {
+            self.branches.push((alternate.span(), test));
+        }
+        walk::walk_if_statement(self, if_stmt);
+    }
+
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        match expr {
+            Expression::ConditionalExpression(conditional) => {
+                let test = conditional.test.span();
+                self.expression_branches
+                    .push((conditional.consequent.span(), test));
+                self.expression_branches
+                    .push((conditional.alternate.span(), test));
+            }
+            Expression::LogicalExpression(logical) => {
+                self.expression_branches
+                    .push((logical.right.span(), logical.left.span()));
+            }
+            expr => self.loads.extend(module_load(expr)),
+        }
+        walk::walk_expression(self, expr);
+    }
+}
+
+/// Kind, specifier, specifier span and span of `require("...")` or `import("...")`
+fn module_load(expr: &Expression) -> Option<(types::ConditionalImportKind, String, Span, Span)> {
+    let (kind, specifier, span) = match expr {
+        Expression::CallExpression(call) => {
+            let Expression::Identifier(callee) = call.callee.get_inner_expression() else {
+                return None;
+            };
+            if callee.name != "require" || call.arguments.len() != 1 {
+                return None;
+            }
+            (
+                types::ConditionalImportKind::Require,
+                call.arguments.first().and_then(Argument::as_expression)?,
+                call.span,
+            )
+        }
+        Expression::ImportExpression(import) => (
+            types::ConditionalImportKind::DynamicImport,
+            &import.source,
+            import.span,
+        ),
+        _ => return None,
+    };
+    match specifier.get_inner_expression() {
+        Expression::StringLiteral(literal) => {
+            Some((kind, literal.value.to_string(), literal.span, span))
+        }
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_collect_conditional_imports() {
+        let source = r#"
+const always = require("./always");
+if (isBrowser) {
+  require("./client-thing");
+} else {
+  require("./server-thing");
+}
+const Chart = typeof window !== "undefined" ? import("./chart") : null;
+export function useAnalytics() {
+  useEffect(() => {
+    if (consent) import("./analytics");
+  }, []);
+  return hasPolyfill || require(polyfillPath);
+}
+const lazy = () => import("./lazy");
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let imports = collect_conditional_imports(&ret.program.body);
+
+        let summary = imports
+            .iter()
+            .map(|import| {
+                (
+                    import.kind,
+                    import.source.as_str(),
+                    &source[import.guard_span.start as usize..import.guard_span.end as usize],
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    types::ConditionalImportKind::Require,
+                    "./client-thing",
+                    "isBrowser"
+                ),
+                (
+                    types::ConditionalImportKind::Require,
+                    "./server-thing",
+                    "isBrowser"
+                ),
+                (
+                    types::ConditionalImportKind::DynamicImport,
+                    "./chart",
+                    "typeof window !== \"undefined\""
+                ),
+                (
+                    types::ConditionalImportKind::DynamicImport,
+                    "./analytics",
+                    "consent"
+                ),
+            ]
+        );
+        let span = imports[0].source_span;
+        assert_eq!(
+            &source[span.start as usize..span.end as usize],
+            "\"./client-thing\""
+        );
+    }
+}
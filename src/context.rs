@@ -0,0 +1,320 @@
+use crate::jsx::JsxCollection;
+use crate::rules::Rule;
+use oxc::ast::ast::{
+    Argument, BindingPatternKind, CallExpression, Declaration, Expression,
+    ImportDeclarationSpecifier, Statement,
+};
+use oxc::ast_visit::{Visit, walk};
+use oxc::span::Span;
+use std::collections::HashSet;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// A `useContext` call reading a context imported from another module
+pub(crate) struct ContextConsumer {
+    /// Local name of the imported binding the context is read through
+    pub(crate) local: String,
+    /// Name the context is exported as by its module: `default` for default imports, the member
+    /// name for namespace members (`useContext(Contexts.Theme)`)
+    pub(crate) imported: String,
+    pub(crate) source: String,
+    /// Span of the `useContext` call
+    pub(crate) span: Span,
+}
+
+/// Collect the `useContext(...)` calls whose context is an imported binding, so providers and
+/// consumers can be paired across modules
+pub(crate) fn collect_context_consumers(statements: &[Statement]) -> Vec<ContextConsumer> {
+    let (hooks, namespaces) = crate::signal::imported_apis(statements, &["react"], &["useContext"]);
+    if hooks.is_empty() && namespaces.is_empty() {
+        return Vec::new();
+    }
+    let imports = imported_bindings(statements);
+
+    let mut collector = ContextConsumerCollector {
+        hooks: &hooks,
+        namespaces: &namespaces,
+        imports: &imports,
+        consumers: Vec::new(),
+    };
+    for statement in statements {
+        collector.visit_statement(statement);
+    }
+    collector.consumers
+}
+
+/// Diagnose the context providers rendered by a module without "use client": `<Theme.Provider>`,
+/// and contexts created in the module rendered directly (`<ThemeContext value={...}>`, React 19).
+/// Providers hold client state, so they only work in client components. Returns the messages with
+/// the span of the provider element
+pub(crate) fn collect_provider_diagnostics(
+    statements: &[Statement],
+    jsx: &JsxCollection,
+) -> Vec<(Rule, String, Span)> {
+    let local_contexts = local_contexts(statements);
+
+    jsx.usages
+        .iter()
+        .filter_map(|(name, span)| {
+            let provider = match jsx.member_for(*span) {
+                Some(member) if member == "Provider" || member.ends_with(".Provider") => {
+                    format!("{}.{}", name, member)
+                }
+                None if local_contexts.contains(name.as_str()) => name.clone(),
+                _ => return None,
+            };
+            Some((
+                Rule::ContextProviderInServer,
+                format!(
+                    "<{}> provides a context, which requires a client component. Move it into a \
+                     \"use client\" module",
+                    provider
+                ),
+                *span,
+            ))
+        })
+        .collect()
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+struct ContextConsumerCollector<'c> {
+    hooks: &'c [(String, String, Span)],
+    namespaces: &'c [String],
+    imports: &'c [(String, String, String)],
+    consumers: Vec<ContextConsumer>,
+}
+
+impl ContextConsumerCollector<'_> {
+    /// Record the call if it is a `useContext` reading an imported context
+    fn collect_call(&mut self, call: &CallExpression) {
+        let is_use_context = match call.callee.get_inner_expression() {
+            Expression::Identifier(ident) => self
+                .hooks
+                .iter()
+                .any(|(local, _, _)| local == ident.name.as_str()),
+            Expression::StaticMemberExpression(member) => {
+                member.property.name == "useContext"
+                    && matches!(&member.object, Expression::Identifier(object)
+                        if self.namespaces.iter().any(|ns| ns == object.name.as_str()))
+            }
+            _ => false,
+        };
+        if !is_use_context {
+            return;
+        }
+
+        let context = match call.arguments.first() {
+            Some(Argument::SpreadElement(_)) | None => None,
+            Some(argument) => argument
+                .as_expression()
+                .map(|arg| arg.get_inner_expression()),
+        };
+        let consumer = match context {
+            Some(Expression::Identifier(ident)) => self
+                .imports
+                .iter()
+                .find(|(local, _, _)| local == ident.name.as_str())
+                .filter(|(_, imported, _)| imported != "*")
+                .map(|(local, imported, source)| (local.clone(), imported.clone(), source)),
+            Some(Expression::StaticMemberExpression(member)) => match &member.object {
+                Expression::Identifier(object) => self
+                    .imports
+                    .iter()
+                    .find(|(local, imported, _)| local == object.name.as_str() && imported == "*")
+                    .map(|(local, _, source)| {
+                        (local.clone(), member.property.name.to_string(), source)
+                    }),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some((local, imported, source)) = consumer {
+            self.consumers.push(ContextConsumer {
+                local,
+                imported,
+                source: source.clone(),
+                span: call.span,
+            });
+        }
+    }
+}
+
+impl<'a> Visit<'a> for ContextConsumerCollector<'_> {
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        if let Expression::CallExpression(call) = expr {
+            self.collect_call(call);
+        }
+        walk::walk_expression(self, expr);
+    }
+}
+
+/// Top-level bindings initialized with `createContext(...)` or `React.createContext(...)`
+fn local_contexts<'s>(statements: &'s [Statement]) -> HashSet<&'s str> {
+    let mut contexts = HashSet::new();
+
+    for statement in statements {
+        let declaration = match statement {
+            Statement::ExportNamedDeclaration(export_decl) => export_decl.declaration.as_ref(),
+            statement => statement.as_declaration(),
+        };
+        let Some(Declaration::VariableDeclaration(var_decl)) = declaration else {
+            continue;
+        };
+        for declarator in var_decl.declarations.iter() {
+            let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind else {
+                continue;
+            };
+            let Some(Expression::CallExpression(call)) = declarator
+                .init
+                .as_ref()
+                .map(Expression::get_inner_expression)
+            else {
+                continue;
+            };
+            let is_create_context = match call.callee.get_inner_expression() {
+                Expression::Identifier(callee) => callee.name == "createContext",
+                Expression::StaticMemberExpression(member) => {
+                    member.property.name == "createContext"
+                }
+                _ => false,
+            };
+            if is_create_context {
+                contexts.insert(ident.name.as_str());
+            }
+        }
+    }
+
+    contexts
+}
+
+/// Value imports of the module as (local name, imported name, source). Default imports are
+/// imported as `default` and namespace imports as `*`
+fn imported_bindings(statements: &[Statement]) -> Vec<(String, String, String)> {
+    let mut bindings = Vec::new();
+
+    for statement in statements {
+        let Statement::ImportDeclaration(import_decl) = statement else {
+            continue;
+        };
+        if import_decl.import_kind.is_type() {
+            continue;
+        }
+        let source = import_decl.source.value.to_string();
+
+        for specifier in import_decl.specifiers.iter().flatten() {
+            let (local, imported) = match specifier {
+                ImportDeclarationSpecifier::ImportSpecifier(spec) => {
+                    if spec.import_kind.is_type() {
+                        continue;
+                    }
+                    (&spec.local, crate::module_export_name(&spec.imported))
+                }
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                    (&spec.local, "default".to_string())
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
+                    (&spec.local, "*".to_string())
+                }
+            };
+            bindings.push((local.name.to_string(), imported, source.clone()));
+        }
+    }
+
+    bindings
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_collect_imported_context_consumers() {
+        let source = r#""use client";
+import React, { useContext as readContext } from "react";
+import ThemeContext from "./theme";
+import { UserContext as User } from "./user";
+import * as Contexts from "./contexts";
+
+const LocalContext = React.createContext(null);
+
+export function Toolbar() {
+  const theme = readContext(ThemeContext);
+  const user = React.useContext(User);
+  const locale = useLocale(() => React.useContext(Contexts.Locale));
+  const local = readContext(LocalContext);
+  return <div className={theme} />;
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let consumers = collect_context_consumers(&ret.program.body)
+            .into_iter()
+            .map(|consumer| (consumer.local, consumer.imported, consumer.source))
+            .collect::<Vec<_>>();
+
+        let entry = |local: &str, imported: &str, source: &str| {
+            (local.to_string(), imported.to_string(), source.to_string())
+        };
+        assert_eq!(
+            consumers,
+            vec![
+                entry("ThemeContext", "default", "./theme"),
+                entry("User", "UserContext", "./user"),
+                entry("Contexts", "Locale", "./contexts"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_provider_diagnostics() {
+        let source = r#"
+import { createContext } from "react";
+import { ThemeContext } from "./theme";
+import * as Contexts from "./contexts";
+
+export const LocaleContext = createContext("en");
+
+export default function Layout({ children }) {
+  return (
+    <ThemeContext.Provider value="dark">
+      <Contexts.User.Provider value={null}>
+        <LocaleContext value="fr">
+          <ThemeContext.Consumer>{() => children}</ThemeContext.Consumer>
+        </LocaleContext>
+      </Contexts.User.Provider>
+    </ThemeContext.Provider>
+  );
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let jsx = crate::jsx::collect_jsx(&ret.program.body);
+        let messages = collect_provider_diagnostics(&ret.program.body, &jsx)
+            .into_iter()
+            .map(|(_, message, _)| message)
+            .collect::<Vec<_>>();
+
+        assert_eq!(messages.len(), 3);
+        assert_eq!(
+            messages[0],
+            "<ThemeContext.Provider> provides a context, which requires a client component. \
+             Move it into a \"use client\" module"
+        );
+        assert!(messages[1].starts_with("<Contexts.User.Provider>"));
+        assert!(messages[2].starts_with("<LocaleContext>"));
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::OnceLock;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Time budget of a single file's analysis.
+///
+/// Like the memory budget, the deadline is only checked at checkpoints (between passes and every
+/// few elements of the JSX walk), so an analysis overshoots it by at most one step. Once it has
+/// passed, the analysis returns what it found so far instead of holding up the host's queue.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Deadline {
+    /// Clock reading, in milliseconds, after which the analysis gives up
+    at: Option<u64>,
+}
+
+impl Deadline {
+    /// Start a deadline `timeout` milliseconds from now. `None` never expires
+    pub(crate) fn start(timeout: Option<u32>) -> Self {
+        Self {
+            at: timeout.map(|timeout| now().saturating_add(timeout as u64)),
+        }
+    }
+
+    /// Whether the deadline has passed
+    pub(crate) fn expired(&self) -> bool {
+        self.at.is_some_and(|at| now() >= at)
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Milliseconds on a monotonic clock. `wasm32-unknown-unknown` has no clock of its own, so the
+/// component reads the host's
+fn now() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    return crate::now();
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        static EPOCH: OnceLock<Instant> = OnceLock::new();
+        EPOCH.get_or_init(Instant::now).elapsed().as_millis() as u64
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadline_expiry() {
+        assert!(!Deadline::default().expired());
+        assert!(!Deadline::start(None).expired());
+        assert!(!Deadline::start(Some(60_000)).expired());
+        assert!(Deadline::start(Some(0)).expired());
+    }
+}
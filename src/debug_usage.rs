@@ -0,0 +1,149 @@
+use crate::analyze_react_boundary::check::types;
+use oxc::ast::ast::{DebuggerStatement, Expression, Statement};
+use oxc::ast_visit::{Visit, walk};
+use oxc::span::Span;
+use std::collections::HashSet;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// A `console.*` call or a `debugger` statement
+pub(crate) struct DebugUsage {
+    pub(crate) kind: types::DebugUsageKind,
+    /// Method called on `console` (`log`, `error`, ...), `None` for `debugger`
+    pub(crate) method: Option<String>,
+    /// Top-level component whose declaration contains the usage
+    pub(crate) component: Option<String>,
+    pub(crate) span: Span,
+}
+
+/// Collect the `console.*` calls and `debugger` statements of a module, with the top-level
+/// component among `component_names` they are found in, so hosts can enforce rules like "no
+/// `console.log` in server components". Calls through a computed member (`console["log"]`) are
+/// skipped
+pub(crate) fn collect_debug_usages(
+    statements: &[Statement],
+    component_names: &HashSet<&str>,
+) -> Vec<DebugUsage> {
+    // Spans of the top-level component declarations
+    let components = statements
+        .iter()
+        .flat_map(crate::top_level_bindings)
+        .filter(|(name, _)| component_names.contains(name))
+        .collect::<Vec<_>>();
+    let component_at = |span: Span| {
+        components
+            .iter()
+            .find(|(_, declaration)| declaration.start <= span.start && span.end <= declaration.end)
+            .map(|(name, _)| name.to_string())
+    };
+
+    let mut collector = DebugUsageCollector { usages: Vec::new() };
+    for statement in statements {
+        collector.visit_statement(statement);
+    }
+
+    let mut usages = collector
+        .usages
+        .into_iter()
+        .map(|(kind, method, span)| DebugUsage {
+            kind,
+            method,
+            component: component_at(span),
+            span,
+        })
+        .collect::<Vec<_>>();
+    usages.sort_by_key(|usage| usage.span.start);
+    usages
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Records the `console.*` calls and `debugger` statements of a module in source order
+struct DebugUsageCollector {
+    usages: Vec<(types::DebugUsageKind, Option<String>, Span)>,
+}
+
+impl<'a> Visit<'a> for DebugUsageCollector {
+    fn visit_debugger_statement(&mut self, debugger: &DebuggerStatement) {
+        self.usages
+            .push((types::DebugUsageKind::Debugger, None, debugger.span));
+    }
+
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        if let Expression::CallExpression(call) = expr
+            && let Expression::StaticMemberExpression(member) = call.callee.get_inner_expression()
+            && matches!(&member.object, Expression::Identifier(object) if object.name == "console")
+        {
+            self.usages.push((
+                types::DebugUsageKind::Console,
+                Some(member.property.name.to_string()),
+                call.span,
+            ));
+        }
+        walk::walk_expression(self, expr);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_collect_debug_usages() {
+        let source = r#"
+console.info("loaded");
+export default async function Page() {
+  const data = await load();
+  console.log(data);
+  debugger;
+  return <List items={data} />;
+}
+const List = ({ items }) => {
+  useEffect(() => console.error(items), [items]);
+  return <ul />;
+};
+function helper() {
+  console["warn"]("skipped");
+  console.warn("helper");
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let usages = collect_debug_usages(&ret.program.body, &HashSet::from(["Page", "List"]));
+
+        let summary = usages
+            .iter()
+            .map(|usage| {
+                (
+                    usage.kind,
+                    usage.method.as_deref(),
+                    usage.component.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (types::DebugUsageKind::Console, Some("info"), None),
+                (types::DebugUsageKind::Console, Some("log"), Some("Page")),
+                (types::DebugUsageKind::Debugger, None, Some("Page")),
+                (types::DebugUsageKind::Console, Some("error"), Some("List")),
+                (types::DebugUsageKind::Console, Some("warn"), None),
+            ]
+        );
+        let span = usages[2].span;
+        assert_eq!(&source[span.start as usize..span.end as usize], "debugger;");
+    }
+}
@@ -0,0 +1,225 @@
+use crate::analyze_react_boundary::check::types;
+use crate::graph::ModuleGraph;
+
+const CLIENT_FILL: &str = "#fde68a";
+const SERVER_FILL: &str = "#dbeafe";
+const BOUNDARY_STROKE: &str = "#dc2626";
+
+/// How an edge of the diagram is drawn
+#[derive(Clone, Copy, PartialEq)]
+enum Edge {
+    Import,
+    /// A server module importing a client module
+    Boundary,
+    /// A module starting another as a worker
+    Worker,
+    /// A module loading another under a condition
+    Conditional,
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Render the module graph of a file set as a DOT or Mermaid diagram. Client modules and server
+/// modules are filled differently, and the edges where a server module imports a client module,
+/// i.e. where the client boundary is crossed, are drawn thick and red. Worker scripts started with
+/// `new Worker(...)` are linked with dashed edges, and modules loaded under a condition with dotted
+/// edges labeled `conditional`.
+///
+/// Nodes are numbered by path so the output is stable across runs. Files that fail to parse are
+/// left out.
+pub(crate) fn render_graph(files: Vec<types::SourceFile>, format: types::GraphFormat) -> String {
+    let graph = ModuleGraph::new(&files);
+
+    let mut paths = graph.modules.keys().map(String::as_str).collect::<Vec<_>>();
+    paths.sort_unstable();
+    let is_client = |path: &str| {
+        graph
+            .modules
+            .get(path)
+            .is_some_and(|module| module.is_client)
+    };
+    let node = |path: &str| paths.binary_search(&path).unwrap_or_default();
+
+    let edges = paths
+        .iter()
+        .flat_map(|from| {
+            let imports = graph.dependencies(from).into_iter().map(|to| {
+                let edge = if !is_client(from) && is_client(&to) {
+                    Edge::Boundary
+                } else {
+                    Edge::Import
+                };
+                (node(from), node(&to), edge)
+            });
+            let workers = graph
+                .worker_dependencies(from)
+                .into_iter()
+                .map(|to| (node(from), node(&to), Edge::Worker));
+            let conditional = graph
+                .conditional_dependencies(from)
+                .into_iter()
+                .map(|to| (node(from), node(&to), Edge::Conditional));
+            imports
+                .chain(workers)
+                .chain(conditional)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let nodes = paths
+        .iter()
+        .map(|path| (*path, is_client(path)))
+        .collect::<Vec<_>>();
+
+    match format {
+        types::GraphFormat::Dot => render_dot(&nodes, &edges),
+        types::GraphFormat::Mermaid => render_mermaid(&nodes, &edges),
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// `nodes` are `(path, is client)`, `edges` are `(from, to, kind)` with node indices
+fn render_dot(nodes: &[(&str, bool)], edges: &[(usize, usize, Edge)]) -> String {
+    let mut out =
+        String::from("digraph boundary {\n  rankdir=LR;\n  node [shape=box, style=filled];\n");
+    for (index, (path, is_client)) in nodes.iter().enumerate() {
+        let fill = if *is_client { CLIENT_FILL } else { SERVER_FILL };
+        out.push_str(&format!(
+            "  n{} [label=\"{}\", fillcolor=\"{}\"];\n",
+            index,
+            path.replace('\\', "\\\\").replace('"', "\\\""),
+            fill
+        ));
+    }
+    for (from, to, edge) in edges {
+        match edge {
+            Edge::Import => out.push_str(&format!("  n{} -> n{};\n", from, to)),
+            Edge::Boundary => out.push_str(&format!(
+                "  n{} -> n{} [color=\"{}\", penwidth=2];\n",
+                from, to, BOUNDARY_STROKE
+            )),
+            Edge::Worker => out.push_str(&format!("  n{} -> n{} [style=dashed];\n", from, to)),
+            Edge::Conditional => out.push_str(&format!(
+                "  n{} -> n{} [style=dotted, label=\"conditional\"];\n",
+                from, to
+            )),
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn render_mermaid(nodes: &[(&str, bool)], edges: &[(usize, usize, Edge)]) -> String {
+    let mut out = String::from("flowchart LR\n");
+    out.push_str(&format!("  classDef client fill:{}\n", CLIENT_FILL));
+    out.push_str(&format!("  classDef server fill:{}\n", SERVER_FILL));
+    for (index, (path, is_client)) in nodes.iter().enumerate() {
+        out.push_str(&format!(
+            "  n{}[\"{}\"]:::{}\n",
+            index,
+            path.replace('"', "#quot;"),
+            if *is_client { "client" } else { "server" }
+        ));
+    }
+    for (index, (from, to, edge)) in edges.iter().enumerate() {
+        match edge {
+            Edge::Import => out.push_str(&format!("  n{} --> n{}\n", from, to)),
+            Edge::Boundary => {
+                out.push_str(&format!("  n{} ==> n{}\n", from, to));
+                // Links are styled by their position in the diagram
+                out.push_str(&format!(
+                    "  linkStyle {} stroke:{}\n",
+                    index, BOUNDARY_STROKE
+                ));
+            }
+            Edge::Worker => out.push_str(&format!("  n{} -.-> n{}\n", from, to)),
+            Edge::Conditional => out.push_str(&format!("  n{} -. conditional .-> n{}\n", from, to)),
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::source_file;
+
+    fn files() -> Vec<types::SourceFile> {
+        [
+            (
+                "/app/page.tsx",
+                r#"import { Toolbar } from "./toolbar"; import { db } from "./db";
+if (typeof window !== "undefined") require("./zoom");"#,
+            ),
+            ("/app/zoom.ts", r#"export const zoom = 1;"#),
+            ("/app/db.ts", r#"export const db = {};"#),
+            ("/app/worker.ts", r#"self.onmessage = () => {};"#),
+            (
+                "/app/toolbar.tsx",
+                r#""use client"; import { Button } from "./button";
+const worker = new Worker(new URL("./worker.ts", import.meta.url));"#,
+            ),
+            (
+                "/app/button.tsx",
+                r#""use client"; export const Button = 1;"#,
+            ),
+        ]
+        .into_iter()
+        .map(|(path, content)| source_file(path, content))
+        .collect()
+    }
+
+    #[test]
+    fn test_render_dot() {
+        assert_eq!(
+            render_graph(files(), types::GraphFormat::Dot),
+            r##"digraph boundary {
+  rankdir=LR;
+  node [shape=box, style=filled];
+  n0 [label="/app/button.tsx", fillcolor="#fde68a"];
+  n1 [label="/app/db.ts", fillcolor="#dbeafe"];
+  n2 [label="/app/page.tsx", fillcolor="#dbeafe"];
+  n3 [label="/app/toolbar.tsx", fillcolor="#fde68a"];
+  n4 [label="/app/worker.ts", fillcolor="#dbeafe"];
+  n5 [label="/app/zoom.ts", fillcolor="#dbeafe"];
+  n2 -> n1;
+  n2 -> n3 [color="#dc2626", penwidth=2];
+  n2 -> n5 [style=dotted, label="conditional"];
+  n3 -> n0;
+  n3 -> n4 [style=dashed];
+}
+"##
+        );
+    }
+
+    #[test]
+    fn test_render_mermaid() {
+        assert_eq!(
+            render_graph(files(), types::GraphFormat::Mermaid),
+            r##"flowchart LR
+  classDef client fill:#fde68a
+  classDef server fill:#dbeafe
+  n0["/app/button.tsx"]:::client
+  n1["/app/db.ts"]:::server
+  n2["/app/page.tsx"]:::server
+  n3["/app/toolbar.tsx"]:::client
+  n4["/app/worker.ts"]:::server
+  n5["/app/zoom.ts"]:::server
+  n2 --> n1
+  n2 ==> n3
+  linkStyle 1 stroke:#dc2626
+  n2 -. conditional .-> n5
+  n3 --> n0
+  n3 -.-> n4
+"##
+        );
+    }
+}
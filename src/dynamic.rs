@@ -0,0 +1,235 @@
+use oxc::ast::ast::{
+    Argument, BindingPatternKind, Declaration, Expression, FunctionBody,
+    ImportDeclarationSpecifier, ObjectPropertyKind, PropertyKey, Statement,
+};
+use oxc::span::Span;
+
+/// Module whose default export lazily loads a component (`dynamic(() => import("./chart"))`)
+pub(crate) const NEXT_DYNAMIC: &str = "next/dynamic";
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// A component loaded through `next/dynamic`
+pub(crate) struct DynamicBoundary {
+    /// Name of the binding the component is assigned to
+    pub(crate) name: String,
+    /// Specifier of the `import(...)` the loader returns
+    pub(crate) source: String,
+    pub(crate) source_span: Span,
+    /// `false` when the component is only rendered on the client (`{ ssr: false }`)
+    pub(crate) ssr: bool,
+    /// Span of the `dynamic(...)` call
+    pub(crate) span: Span,
+}
+
+/// Collect the top-level bindings initialized with `dynamic(() => import("..."))`, where `dynamic`
+/// is the default import of `next/dynamic`. A server module loading a client component this way
+/// declares the client boundary on purpose, so it is reported apart from plain imports. Loaders
+/// that don't return an `import(...)` of a string literal are skipped
+pub(crate) fn collect_dynamic_boundaries(statements: &[Statement]) -> Vec<DynamicBoundary> {
+    let locals = dynamic_locals(statements);
+    if locals.is_empty() {
+        return Vec::new();
+    }
+
+    let mut boundaries = Vec::new();
+    for statement in statements {
+        let declaration = match statement {
+            Statement::ExportNamedDeclaration(export_decl) => export_decl.declaration.as_ref(),
+            statement => statement.as_declaration(),
+        };
+        let Some(Declaration::VariableDeclaration(var_decl)) = declaration else {
+            continue;
+        };
+
+        for declarator in var_decl.declarations.iter() {
+            let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind else {
+                continue;
+            };
+            let Some(Expression::CallExpression(call)) = declarator
+                .init
+                .as_ref()
+                .map(|init| init.get_inner_expression())
+            else {
+                continue;
+            };
+            if !matches!(call.callee.get_inner_expression(), Expression::Identifier(callee)
+                if locals.contains(&callee.name.as_str()))
+            {
+                continue;
+            }
+
+            let Some((source, source_span)) = call
+                .arguments
+                .first()
+                .and_then(Argument::as_expression)
+                .and_then(loader_source)
+            else {
+                continue;
+            };
+            boundaries.push(DynamicBoundary {
+                name: ident.name.to_string(),
+                source,
+                source_span,
+                ssr: call
+                    .arguments
+                    .get(1)
+                    .and_then(Argument::as_expression)
+                    .is_none_or(|options| !disables_ssr(options)),
+                span: call.span,
+            });
+        }
+    }
+
+    boundaries
+}
+
+/// Local names of the default imports of `next/dynamic`
+pub(crate) fn dynamic_locals<'s>(statements: &'s [Statement]) -> Vec<&'s str> {
+    statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::ImportDeclaration(import_decl)
+                if import_decl.source.value == NEXT_DYNAMIC
+                    && !import_decl.import_kind.is_type() =>
+            {
+                import_decl.specifiers.as_ref()
+            }
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|specifier| match specifier {
+            ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                Some(spec.local.name.as_str())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Specifier and span of the `import(...)` a loader function returns: `() => import("./chart")`,
+/// `() => import("./chart").then((mod) => mod.Chart)`, or a body returning either
+fn loader_source(loader: &Expression) -> Option<(String, Span)> {
+    match loader.get_inner_expression() {
+        Expression::ArrowFunctionExpression(arrow) if arrow.expression => {
+            match arrow.body.statements.first()? {
+                Statement::ExpressionStatement(statement) => import_source(&statement.expression),
+                _ => None,
+            }
+        }
+        Expression::ArrowFunctionExpression(arrow) => returned_import_source(&arrow.body),
+        Expression::FunctionExpression(func) => returned_import_source(func.body.as_ref()?),
+        _ => None,
+    }
+}
+
+fn returned_import_source(body: &FunctionBody) -> Option<(String, Span)> {
+    body.statements
+        .iter()
+        .find_map(|statement| match statement {
+            Statement::ReturnStatement(ret) => import_source(ret.argument.as_ref()?),
+            _ => None,
+        })
+}
+
+/// Specifier of `import("...")`, looking through `.then(...)` and `.catch(...)` chains
+fn import_source(expr: &Expression) -> Option<(String, Span)> {
+    match expr.get_inner_expression() {
+        Expression::ImportExpression(import) => match &import.source {
+            Expression::StringLiteral(literal) => Some((literal.value.to_string(), literal.span)),
+            _ => None,
+        },
+        Expression::CallExpression(call) => match call.callee.get_inner_expression() {
+            Expression::StaticMemberExpression(member)
+                if matches!(member.property.name.as_str(), "then" | "catch") =>
+            {
+                import_source(&member.object)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether the options object passed to `dynamic` contains `ssr: false`
+fn disables_ssr(options: &Expression) -> bool {
+    let Expression::ObjectExpression(object) = options.get_inner_expression() else {
+        return false;
+    };
+    object.properties.iter().any(|property| {
+        matches!(property, ObjectPropertyKind::ObjectProperty(property)
+            if matches!(&property.key, PropertyKey::StaticIdentifier(key) if key.name == "ssr")
+                && matches!(&property.value, Expression::BooleanLiteral(value) if !value.value))
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_collect_dynamic_boundaries() {
+        let source = r#"
+import load from "next/dynamic";
+import { lazy } from "react";
+
+const Map = load(() => import("./map"), { ssr: false });
+export const Chart = load(() => import("./chart").then((mod) => mod.Chart));
+const Editor = load(async function () {
+  return import("./editor");
+}, { loading: () => null, ssr: true });
+const Lazy = lazy(() => import("./lazy"));
+const Computed = load(() => import(path));
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let boundaries = collect_dynamic_boundaries(&ret.program.body);
+
+        let summary = boundaries
+            .iter()
+            .map(|boundary| {
+                (
+                    boundary.name.as_str(),
+                    boundary.source.as_str(),
+                    boundary.ssr,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("Map", "./map", false),
+                ("Chart", "./chart", true),
+                ("Editor", "./editor", true),
+            ]
+        );
+        let span = boundaries[0].source_span;
+        assert_eq!(&source[span.start as usize..span.end as usize], "\"./map\"");
+    }
+
+    #[test]
+    fn test_collect_dynamic_boundaries_without_import() {
+        let source = r#"
+const dynamic = (load) => load;
+const Map = dynamic(() => import("./map"), { ssr: false });
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        assert!(collect_dynamic_boundaries(&ret.program.body).is_empty());
+    }
+}
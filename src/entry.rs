@@ -0,0 +1,358 @@
+use crate::analyze_react_boundary::check::types;
+use crate::rules::Rule;
+use oxc::allocator::Allocator;
+use oxc::ast::ast::Statement;
+use oxc::parser::Parser;
+use oxc::span::SourceType;
+
+/// Extensions Next.js picks up for app router special files
+const ENTRY_EXTENSIONS: [&str; 4] = ["js", "jsx", "ts", "tsx"];
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Classify a Next.js app router special file by its path, or `None` if the path isn't one
+pub(crate) fn classify_entry(path: &str) -> Option<types::EntryKind> {
+    let path = path.replace('\\', "/");
+    let mut segments = path.split('/').rev();
+    let file_name = segments.next()?;
+
+    // Special files only have meaning inside the app directory
+    if !segments.any(|segment| segment == "app") {
+        return None;
+    }
+
+    let (stem, extension) = file_name.rsplit_once('.')?;
+    if !ENTRY_EXTENSIONS.contains(&extension) {
+        return None;
+    }
+
+    match stem {
+        "page" => Some(types::EntryKind::Page),
+        "layout" => Some(types::EntryKind::Layout),
+        "template" => Some(types::EntryKind::Template),
+        "loading" => Some(types::EntryKind::Loading),
+        "error" => Some(types::EntryKind::Error),
+        "not-found" => Some(types::EntryKind::NotFound),
+        "route" => Some(types::EntryKind::Route),
+        _ => None,
+    }
+}
+
+/// Analyze a file and check it against the conventions of its app router entry kind:
+/// - `error` files must be client components
+/// - component entries must default export a component
+/// - `route` handlers must not be client modules or export components
+///
+/// Each convention is a rule, reported with the severity `rules` configure for it
+pub(crate) fn check_entry(
+    path: &str,
+    content: Vec<u8>,
+    extension: String,
+    rules: &[types::RuleSetting],
+    is_cancelled: &dyn Fn() -> bool,
+) -> Result<types::EntryCheck, String> {
+    let kind = classify_entry(path);
+    // Whether the module has a default export at all, which the component heuristic can't tell:
+    // `export default Home` of an imported binding or a layout returning `children` is no
+    // detected component
+    let has_default_export = kind.is_some() && has_default_export(&content, &extension);
+    let analysis = crate::analyze_source(content, extension, is_cancelled)?;
+
+    let diagnostics = match kind {
+        Some(kind) => entry_diagnostics(kind, &analysis, has_default_export)
+            .into_iter()
+            .filter_map(|(rule, message, range)| {
+                Some(types::Diagnostic {
+                    severity: rule.severity(rules)?,
+                    rule: rule.id().to_string(),
+                    message,
+                    range,
+                    related_range: None,
+                })
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    Ok(types::EntryCheck {
+        kind,
+        analysis,
+        diagnostics,
+    })
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Entry convention violations as (rule, message, range)
+fn entry_diagnostics(
+    kind: types::EntryKind,
+    analysis: &types::AnalysisResult,
+    has_default_export: bool,
+) -> Vec<(Rule, String, types::Range)> {
+    let start_of_file = types::Range {
+        start: types::Position {
+            line: 0,
+            character: 0,
+        },
+        end: types::Position {
+            line: 0,
+            character: 0,
+        },
+    };
+    let default_export = analysis.components.iter().find(|component| {
+        matches!(
+            component.export_kind,
+            types::ExportKind::Default | types::ExportKind::Both
+        )
+    });
+
+    let mut diagnostics = Vec::new();
+
+    if kind == types::EntryKind::Route {
+        if let Some(range) = analysis.use_client_directive {
+            diagnostics.push((
+                Rule::RouteHandlerClient,
+                "Route handlers run on the server and cannot be client modules".to_string(),
+                range,
+            ));
+        }
+        if let Some(component) = default_export {
+            diagnostics.push((
+                Rule::RouteHandlerComponent,
+                format!(
+                    "Route handlers export HTTP methods, not components: {}",
+                    component.name
+                ),
+                component.export_range,
+            ));
+        }
+        return diagnostics;
+    }
+
+    if kind == types::EntryKind::Error && analysis.use_client_directive.is_none() {
+        diagnostics.push((
+            Rule::ErrorBoundaryNotClient,
+            "Error boundaries must be client components, add \"use client\"".to_string(),
+            start_of_file,
+        ));
+    }
+
+    if !has_default_export {
+        diagnostics.push((
+            Rule::MissingDefaultExport,
+            format!("{} files must default export a component", entry_name(kind)),
+            start_of_file,
+        ));
+    }
+
+    diagnostics
+}
+
+/// Whether the module has a default export: `export default ...`, `export { x as default }` or a
+/// re-exported default (`export { default } from "../features/home"`). Sources that don't parse
+/// count as having one, so only the parse errors are reported
+fn has_default_export(content: &[u8], extension: &str) -> bool {
+    let (Ok(source_text), Ok(source_type)) = (
+        std::str::from_utf8(content),
+        SourceType::from_extension(extension),
+    ) else {
+        return true;
+    };
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    if ret.panicked {
+        return true;
+    }
+
+    ret.program.body.iter().any(|statement| match statement {
+        Statement::ExportDefaultDeclaration(_) => true,
+        Statement::ExportNamedDeclaration(export_decl) => {
+            !export_decl.export_kind.is_type()
+                && export_decl.specifiers.iter().any(|specifier| {
+                    !specifier.export_kind.is_type()
+                        && crate::module_export_name(&specifier.exported) == "default"
+                })
+        }
+        _ => false,
+    })
+}
+
+fn entry_name(kind: types::EntryKind) -> &'static str {
+    match kind {
+        types::EntryKind::Page => "page",
+        types::EntryKind::Layout => "layout",
+        types::EntryKind::Template => "template",
+        types::EntryKind::Loading => "loading",
+        types::EntryKind::Error => "error",
+        types::EntryKind::NotFound => "not-found",
+        types::EntryKind::Route => "route",
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(path: &str, source: &str) -> types::EntryCheck {
+        let extension = crate::test_support::extension(path);
+        check_entry(path, source.as_bytes().to_vec(), extension, &[], &|| false).unwrap()
+    }
+
+    #[test]
+    fn test_classify_entry() {
+        assert_eq!(
+            classify_entry("/repo/app/dashboard/page.tsx"),
+            Some(types::EntryKind::Page)
+        );
+        assert_eq!(
+            classify_entry("C:\\repo\\src\\app\\layout.js"),
+            Some(types::EntryKind::Layout)
+        );
+        assert_eq!(
+            classify_entry("/repo/app/(marketing)/not-found.jsx"),
+            Some(types::EntryKind::NotFound)
+        );
+        assert_eq!(
+            classify_entry("/repo/app/api/users/route.ts"),
+            Some(types::EntryKind::Route)
+        );
+    }
+
+    #[test]
+    fn test_classify_entry_outside_app_or_unknown() {
+        assert_eq!(classify_entry("/repo/pages/page.tsx"), None);
+        assert_eq!(classify_entry("/repo/app/button.tsx"), None);
+        assert_eq!(classify_entry("/repo/app/page.mdx"), None);
+        assert_eq!(classify_entry("page.tsx"), None);
+    }
+
+    #[test]
+    fn test_check_entry_error_requires_use_client() {
+        let result = check(
+            "/repo/app/error.tsx",
+            "export default function Error() { return <div />; }",
+        );
+
+        assert_eq!(result.kind, Some(types::EntryKind::Error));
+        assert_eq!(result.diagnostics.len(), 1);
+        assert!(result.diagnostics[0].message.contains("use client"));
+
+        let result = check(
+            "/repo/app/error.tsx",
+            r#""use client"; export default function Error() { return <div />; }"#,
+        );
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_check_entry_requires_default_export() {
+        let result = check("/repo/app/page.tsx", "export const Page = () => <div />;");
+
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(
+            result.diagnostics[0].message,
+            "page files must default export a component"
+        );
+    }
+
+    #[test]
+    fn test_check_entry_default_export_forms() {
+        let sources = [
+            ("page.tsx", r#"export { default } from "../features/home";"#),
+            (
+                "page.tsx",
+                r#"import Home from "../features/home"; export default Home;"#,
+            ),
+            (
+                "page.tsx",
+                r#"function Home() { return <main />; } export { Home as default };"#,
+            ),
+            (
+                "layout.tsx",
+                "export default function Layout({ children }) { return children; }",
+            ),
+            (
+                "loading.tsx",
+                "export default function Loading() { return null; }",
+            ),
+            (
+                "page.tsx",
+                r#"import { redirect } from "next/navigation";
+export default function Page() { redirect("/login"); }"#,
+            ),
+        ];
+
+        for (file, source) in sources {
+            let result = check(&format!("/repo/app/{}", file), source);
+            assert!(
+                result.diagnostics.is_empty(),
+                "{}: {:?}",
+                source,
+                result
+                    .diagnostics
+                    .iter()
+                    .map(|diagnostic| &diagnostic.message)
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_entry_route_handler() {
+        let result = check(
+            "/repo/app/api/route.ts",
+            r#""use client"; export async function GET() { return new Response("ok"); }"#,
+        );
+
+        assert_eq!(result.kind, Some(types::EntryKind::Route));
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].range.start.character, 0);
+        assert_eq!(result.diagnostics[0].range.end.character, 12);
+    }
+
+    #[test]
+    fn test_check_entry_rule_settings() {
+        let source = "export const Page = () => <div />;";
+        assert_eq!(
+            check("/repo/app/page.tsx", source).diagnostics[0].rule,
+            "missing-default-export"
+        );
+
+        let check_with = |level| {
+            let rules = [types::RuleSetting {
+                rule: "missing-default-export".to_string(),
+                level,
+            }];
+            check_entry(
+                "/repo/app/page.tsx",
+                source.as_bytes().to_vec(),
+                "tsx".to_string(),
+                &rules,
+                &|| false,
+            )
+            .unwrap()
+            .diagnostics
+        };
+        assert!(check_with(types::RuleLevel::Off).is_empty());
+        assert_eq!(
+            check_with(types::RuleLevel::Hint)[0].severity,
+            types::Severity::Hint
+        );
+    }
+
+    #[test]
+    fn test_check_entry_regular_file_has_no_diagnostics() {
+        let result = check("/repo/app/button.tsx", "export const Button = 1;");
+
+        assert_eq!(result.kind, None);
+        assert!(result.diagnostics.is_empty());
+    }
+}
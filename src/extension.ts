@@ -22,17 +22,25 @@ export async function activate(context: vscode.ExtensionContext) {
     context.extensionUri,
     'target',
     'wasm32-unknown-unknown',
-    process.env.NODE_ENV === 'production' ? 'release' : 'debug',
+    process.env.NODE_ENV === 'production' ? 'extension' : 'debug',
     'check_react_boundary.wasm',
   );
   const bits = await vscode.workspace.fs.readFile(filename);
   const module = await WebAssembly.compile(bits as Uint8Array<ArrayBuffer>);
 
-  // The implementation of the log function that is called from WASM
+  // The implementation of the functions that are called from WASM
   const service: analyzeReactBoundary.Imports = {
     log: (msg: string) => {
       channel.info(msg);
     },
+    // Analysis runs synchronously on the extension host thread, so an editor
+    // switch can never be observed in the middle of a call
+    isCancelled: () => false,
+    now: () => BigInt(Math.floor(performance.now())),
+    reportProgress: (_done: number, _total: number, _currentPath: string) => {},
+    // Cross-file analysis is not wired up in the extension yet
+    resolve: (_specifier: string, _from: string) => undefined,
+    readFile: (_path: string) => undefined,
   };
 
   // The context for the WASM module
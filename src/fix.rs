@@ -0,0 +1,679 @@
+use crate::analyze_react_boundary::check::types;
+use crate::dynamic::{NEXT_DYNAMIC, dynamic_locals};
+use oxc::allocator::Allocator;
+use oxc::ast::ast::{
+    BindingPatternKind, Declaration, ExportDefaultDeclarationKind, ImportDeclaration,
+    ImportDeclarationSpecifier, Statement,
+};
+use oxc::parser::Parser;
+use oxc::semantic::SemanticBuilder;
+use oxc::span::{GetSpan, SourceType, Span};
+use std::collections::HashSet;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Edits replacing the static import at `import_range` with `next/dynamic` declarations that only
+/// load the imported components on the client:
+///
+/// ```text
+/// import { Chart } from "./chart";
+/// // becomes
+/// const Chart = dynamic(() => import("./chart").then((mod) => mod.Chart), { ssr: false });
+/// ```
+///
+/// Each binding of the import gets a declaration of its own, default imports loading the module
+/// itself. `import dynamic from "next/dynamic"` is added before the first import unless the module
+/// already has one, in which case its local name is used. `import_range` is matched against the
+/// import declarations the way the analysis reports them, with 0-based lines and columns counting
+/// characters; any position inside the import selects it.
+///
+/// Namespace, side-effect and type-only imports have no components to load lazily and are
+/// rejected with an error, as are positions outside any import.
+pub(crate) fn dynamic_import_fix(
+    content: Vec<u8>,
+    extension: String,
+    import_range: types::Range,
+) -> Result<Vec<types::TextEdit>, String> {
+    let source_text =
+        String::from_utf8(content).map_err(|e| format!("Invalid UTF-8 source: {}", e))?;
+    let source_type = SourceType::from_extension(&extension)
+        .map_err(|e| format!("{}: {}", e.to_string().replace("\"", ""), extension))?;
+
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, &source_text, source_type).parse();
+    if ret.panicked {
+        return Err("Failed to parse source".to_string());
+    }
+    let statements = &ret.program.body;
+
+    let offset = crate::range::offset_at(
+        &source_text,
+        import_range.start,
+        types::PositionEncoding::Utf32,
+    );
+    let import_decl = statements
+        .iter()
+        .find_map(|statement| match statement {
+            Statement::ImportDeclaration(import_decl)
+                if import_decl.span.start <= offset && offset < import_decl.span.end =>
+            {
+                Some(import_decl)
+            }
+            _ => None,
+        })
+        .ok_or_else(|| "No import declaration at the given range".to_string())?;
+    if import_decl.source.value == NEXT_DYNAMIC {
+        return Err(format!(
+            "\"{}\" can't be loaded through itself",
+            NEXT_DYNAMIC
+        ));
+    }
+
+    let existing = dynamic_locals(statements).first().copied();
+    let dynamic = existing.unwrap_or("dynamic");
+    let declarations = dynamic_declarations(import_decl, dynamic)?;
+
+    let to_edit = |span: Span, new_text: String| types::TextEdit {
+        range: crate::range::span_to_range(&source_text, span),
+        new_text,
+    };
+    let first_import = statements.iter().find_map(|statement| match statement {
+        Statement::ImportDeclaration(first) => Some(first.span),
+        _ => None,
+    });
+    let dynamic_import = format!("import {} from \"{}\";\n", dynamic, NEXT_DYNAMIC);
+
+    Ok(match (existing, first_import) {
+        // Insert the `next/dynamic` import in place of the converted import when that one comes
+        // first, so the edits never touch the same position
+        (None, Some(first)) if first == import_decl.span => {
+            vec![to_edit(import_decl.span, dynamic_import + &declarations)]
+        }
+        (None, Some(first)) => vec![
+            to_edit(Span::new(first.start, first.start), dynamic_import),
+            to_edit(import_decl.span, declarations),
+        ],
+        _ => vec![to_edit(import_decl.span, declarations)],
+    })
+}
+
+/// Edits moving the top-level component `component` of a server module into a new "use client"
+/// module next to it, named after the component (`/app/Counter.tsx`), so the client-only code it
+/// contains becomes a client island. The new module exports the component by name and gets the
+/// imports of the original module whose bindings the component references. The original module
+/// imports it back from the new one in place of the declaration, exporting it again the way it
+/// did before; imports left unused there are kept.
+///
+/// Other top-level bindings of the module can't follow the component without breaking the code
+/// still using them, so a component referencing one is rejected with an error naming them, as are
+/// "use client" modules and components that aren't declared at the top level.
+pub(crate) fn extract_client_island(
+    path: &str,
+    content: Vec<u8>,
+    extension: String,
+    component: &str,
+) -> Result<types::WorkspaceEdit, String> {
+    let source_text =
+        String::from_utf8(content).map_err(|e| format!("Invalid UTF-8 source: {}", e))?;
+    let source_type = SourceType::from_extension(&extension)
+        .map_err(|e| format!("{}: {}", e.to_string().replace("\"", ""), extension))?;
+
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, &source_text, source_type).parse();
+    if ret.panicked {
+        return Err("Failed to parse source".to_string());
+    }
+    let program = &ret.program;
+    if program
+        .directives
+        .iter()
+        .any(|directive| directive.directive == "use client")
+    {
+        return Err("The module is already a \"use client\" module".to_string());
+    }
+
+    let (statement, declaration_span, export) = program
+        .body
+        .iter()
+        .find_map(|statement| component_declaration(statement, component))
+        .ok_or_else(|| format!("No top-level declaration of {}", component))?;
+    let declaration = &source_text[declaration_span.start as usize..declaration_span.end as usize];
+
+    // Top-level bindings the declaration references, resolved through scopes so shadowing locals,
+    // JSX text, strings and property keys don't count
+    let semantic = SemanticBuilder::new().build(program).semantic;
+    let scoping = semantic.scoping();
+    let referenced = scoping
+        .iter_bindings_in(scoping.root_scope_id())
+        .filter(|&symbol| {
+            !declaration_span.contains_inclusive(scoping.symbol_span(symbol))
+                && scoping
+                    .get_resolved_reference_ids(symbol)
+                    .iter()
+                    .any(|&reference| {
+                        let node = scoping.get_reference(reference).node_id();
+                        declaration_span.contains_inclusive(semantic.nodes().kind(node).span())
+                    })
+        })
+        .map(|symbol| scoping.symbol_name(symbol))
+        .collect::<HashSet<_>>();
+
+    let locals = program
+        .body
+        .iter()
+        .filter(|other| other.span() != statement.span())
+        .flat_map(crate::top_level_bindings)
+        .map(|(name, _)| name)
+        .filter(|name| referenced.contains(name))
+        .collect::<Vec<_>>();
+    if !locals.is_empty() {
+        return Err(format!(
+            "{} uses {} of the module, which would be left behind",
+            component,
+            locals.join(", ")
+        ));
+    }
+
+    // Imports of the original module the component needs, keeping only the bindings it references
+    let imports = program
+        .body
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::ImportDeclaration(import_decl) => {
+                needed_import(import_decl, &source_text, &referenced)
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let mut island = String::from("\"use client\";\n\n");
+    if !imports.is_empty() {
+        island.push_str(&imports.join("\n"));
+        island.push_str("\n\n");
+    }
+    island.push_str("export ");
+    island.push_str(declaration);
+    island.push('\n');
+
+    let island_path = match path.rsplit_once('/') {
+        Some((dir, _)) => format!("{}/{}.{}", dir, component, extension),
+        // A bare file name gets a bare sibling
+        None => format!("{}.{}", component, extension),
+    };
+    let import = format!("import {{ {} }} from \"./{}\";", component, component);
+    let replacement = match export {
+        Some(types::ExportKind::Default) => format!("export default {};", component),
+        Some(_) => format!("export {{ {} }};", component),
+        None => String::new(),
+    };
+
+    // Drop the declaration with its line break when nothing replaces it
+    let mut removed = statement.span();
+    if replacement.is_empty() && source_text[removed.end as usize..].starts_with('\n') {
+        removed.end += 1;
+    }
+    let last_import = program
+        .body
+        .iter()
+        .rfind(|statement| matches!(statement, Statement::ImportDeclaration(_)))
+        .map(GetSpan::span);
+    let to_edit = |span: Span, new_text: String| types::TextEdit {
+        range: crate::range::span_to_range(&source_text, span),
+        new_text,
+    };
+    let edits = match last_import {
+        Some(last) => vec![
+            to_edit(Span::new(last.end, last.end), format!("\n{}", import)),
+            to_edit(removed, replacement),
+        ],
+        // Without imports, the import takes the place of the declaration
+        None if replacement.is_empty() => vec![to_edit(removed, format!("{}\n", import))],
+        None => vec![to_edit(removed, format!("{}\n{}", import, replacement))],
+    };
+
+    Ok(types::WorkspaceEdit {
+        created_files: vec![types::CreatedFile {
+            path: island_path,
+            content: island,
+        }],
+        file_edits: vec![types::FileEdit {
+            path: path.to_string(),
+            edits,
+        }],
+    })
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// One `const X = dynamic(...)` declaration per binding of the import, separated by line breaks
+fn dynamic_declarations(import_decl: &ImportDeclaration, dynamic: &str) -> Result<String, String> {
+    if import_decl.import_kind.is_type() {
+        return Err("Type-only imports load nothing at runtime".to_string());
+    }
+    let specifiers = import_decl
+        .specifiers
+        .as_ref()
+        .filter(|specifiers| !specifiers.is_empty())
+        .ok_or_else(|| "Side-effect imports have no component to load".to_string())?;
+
+    let source = &import_decl.source.value;
+    let mut declarations = Vec::new();
+    for specifier in specifiers.iter() {
+        let (local, loader) = match specifier {
+            ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                (&spec.local.name, format!("import(\"{}\")", source))
+            }
+            ImportDeclarationSpecifier::ImportSpecifier(spec) if spec.import_kind.is_type() => {
+                return Err(format!(
+                    "Type-only import of {} can't be loaded dynamically",
+                    spec.local.name
+                ));
+            }
+            ImportDeclarationSpecifier::ImportSpecifier(spec) => {
+                let imported = spec.imported.name();
+                let access = if is_identifier_name(&imported) {
+                    format!("mod.{}", imported)
+                } else {
+                    format!("mod[\"{}\"]", imported)
+                };
+                (
+                    &spec.local.name,
+                    format!("import(\"{}\").then((mod) => {})", source, access),
+                )
+            }
+            ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
+                return Err(format!(
+                    "Namespace import {} can't be loaded dynamically",
+                    spec.local.name
+                ));
+            }
+        };
+        declarations.push(format!(
+            "const {} = {}(() => {}, {{ ssr: false }});",
+            local, dynamic, loader
+        ));
+    }
+
+    Ok(declarations.join("\n"))
+}
+
+/// The statement declaring `name` as a function, class or variable, the span of the declaration
+/// without its `export` keywords, and how the statement exports it
+fn component_declaration<'s, 'a>(
+    statement: &'s Statement<'a>,
+    name: &str,
+) -> Option<(&'s Statement<'a>, Span, Option<types::ExportKind>)> {
+    let (declaration, export) = match statement {
+        Statement::ExportNamedDeclaration(export_decl) => (
+            export_decl.declaration.as_ref()?,
+            Some(types::ExportKind::Named),
+        ),
+        Statement::ExportDefaultDeclaration(export_decl) => {
+            let (id, span) = match &export_decl.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                    (func.id.as_ref()?, func.span)
+                }
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                    (class.id.as_ref()?, class.span)
+                }
+                _ => return None,
+            };
+            return (id.name == name).then_some((
+                statement,
+                span,
+                Some(types::ExportKind::Default),
+            ));
+        }
+        statement => (statement.as_declaration()?, None),
+    };
+
+    let declares = match declaration {
+        Declaration::FunctionDeclaration(func) => {
+            func.id.as_ref().is_some_and(|id| id.name == name)
+        }
+        Declaration::ClassDeclaration(class) => class.id.as_ref().is_some_and(|id| id.name == name),
+        // Moving one of several declarators would split the declaration
+        Declaration::VariableDeclaration(var_decl) => {
+            var_decl.declarations.len() == 1
+                && matches!(&var_decl.declarations[0].id.kind,
+                    BindingPatternKind::BindingIdentifier(ident) if ident.name == name)
+        }
+        _ => false,
+    };
+    declares.then(|| (statement, declaration.span(), export))
+}
+
+/// The import declaration restricted to the `referenced` bindings, or `None` if it binds none of
+/// them. Imports whose bindings are all referenced are copied as written
+fn needed_import(
+    import_decl: &ImportDeclaration,
+    source: &str,
+    referenced: &HashSet<&str>,
+) -> Option<String> {
+    let specifiers = import_decl.specifiers.as_ref()?;
+    let used = specifiers
+        .iter()
+        .filter(|specifier| referenced.contains(specifier.local().name.as_str()))
+        .collect::<Vec<_>>();
+    if used.is_empty() {
+        return None;
+    }
+    if used.len() == specifiers.len() {
+        return Some(
+            source[import_decl.span.start as usize..import_decl.span.end as usize].to_string(),
+        );
+    }
+
+    let text = |span: Span| &source[span.start as usize..span.end as usize];
+    let mut clauses = Vec::new();
+    let mut named = Vec::new();
+    for specifier in used {
+        match specifier {
+            ImportDeclarationSpecifier::ImportSpecifier(spec) => named.push(text(spec.span)),
+            ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                clauses.push(text(spec.span).to_string())
+            }
+            ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
+                clauses.push(text(spec.span).to_string())
+            }
+        }
+    }
+    if !named.is_empty() {
+        clauses.push(format!("{{ {} }}", named.join(", ")));
+    }
+    Some(format!(
+        "import {}{} from {};",
+        if import_decl.import_kind.is_type() {
+            "type "
+        } else {
+            ""
+        },
+        clauses.join(", "),
+        text(import_decl.source.span)
+    ))
+}
+
+fn is_identifier_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(line: u32, character: u32) -> types::Position {
+        types::Position { line, character }
+    }
+
+    fn fix(source: &str, line: u32) -> Result<Vec<types::TextEdit>, String> {
+        dynamic_import_fix(
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            types::Range {
+                start: position(line, 0),
+                end: position(line, 0),
+            },
+        )
+    }
+
+    /// Apply edits that don't overlap, last first so earlier ranges stay valid
+    fn apply(source: &str, mut edits: Vec<types::TextEdit>) -> String {
+        edits.sort_by_key(|edit| (edit.range.start.line, edit.range.start.character));
+        let mut result = source.to_string();
+        for edit in edits.into_iter().rev() {
+            let start =
+                crate::range::offset_at(&result, edit.range.start, types::PositionEncoding::Utf32)
+                    as usize;
+            let end =
+                crate::range::offset_at(&result, edit.range.end, types::PositionEncoding::Utf32)
+                    as usize;
+            result.replace_range(start..end, &edit.new_text);
+        }
+        result
+    }
+
+    #[test]
+    fn test_dynamic_import_fix() {
+        let source = r#"import { useState } from "react";
+import Map, { Chart as LineChart, "data-grid" as Grid } from "./charts";
+"#;
+
+        let edits = fix(source, 1).unwrap();
+        assert_eq!(edits.len(), 2);
+        assert_eq!(
+            apply(source, edits),
+            r#"import dynamic from "next/dynamic";
+import { useState } from "react";
+const Map = dynamic(() => import("./charts"), { ssr: false });
+const LineChart = dynamic(() => import("./charts").then((mod) => mod.Chart), { ssr: false });
+const Grid = dynamic(() => import("./charts").then((mod) => mod["data-grid"]), { ssr: false });
+"#
+        );
+    }
+
+    #[test]
+    fn test_dynamic_import_fix_first_import() {
+        let source = "import { Editor } from \"./editor\";\nexport default Editor;\n";
+
+        let edits = fix(source, 0).unwrap();
+        assert_eq!(edits.len(), 1);
+        assert_eq!(
+            apply(source, edits),
+            "import dynamic from \"next/dynamic\";\nconst Editor = dynamic(() => \
+             import(\"./editor\").then((mod) => mod.Editor), { ssr: false });\nexport default \
+             Editor;\n"
+        );
+    }
+
+    #[test]
+    fn test_dynamic_import_fix_existing_dynamic_import() {
+        let source = "import lazy from \"next/dynamic\";\nimport Editor from \"./editor\";\n";
+
+        let edits = fix(source, 1).unwrap();
+        assert_eq!(
+            apply(source, edits),
+            "import lazy from \"next/dynamic\";\nconst Editor = lazy(() => import(\"./editor\"), \
+             { ssr: false });\n"
+        );
+    }
+
+    #[test]
+    fn test_dynamic_import_fix_rejects_other_imports() {
+        let source = r#"import * as charts from "./charts";
+import "./styles.css";
+import type { Props } from "./props";
+const x = 1;
+"#;
+
+        assert!(
+            fix(source, 0)
+                .unwrap_err()
+                .contains("Namespace import charts")
+        );
+        assert!(fix(source, 1).unwrap_err().contains("Side-effect"));
+        assert!(fix(source, 2).unwrap_err().contains("Type-only"));
+        assert!(
+            fix(source, 3)
+                .unwrap_err()
+                .contains("No import declaration")
+        );
+    }
+
+    #[test]
+    fn test_extract_client_island() {
+        let source = r#"import { useState, type ReactNode } from "react";
+import { db } from "./db";
+import Icon from "./icon";
+
+export function Counter({ icon }: { icon: ReactNode }) {
+  const [count, setCount] = useState(0);
+  return <button onClick={() => setCount(count + 1)}><Icon />{count}</button>;
+}
+
+export default async function Page() {
+  const rows = await db.query();
+  return <Counter icon={rows.length} />;
+}
+"#;
+
+        let edit = extract_client_island(
+            "/app/page.tsx",
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            "Counter",
+        )
+        .unwrap();
+
+        assert_eq!(edit.created_files.len(), 1);
+        assert_eq!(edit.created_files[0].path, "/app/Counter.tsx");
+        assert_eq!(
+            edit.created_files[0].content,
+            r#""use client";
+
+import { useState, type ReactNode } from "react";
+import Icon from "./icon";
+
+export function Counter({ icon }: { icon: ReactNode }) {
+  const [count, setCount] = useState(0);
+  return <button onClick={() => setCount(count + 1)}><Icon />{count}</button>;
+}
+"#
+        );
+
+        assert_eq!(edit.file_edits.len(), 1);
+        assert_eq!(edit.file_edits[0].path, "/app/page.tsx");
+        assert_eq!(
+            apply(source, edit.file_edits[0].edits.clone()),
+            r#"import { useState, type ReactNode } from "react";
+import { db } from "./db";
+import Icon from "./icon";
+import { Counter } from "./Counter";
+
+export { Counter };
+
+export default async function Page() {
+  const rows = await db.query();
+  return <Counter icon={rows.length} />;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_extract_client_island_partial_imports() {
+        let source = r#"import { useState, cache } from "react";
+const Toggle = () => {
+  const [on, setOn] = useState(false);
+  return <button onClick={() => setOn(!on)} />;
+};
+export default function Page() {
+  return <Toggle />;
+}
+"#;
+
+        let edit = extract_client_island(
+            "/app/page.tsx",
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            "Toggle",
+        )
+        .unwrap();
+
+        assert!(edit.created_files[0].content.starts_with(
+            "\"use client\";\n\nimport { useState } from \"react\";\n\nexport const Toggle"
+        ));
+        assert_eq!(
+            apply(source, edit.file_edits[0].edits.clone()),
+            r#"import { useState, cache } from "react";
+import { Toggle } from "./Toggle";
+export default function Page() {
+  return <Toggle />;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_extract_client_island_ignores_text_mentions() {
+        let source = r#"import { db } from "./db";
+import { Spinner } from "./spinner";
+const label = "Loading";
+function Status({ label }: { label: string }) {
+  const Spinner = () => <span />;
+  return <p title="db" data-label={label}>No db connection <Spinner /></p>;
+}
+export default async function Page() {
+  return <Status label={(await db.query()).length ? label : ""} />;
+}
+"#;
+
+        let edit = extract_client_island(
+            "/app/page.tsx",
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            "Status",
+        )
+        .unwrap();
+
+        assert!(
+            edit.created_files[0]
+                .content
+                .starts_with("\"use client\";\n\nexport function Status")
+        );
+    }
+
+    #[test]
+    fn test_extract_client_island_bare_path() {
+        let source = r#"function Counter() { return <button />; }
+export default function Page() { return <Counter />; }
+"#;
+
+        let edit = extract_client_island(
+            "page.tsx",
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            "Counter",
+        )
+        .unwrap();
+
+        assert_eq!(edit.created_files[0].path, "Counter.tsx");
+        assert_eq!(edit.file_edits[0].path, "page.tsx");
+    }
+
+    #[test]
+    fn test_extract_client_island_errors() {
+        let extract = |source: &str, component: &str| {
+            extract_client_island(
+                "/app/page.tsx",
+                source.as_bytes().to_vec(),
+                "tsx".to_string(),
+                component,
+            )
+            .unwrap_err()
+        };
+
+        let source = r#"const LIMIT = 3;
+interface Props { max: number }
+function List(props: Props) { return <ul>{LIMIT}</ul>; }
+"#;
+        assert_eq!(
+            extract(source, "List"),
+            "List uses LIMIT, Props of the module, which would be left behind"
+        );
+        assert_eq!(
+            extract(source, "Missing"),
+            "No top-level declaration of Missing"
+        );
+        assert!(extract("\"use client\";\nexport function A() {}", "A").contains("already"));
+    }
+}
@@ -0,0 +1,232 @@
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Whether the source starts with a Flow pragma (`// @flow` or `@noflow`) in its leading comments
+pub(crate) fn has_flow_pragma(source: &str) -> bool {
+    let mut rest = source.trim_start_matches('\u{feff}').trim_start();
+    loop {
+        let comment = if let Some(line) = rest.strip_prefix("//") {
+            let end = line.find('\n').unwrap_or(line.len());
+            rest = &line[end..];
+            &line[..end]
+        } else if let Some(block) = rest.strip_prefix("/*") {
+            let Some(end) = block.find("*/") else {
+                return false;
+            };
+            rest = &block[end + 2..];
+            &block[..end]
+        } else {
+            return false;
+        };
+
+        let has_pragma = comment
+            .split(|c: char| c.is_whitespace() || c == '*')
+            .any(|word| word == "@flow" || word == "@noflow");
+        if has_pragma {
+            return true;
+        }
+        rest = rest.trim_start();
+    }
+}
+
+/// Blank out the Flow-only syntax that TypeScript can't parse, so a Flow file parses as TSX:
+/// exact objects (`{| |}`), maybe types (`?T`), variance sigils (`+name:`), existential types
+/// (`*`), `opaque type`, `import typeof` and `%checks`. The pragma itself is blanked too, since
+/// the parser rejects files carrying it.
+///
+/// Replacements never change the length of the source, so spans of the parsed program still
+/// point into the original text. Strings, template literals and comments are left alone, except
+/// for the pragma.
+pub(crate) fn strip_flow_syntax(source: &str) -> String {
+    let mut bytes = source.as_bytes().to_vec();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let end = find_from(&bytes, i, b"\n").unwrap_or(bytes.len());
+                blank_pragma(&mut bytes[i..end]);
+                i = end;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = find_from(&bytes, i + 2, b"*/").map_or(bytes.len(), |end| end + 2);
+                blank_pragma(&mut bytes[i..end]);
+                i = end;
+            }
+            quote @ (b'"' | b'\'' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            b'{' if bytes.get(i + 1) == Some(&b'|') => {
+                bytes[i + 1] = b' ';
+                i += 2;
+            }
+            b'|' if bytes.get(i + 1) == Some(&b'}') => {
+                bytes[i] = b' ';
+                i += 2;
+            }
+            b'?' if matches!(
+                previous_token(&bytes, i),
+                Some(b':' | b'=' | b'<' | b',' | b'|')
+            ) =>
+            {
+                bytes[i] = b' ';
+                i += 1;
+            }
+            b'*' if matches!(previous_token(&bytes, i), Some(b'<' | b','))
+                && matches!(next_token(&bytes, i + 1), Some(b'>' | b',')) =>
+            {
+                // Any identifier parses as a type reference
+                bytes[i] = b'_';
+                i += 1;
+            }
+            b'+' | b'-'
+                if matches!(previous_token(&bytes, i), Some(b'{' | b','))
+                    && is_property_key(&bytes, i + 1) =>
+            {
+                bytes[i] = b' ';
+                i += 1;
+            }
+            b'%' if bytes[i..].starts_with(b"%checks") => {
+                if let Some(colon) = previous_token_index(&bytes, i)
+                    && bytes[colon] == b':'
+                {
+                    bytes[colon] = b' ';
+                }
+                bytes[i..i + "%checks".len()].fill(b' ');
+                i += "%checks".len();
+            }
+            b'o' if is_word_at(&bytes, i, b"opaque") && next_word_is(&bytes, i + 6, b"type") => {
+                bytes[i..i + 6].fill(b' ');
+                i += 6;
+            }
+            b'i' if is_word_at(&bytes, i, b"import") && next_word_is(&bytes, i + 6, b"typeof") => {
+                let typeof_start = i + 6 + skip_whitespace(&bytes[i + 6..]);
+                bytes[typeof_start..typeof_start + 6].copy_from_slice(b"type  ");
+                i = typeof_start + 6;
+            }
+            c if is_identifier_byte(c) => {
+                // Skip whole words so keywords are only matched at word starts
+                while i < bytes.len() && is_identifier_byte(bytes[i]) {
+                    i += 1;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    // Only ASCII bytes outside of multi-byte sequences were replaced
+    String::from_utf8(bytes).unwrap_or_else(|_| source.to_string())
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn find_from(bytes: &[u8], start: usize, needle: &[u8]) -> Option<usize> {
+    bytes[start..]
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|position| start + position)
+}
+
+/// Replace `@flow` / `@noflow` in a comment with spaces
+fn blank_pragma(comment: &mut [u8]) {
+    for pragma in [&b"@noflow"[..], &b"@flow"[..]] {
+        while let Some(start) = find_from(comment, 0, pragma) {
+            comment[start..start + pragma.len()].fill(b' ');
+        }
+    }
+}
+
+fn is_identifier_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'$'
+}
+
+fn skip_whitespace(bytes: &[u8]) -> usize {
+    bytes.iter().take_while(|c| c.is_ascii_whitespace()).count()
+}
+
+fn previous_token_index(bytes: &[u8], end: usize) -> Option<usize> {
+    bytes[..end].iter().rposition(|c| !c.is_ascii_whitespace())
+}
+
+fn previous_token(bytes: &[u8], end: usize) -> Option<u8> {
+    previous_token_index(bytes, end).map(|index| bytes[index])
+}
+
+fn next_token(bytes: &[u8], start: usize) -> Option<u8> {
+    bytes[start..]
+        .iter()
+        .copied()
+        .find(|c| !c.is_ascii_whitespace())
+}
+
+fn is_word_at(bytes: &[u8], start: usize, word: &[u8]) -> bool {
+    bytes[start..].starts_with(word)
+        && !bytes
+            .get(start + word.len())
+            .is_some_and(|c| is_identifier_byte(*c))
+}
+
+/// Whether `word` follows `start` after at least one whitespace character
+fn next_word_is(bytes: &[u8], start: usize, word: &[u8]) -> bool {
+    let whitespace = skip_whitespace(&bytes[start..]);
+    whitespace > 0 && is_word_at(bytes, start + whitespace, word)
+}
+
+/// Whether an identifier followed by `:` or `?:` starts at `start`, as in `{ +name: T }`
+fn is_property_key(bytes: &[u8], start: usize) -> bool {
+    let length = bytes[start..]
+        .iter()
+        .take_while(|c| is_identifier_byte(**c))
+        .count();
+    length > 0 && matches!(next_token(bytes, start + length), Some(b':' | b'?'))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_flow_pragma() {
+        assert!(has_flow_pragma("// @flow\nimport React from 'react';"));
+        assert!(has_flow_pragma(
+            "/**\n * Copyright\n * @flow strict\n */\n'use strict';"
+        ));
+        assert!(has_flow_pragma("// license\n/* @noflow */"));
+        assert!(!has_flow_pragma("import React from 'react'; // @flow"));
+        assert!(!has_flow_pragma("// @flowtype\nconst a = 1;"));
+    }
+
+    #[test]
+    fn test_strip_flow_syntax() {
+        let source = "// @flow\n\
+            import typeof Theme from './theme';\n\
+            export opaque type Id = string;\n\
+            type Props = {| +name: ?string, -id: Id, items: Array<*> |};\n\
+            function isId(x: mixed): %checks { return typeof x === 'string'; }\n\
+            const label = '{| ?: |}';\n";
+
+        let stripped = strip_flow_syntax(source);
+
+        assert_eq!(stripped.len(), source.len());
+        assert_eq!(
+            stripped,
+            "//      \n\
+            import type   Theme from './theme';\n\
+            export        type Id = string;\n\
+            type Props = {   name:  string,  id: Id, items: Array<_>  };\n\
+            function isId(x: mixed)          { return typeof x === 'string'; }\n\
+            const label = '{| ?: |}';\n"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! Golden-fixture tests over compiled npm package output.
+//!
+//! Every file under `fixtures/npm` is a dist file as published by a real package. Each one is
+//! analyzed and the result compared against the `.snap` file next to it, so changes to how
+//! bundled code is detected show up as snapshot diffs.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1 cargo test golden` to accept new output.
+
+use crate::{AnalyzeReactBoundary, Guest};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/npm");
+const SNAPSHOT_EXTENSION: &str = "snap";
+
+// ============================================================================
+// Harness
+// ============================================================================
+
+/// Recursively collect every fixture source file (anything that is not a snapshot)
+fn collect_fixtures(dir: &Path, fixtures: &mut Vec<PathBuf>) {
+    let mut entries = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_fixtures(&path, fixtures);
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext != SNAPSHOT_EXTENSION)
+        {
+            fixtures.push(path);
+        }
+    }
+}
+
+/// Analyze a fixture and render the result in the snapshot format
+fn render_fixture(path: &Path) -> String {
+    let content = fs::read(path).unwrap();
+    let extension = path.extension().unwrap().to_string_lossy().to_string();
+
+    match AnalyzeReactBoundary::analyze(content, extension) {
+        Ok(result) => format!("{:#?}\n", result),
+        Err(error) => format!("Err({})\n", error),
+    }
+}
+
+fn snapshot_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap().to_os_string();
+    file_name.push(".");
+    file_name.push(SNAPSHOT_EXTENSION);
+    path.with_file_name(file_name)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_npm_fixtures_match_snapshots() {
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+
+    let mut fixtures = Vec::new();
+    collect_fixtures(Path::new(FIXTURES_DIR), &mut fixtures);
+    assert!(
+        !fixtures.is_empty(),
+        "No fixtures found in {}",
+        FIXTURES_DIR
+    );
+
+    let mut mismatches = Vec::new();
+    for fixture in fixtures.iter() {
+        let actual = render_fixture(fixture);
+        let snapshot = snapshot_path(fixture);
+
+        if update {
+            fs::write(&snapshot, &actual).unwrap();
+            continue;
+        }
+
+        match fs::read_to_string(&snapshot) {
+            Ok(expected) if expected == actual => {}
+            Ok(_) => mismatches.push(format!("{} (snapshot differs)", fixture.display())),
+            Err(_) => mismatches.push(format!("{} (snapshot missing)", fixture.display())),
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "Golden fixtures out of date, rerun with UPDATE_SNAPSHOTS=1 to accept:\n{}",
+        mismatches.join("\n")
+    );
+}
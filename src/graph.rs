@@ -0,0 +1,631 @@
+use crate::analyze_react_boundary::check::types;
+use oxc::allocator::Allocator;
+use oxc::ast::ast::{ImportDeclarationSpecifier, Statement};
+use oxc::parser::Parser;
+use oxc::span::{SourceType, Span};
+use std::collections::{HashMap, HashSet};
+
+/// Extensions tried, in order, when a relative specifier omits one
+const RESOLVE_EXTENSIONS: [&str; 6] = ["tsx", "ts", "jsx", "js", "mjs", "cjs"];
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Build the module graph of a file set and report every server module rendering a component that
+/// comes from a "use client" module, following barrel re-exports so the full import chain can be
+/// shown.
+///
+/// Only relative imports between files of the set are followed. Files that fail to parse are left
+/// out of the graph.
+pub(crate) fn check_boundaries(
+    files: Vec<types::SourceFile>,
+    is_cancelled: &dyn Fn() -> bool,
+) -> Vec<types::BoundaryViolation> {
+    let graph = ModuleGraph::new(&files);
+    let mut violations = Vec::new();
+
+    for (path, module) in &graph.modules {
+        if is_cancelled() {
+            break;
+        }
+        if module.is_client {
+            continue;
+        }
+
+        for (local, usage_range) in &module.jsx_usages {
+            let Some(import) = module.imports.iter().find(|import| &import.local == local) else {
+                continue;
+            };
+            let Some(target) = graph.resolve(path, &import.source) else {
+                continue;
+            };
+
+            let first_hop = types::ImportHop {
+                path: path.clone(),
+                source: import.source.clone(),
+                range: import.source_range,
+            };
+            let mut visited = HashSet::new();
+            let Some((client_path, mut chain)) =
+                graph.trace(&target, import.imported.as_deref(), &mut visited)
+            else {
+                continue;
+            };
+            chain.insert(0, first_hop);
+
+            let via = chain
+                .iter()
+                .map(|hop| hop.source.as_str())
+                .collect::<Vec<_>>()
+                .join(" → ");
+            violations.push(types::BoundaryViolation {
+                path: path.clone(),
+                component: local.clone(),
+                range: *usage_range,
+                client_path,
+                message: format!(
+                    "Server module imports client component {} via {}",
+                    local, via
+                ),
+                locations: crate::location::violation_locations(path, *usage_range, &chain),
+                chain,
+            });
+        }
+    }
+
+    violations.sort_by(|a, b| {
+        (&a.path, a.range.start.line, a.range.start.character).cmp(&(
+            &b.path,
+            b.range.start.line,
+            b.range.start.character,
+        ))
+    });
+    violations
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// An import binding with the name it refers to in the imported module
+pub(crate) struct ImportBinding {
+    pub(crate) local: String,
+    /// `default`, the imported name, or `None` for a namespace import
+    pub(crate) imported: Option<String>,
+    pub(crate) source: String,
+    source_range: types::Range,
+}
+
+/// `export { a as b } from "..."` or `export * from "..."`
+struct ReExport {
+    /// `(exported, imported)` pairs, `None` for `export *`
+    names: Option<Vec<(String, String)>>,
+    source: String,
+    source_range: types::Range,
+}
+
+pub(crate) struct Module {
+    pub(crate) is_client: bool,
+    pub(crate) imports: Vec<ImportBinding>,
+    re_exports: Vec<ReExport>,
+    pub(crate) jsx_usages: Vec<(String, types::Range)>,
+    /// Specifiers of the worker scripts the module starts
+    workers: Vec<String>,
+    /// Specifiers of the modules loaded under a condition (`if (isBrowser) require("...")`)
+    conditional_imports: Vec<String>,
+}
+
+/// Modules of a file set by path, with their relative imports resolved against the set
+pub(crate) struct ModuleGraph {
+    pub(crate) modules: HashMap<String, Module>,
+}
+
+impl ModuleGraph {
+    pub(crate) fn new(files: &[types::SourceFile]) -> ModuleGraph {
+        let modules = files
+            .iter()
+            .filter_map(|file| {
+                let module = scan_module(&file.content, &file.extension)?;
+                Some((file.path.clone(), module))
+            })
+            .collect();
+        ModuleGraph { modules }
+    }
+
+    /// Paths of the modules `path` imports or re-exports from
+    pub(crate) fn dependencies(&self, path: &str) -> Vec<String> {
+        let Some(module) = self.modules.get(path) else {
+            return Vec::new();
+        };
+        let mut dependencies = module
+            .imports
+            .iter()
+            .map(|import| import.source.as_str())
+            .chain(
+                module
+                    .re_exports
+                    .iter()
+                    .map(|re_export| re_export.source.as_str()),
+            )
+            .filter_map(|source| self.resolve(path, source))
+            .collect::<Vec<_>>();
+        dependencies.sort();
+        dependencies.dedup();
+        dependencies
+    }
+
+    /// Paths of the modules `path` starts as workers. Workers run in their own context, so these
+    /// edges are kept apart from imports and never cross the client boundary
+    pub(crate) fn worker_dependencies(&self, path: &str) -> Vec<String> {
+        let Some(module) = self.modules.get(path) else {
+            return Vec::new();
+        };
+        let mut dependencies = module
+            .workers
+            .iter()
+            .filter_map(|source| self.resolve(path, source))
+            .collect::<Vec<_>>();
+        dependencies.sort();
+        dependencies.dedup();
+        dependencies
+    }
+
+    /// Paths of the modules `path` loads under a condition with `require(...)` or `import(...)`.
+    /// These edges may never be taken, so they are kept apart from static imports
+    pub(crate) fn conditional_dependencies(&self, path: &str) -> Vec<String> {
+        let Some(module) = self.modules.get(path) else {
+            return Vec::new();
+        };
+        let mut dependencies = module
+            .conditional_imports
+            .iter()
+            .filter_map(|source| self.resolve(path, source))
+            .collect::<Vec<_>>();
+        dependencies.sort();
+        dependencies.dedup();
+        dependencies
+    }
+
+    /// Follow `name` from `path` through re-exports to the modules that may declare it, adding
+    /// each module's path and the name it would be declared as there to `origins`. Declarations
+    /// aren't tracked, so every module `export *` forwards to is a candidate
+    pub(crate) fn origins(&self, path: &str, name: &str, origins: &mut HashSet<(String, String)>) {
+        if !origins.insert((path.to_string(), name.to_string())) {
+            return;
+        }
+        let Some(module) = self.modules.get(path) else {
+            return;
+        };
+
+        for re_export in &module.re_exports {
+            let imported = match &re_export.names {
+                Some(names) => match names.iter().find(|(exported, _)| exported == name) {
+                    Some((_, imported)) => imported.as_str(),
+                    None => continue,
+                },
+                None if name == "default" => continue,
+                None => name,
+            };
+            if let Some(target) = self.resolve(path, &re_export.source) {
+                self.origins(&target, imported, origins);
+            }
+        }
+    }
+
+    /// Resolve a relative specifier to a path in the file set. Assets are never modules of the
+    /// graph, so they are not resolved
+    pub(crate) fn resolve(&self, from: &str, specifier: &str) -> Option<String> {
+        if !specifier.starts_with("./") && !specifier.starts_with("../") {
+            return None;
+        }
+        if crate::asset::asset_kind(specifier).is_some() {
+            return None;
+        }
+
+        let base = match from.rsplit_once('/') {
+            Some((dir, _)) if !dir.is_empty() => {
+                crate::resolve::normalize(&format!("{}/{}", dir, specifier))
+            }
+            // `/page.tsx` keeps its root, bare `page.tsx` resolves next to itself
+            Some(_) => crate::resolve::normalize(&format!("/{}", specifier)),
+            None => crate::resolve::normalize(specifier),
+        };
+
+        std::iter::once(base.clone())
+            .chain(
+                RESOLVE_EXTENSIONS
+                    .iter()
+                    .map(|ext| format!("{}.{}", base, ext)),
+            )
+            .chain(
+                RESOLVE_EXTENSIONS
+                    .iter()
+                    .map(|ext| format!("{}/index.{}", base, ext)),
+            )
+            .find(|candidate| self.modules.contains_key(candidate))
+    }
+
+    /// Follow `name` from `path` through re-exports until a client module is reached, returning
+    /// that module's path and the hops taken after `path`
+    fn trace(
+        &self,
+        path: &str,
+        name: Option<&str>,
+        visited: &mut HashSet<(String, Option<String>)>,
+    ) -> Option<(String, Vec<types::ImportHop>)> {
+        if !visited.insert((path.to_string(), name.map(str::to_string))) {
+            return None;
+        }
+
+        let module = self.modules.get(path)?;
+        if module.is_client {
+            return Some((path.to_string(), Vec::new()));
+        }
+
+        // Namespace imports only reach what the module itself declares
+        let name = name?;
+
+        module.re_exports.iter().find_map(|re_export| {
+            let imported = match &re_export.names {
+                Some(names) => names
+                    .iter()
+                    .find(|(exported, _)| exported == name)
+                    .map(|(_, imported)| imported.as_str())?,
+                // `export *` never forwards the default export
+                None if name == "default" => return None,
+                None => name,
+            };
+            let target = self.resolve(path, &re_export.source)?;
+            let (client_path, mut chain) = self.trace(&target, Some(imported), visited)?;
+            chain.insert(
+                0,
+                types::ImportHop {
+                    path: path.to_string(),
+                    source: re_export.source.clone(),
+                    range: re_export.source_range,
+                },
+            );
+            Some((client_path, chain))
+        })
+    }
+}
+
+/// Collect the facts the graph needs from a module, or `None` if it can't be parsed
+fn scan_module(content: &[u8], extension: &str) -> Option<Module> {
+    let source_text = std::str::from_utf8(content).ok()?;
+    let source_type = SourceType::from_extension(extension).ok()?;
+
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    if ret.panicked {
+        return None;
+    }
+    let program = ret.program;
+    let to_range = |span: Span| crate::range::string_literal_to_range(source_text, span);
+
+    let mut imports = Vec::new();
+    let mut re_exports = Vec::new();
+
+    for statement in &program.body {
+        match statement {
+            Statement::ImportDeclaration(import_decl) if !import_decl.import_kind.is_type() => {
+                for specifier in import_decl.specifiers.iter().flatten() {
+                    let (local, imported) = match specifier {
+                        ImportDeclarationSpecifier::ImportSpecifier(spec) => {
+                            if spec.import_kind.is_type() {
+                                continue;
+                            }
+                            (
+                                spec.local.name.to_string(),
+                                Some(crate::module_export_name(&spec.imported)),
+                            )
+                        }
+                        ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                            (spec.local.name.to_string(), Some("default".to_string()))
+                        }
+                        ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
+                            (spec.local.name.to_string(), None)
+                        }
+                    };
+                    imports.push(ImportBinding {
+                        local,
+                        imported,
+                        source: import_decl.source.value.to_string(),
+                        source_range: to_range(import_decl.source.span),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Type-only re-exports create no runtime edge. `export * as ns from` only exposes a
+    // namespace, which can't be rendered directly
+    for re_export in crate::re_export::collect_re_exports(&program.body) {
+        if re_export.type_only {
+            continue;
+        }
+        let names = match re_export.exported {
+            Some(exported) if re_export.imported != "*" => {
+                Some(vec![(exported, re_export.imported)])
+            }
+            Some(_) => continue,
+            None => None,
+        };
+        re_exports.push(ReExport {
+            names,
+            source: re_export.source,
+            source_range: to_range(re_export.source_span),
+        });
+    }
+
+    let jsx_usages = crate::jsx::collect_jsx_usages(&program.body)
+        .into_iter()
+        .map(|(name, span)| (name, crate::range::span_to_range(source_text, span)))
+        .collect();
+
+    Some(Module {
+        is_client: program
+            .directives
+            .iter()
+            .any(|directive| directive.directive == "use client"),
+        imports,
+        re_exports,
+        jsx_usages,
+        workers: crate::worker::collect_workers(&program.body)
+            .into_iter()
+            .map(|worker| worker.source)
+            .collect(),
+        conditional_imports: crate::conditional::collect_conditional_imports(&program.body)
+            .into_iter()
+            .map(|import| import.source)
+            .collect(),
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::source_file;
+
+    #[test]
+    fn test_check_boundaries_direct_import() {
+        let files = vec![
+            source_file(
+                "/app/page.tsx",
+                r#"
+import { Button } from "./button";
+export default function Page() {
+  return <Button />;
+}
+                "#,
+            ),
+            source_file(
+                "/app/button.tsx",
+                r#""use client"; export const Button = () => <button />;"#,
+            ),
+        ];
+
+        let violations = check_boundaries(files, &|| false);
+
+        assert_eq!(violations.len(), 1);
+        let violation = &violations[0];
+        assert_eq!(violation.path, "/app/page.tsx");
+        assert_eq!(violation.component, "Button");
+        assert_eq!(violation.client_path, "/app/button.tsx");
+        assert_eq!(violation.chain.len(), 1);
+        assert_eq!(violation.chain[0].source, "./button");
+        assert_eq!(violation.range.start.line, 3);
+    }
+
+    #[test]
+    fn test_check_boundaries_bare_paths() {
+        let files = vec![
+            source_file(
+                "page.tsx",
+                r#"import { Button } from "./button"; export default () => <Button />;"#,
+            ),
+            source_file(
+                "button.tsx",
+                r#""use client"; export const Button = () => <button />;"#,
+            ),
+        ];
+
+        let violations = check_boundaries(files, &|| false);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].client_path, "button.tsx");
+    }
+
+    #[test]
+    fn test_check_boundaries_skips_dynamic_boundaries() {
+        let files = vec![
+            source_file(
+                "/app/page.tsx",
+                r#"
+import dynamic from "next/dynamic";
+const Button = dynamic(() => import("./button"), { ssr: false });
+export default function Page() {
+  return <Button />;
+}
+                "#,
+            ),
+            source_file(
+                "/app/button.tsx",
+                r#""use client"; export default () => <button />;"#,
+            ),
+        ];
+
+        assert!(check_boundaries(files, &|| false).is_empty());
+    }
+
+    #[test]
+    fn test_check_boundaries_through_barrels() {
+        let files = vec![
+            source_file(
+                "/app/page.tsx",
+                r#"
+import { PrimaryButton } from "./components";
+export default function Page() {
+  return <PrimaryButton />;
+}
+                "#,
+            ),
+            source_file("/app/components/index.ts", r#"export * from "./forms";"#),
+            source_file(
+                "/app/components/forms.ts",
+                r#"export { Button as PrimaryButton } from "../button";"#,
+            ),
+            source_file(
+                "/app/button.tsx",
+                r#""use client"; export const Button = () => <button />;"#,
+            ),
+        ];
+
+        let violations = check_boundaries(files, &|| false);
+
+        assert_eq!(violations.len(), 1);
+        let violation = &violations[0];
+        assert_eq!(violation.client_path, "/app/button.tsx");
+        let hops = violation
+            .chain
+            .iter()
+            .map(|hop| (hop.path.as_str(), hop.source.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            hops,
+            vec![
+                ("/app/page.tsx", "./components"),
+                ("/app/components/index.ts", "./forms"),
+                ("/app/components/forms.ts", "../button"),
+            ]
+        );
+        assert_eq!(
+            violation.message,
+            "Server module imports client component PrimaryButton via ./components → ./forms → ../button"
+        );
+        // Hop range covers the specifier without quotes
+        assert_eq!(violation.chain[2].range.start.character, 41);
+
+        // The usage, then every hop, each in its own file
+        let locations = violation
+            .locations
+            .iter()
+            .map(|location| (location.path.as_str(), location.range.start.line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            locations,
+            vec![
+                ("/app/page.tsx", 3),
+                ("/app/page.tsx", 1),
+                ("/app/components/index.ts", 0),
+                ("/app/components/forms.ts", 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_boundaries_ignores_client_importers_and_server_targets() {
+        let files = vec![
+            source_file(
+                "/app/client.tsx",
+                r#""use client";
+import { Button } from "./button";
+export const Toolbar = () => <Button />;
+                "#,
+            ),
+            source_file(
+                "/app/page.tsx",
+                r#"
+import { Card } from "./card";
+export default function Page() {
+  return <Card />;
+}
+                "#,
+            ),
+            source_file("/app/card.tsx", r#"export const Card = () => <div />;"#),
+            source_file(
+                "/app/button.tsx",
+                r#""use client"; export const Button = () => <button />;"#,
+            ),
+        ];
+
+        assert!(check_boundaries(files, &|| false).is_empty());
+    }
+
+    #[test]
+    fn test_check_boundaries_star_export_skips_default() {
+        let files = vec![
+            source_file(
+                "/app/page.tsx",
+                r#"
+import Button from "./barrel";
+export default function Page() {
+  return <Button />;
+}
+                "#,
+            ),
+            source_file("/app/barrel.ts", r#"export * from "./button";"#),
+            source_file(
+                "/app/button.tsx",
+                r#""use client"; export default function Button() { return <button />; }"#,
+            ),
+        ];
+
+        assert!(check_boundaries(files, &|| false).is_empty());
+    }
+
+    #[test]
+    fn test_check_boundaries_re_export_cycle() {
+        let files = vec![
+            source_file(
+                "/app/page.tsx",
+                r#"
+import { Button } from "./a";
+export default function Page() {
+  return <Button />;
+}
+                "#,
+            ),
+            source_file("/app/a.ts", r#"export * from "./b";"#),
+            source_file("/app/b.ts", r#"export * from "./a";"#),
+        ];
+
+        assert!(check_boundaries(files, &|| false).is_empty());
+    }
+
+    #[test]
+    fn test_check_boundaries_skips_type_only_re_exports() {
+        let files = vec![
+            source_file(
+                "/app/page.tsx",
+                r#"
+import { Button } from "./components";
+export default function Page() {
+  return <Button />;
+}
+                "#,
+            ),
+            source_file(
+                "/app/components.ts",
+                r#"
+export type { Button } from "./button";
+export { type Button as Other } from "./button";
+                "#,
+            ),
+            source_file(
+                "/app/button.tsx",
+                r#""use client"; export const Button = () => <button />;"#,
+            ),
+        ];
+
+        let violations = check_boundaries(files, &|| false);
+
+        assert!(violations.is_empty());
+    }
+}
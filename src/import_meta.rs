@@ -0,0 +1,139 @@
+use oxc::ast::ast::{Expression, MemberExpression, Statement};
+use oxc::ast_visit::{Visit, walk};
+use oxc::span::{GetSpan, Span};
+use std::collections::HashSet;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// An `import.meta.<property>` access
+pub(crate) struct ImportMetaAccess {
+    /// `url`, `env`, ...
+    pub(crate) property: String,
+    /// Variable read from `import.meta.env` (`import.meta.env.VITE_API_URL`), when the access goes
+    /// on to read one by a static name
+    pub(crate) env_key: Option<String>,
+    /// Span of the access, the variable read included
+    pub(crate) span: Span,
+}
+
+/// Collect the `import.meta` property accesses, so hosts can see module URLs being resolved
+/// (`import.meta.url`) and Vite-style environment variables being read (`import.meta.env`).
+/// Environment variables are inlined into whichever bundle reads them, which matters on both
+/// sides of the boundary
+pub(crate) fn collect_import_meta_accesses(statements: &[Statement]) -> Vec<ImportMetaAccess> {
+    let mut collector = ImportMetaCollector::default();
+    for statement in statements {
+        collector.visit_statement(statement);
+    }
+    collector.accesses
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+#[derive(Default)]
+struct ImportMetaCollector {
+    accesses: Vec<ImportMetaAccess>,
+    /// `import.meta.env` accesses already reported with the variable read from them
+    reported: HashSet<Span>,
+}
+
+impl<'a> Visit<'a> for ImportMetaCollector {
+    // Member expressions are reached here both when read and when assigned to
+    fn visit_member_expression(&mut self, member: &MemberExpression<'a>) {
+        let key = match member {
+            MemberExpression::StaticMemberExpression(member) => {
+                Some(member.property.name.to_string())
+            }
+            MemberExpression::ComputedMemberExpression(member) => match &member.expression {
+                Expression::StringLiteral(literal) => Some(literal.value.to_string()),
+                _ => None,
+            },
+            MemberExpression::PrivateFieldExpression(_) => None,
+        };
+        let span = member.span();
+
+        if let Expression::StaticMemberExpression(env) = member.object()
+            && env.property.name == "env"
+            && is_import_meta(&env.object)
+        {
+            self.reported.insert(env.span);
+            self.accesses.push(ImportMetaAccess {
+                property: "env".to_string(),
+                env_key: key,
+                span,
+            });
+        } else if let MemberExpression::StaticMemberExpression(member) = member
+            && is_import_meta(&member.object)
+            && !self.reported.contains(&member.span)
+        {
+            self.accesses.push(ImportMetaAccess {
+                property: member.property.name.to_string(),
+                env_key: None,
+                span,
+            });
+        }
+        walk::walk_member_expression(self, member);
+    }
+}
+
+fn is_import_meta(expr: &Expression) -> bool {
+    matches!(expr, Expression::MetaProperty(meta)
+        if meta.meta.name == "import" && meta.property.name == "meta")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_collect_import_meta_accesses() {
+        let source = r#"
+const here = new URL("./data.json", import.meta.url);
+const api = import.meta.env.VITE_API_URL;
+const mode = import.meta.env["MODE"];
+const env = import.meta.env;
+export function Debug() {
+  return <pre>{import.meta.env.DEV && import.meta.filename}</pre>;
+}
+window.flags[import.meta.env.VITE_FLAG] = true;
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let accesses = collect_import_meta_accesses(&ret.program.body);
+
+        let summary = accesses
+            .iter()
+            .map(|access| {
+                (
+                    access.property.as_str(),
+                    access.env_key.as_deref(),
+                    &source[access.span.start as usize..access.span.end as usize],
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("url", None, "import.meta.url"),
+                ("env", Some("VITE_API_URL"), "import.meta.env.VITE_API_URL"),
+                ("env", Some("MODE"), "import.meta.env[\"MODE\"]"),
+                ("env", None, "import.meta.env"),
+                ("env", Some("DEV"), "import.meta.env.DEV"),
+                ("filename", None, "import.meta.filename"),
+                ("env", Some("VITE_FLAG"), "import.meta.env.VITE_FLAG"),
+            ]
+        );
+    }
+}
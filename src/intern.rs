@@ -0,0 +1,387 @@
+use crate::analyze_react_boundary::check::types;
+use std::collections::HashMap;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Convert batch results to the interned encoding, where every string is replaced by an index
+/// into a single string table shared by all files
+pub(crate) fn intern_batch(files: Vec<types::FileAnalysis>) -> types::InternedBatch {
+    let mut table = StringTable::default();
+
+    let files = files
+        .into_iter()
+        .map(|file| types::InternedFileAnalysis {
+            path: table.intern(file.path),
+            analysis: file
+                .analysis
+                .map(|analysis| intern_analysis(analysis, &mut table)),
+        })
+        .collect();
+
+    types::InternedBatch {
+        strings: table.strings,
+        files,
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+#[derive(Default)]
+struct StringTable {
+    strings: Vec<String>,
+    indices: HashMap<String, u32>,
+}
+
+impl StringTable {
+    fn intern(&mut self, string: String) -> u32 {
+        if let Some(&index) = self.indices.get(&string) {
+            return index;
+        }
+        let index = self.strings.len() as u32;
+        self.strings.push(string.clone());
+        self.indices.insert(string, index);
+        index
+    }
+}
+
+fn intern_analysis(
+    analysis: types::AnalysisResult,
+    table: &mut StringTable,
+) -> types::InternedAnalysisResult {
+    types::InternedAnalysisResult {
+        imports: analysis
+            .imports
+            .into_iter()
+            .map(|import| types::InternedImportAnalysis {
+                identifier: import
+                    .identifier
+                    .into_iter()
+                    .map(|identifier| table.intern(identifier))
+                    .collect(),
+                source: table.intern(import.source),
+                source_span: import.source_span,
+                range: import.range,
+                specifier_ranges: import.specifier_ranges,
+                asset: import.asset,
+            })
+            .collect(),
+        components: analysis
+            .components
+            .into_iter()
+            .map(|component| types::InternedComponentAnalysis {
+                id: table.intern(component.id),
+                name: table.intern(component.name),
+                is_client_component: component.is_client_component,
+                range: component.range,
+                export_kind: component.export_kind,
+                exported_name: component.exported_name.map(|name| table.intern(name)),
+                export_range: component.export_range,
+                conditional: component.conditional,
+                grouped: component.grouped,
+                annotation: component.annotation,
+                props_type: component
+                    .props_type
+                    .map(|props_type| types::InternedPropsType {
+                        name: table.intern(props_type.name),
+                        range: props_type.range,
+                    }),
+            })
+            .collect(),
+        jsx_usages: analysis
+            .jsx_usages
+            .into_iter()
+            .map(|usage| types::InternedJsxUsage {
+                component_name: table.intern(usage.component_name),
+                range: usage.range,
+                props: usage.props,
+                attributes: usage
+                    .attributes
+                    .into_iter()
+                    .map(|attribute| types::InternedJsxAttribute {
+                        name: table.intern(attribute.name),
+                        range: attribute.range,
+                    })
+                    .collect(),
+                spreads: usage
+                    .spreads
+                    .into_iter()
+                    .map(|spread| types::InternedJsxSpread {
+                        name: spread.name.map(|name| table.intern(name)),
+                        range: spread.range,
+                    })
+                    .collect(),
+                has_key: usage.has_key,
+                has_ref: usage.has_ref,
+                source: usage.source,
+                member: usage.member.map(|member| table.intern(member)),
+                import_index: usage.import_index,
+                is_fragment: usage.is_fragment,
+                in_config: usage.in_config,
+            })
+            .collect(),
+        use_client_directive: analysis.use_client_directive,
+        route_handlers: analysis
+            .route_handlers
+            .into_iter()
+            .map(|handler| types::InternedRouteHandler {
+                method: table.intern(handler.method),
+                range: handler.range,
+            })
+            .collect(),
+        module_kind: analysis.module_kind,
+        pages_data_functions: analysis
+            .pages_data_functions
+            .into_iter()
+            .map(|function| types::InternedPagesDataFunction {
+                name: table.intern(function.name),
+                range: function.range,
+            })
+            .collect(),
+        server_actions: analysis
+            .server_actions
+            .into_iter()
+            .map(|action| types::InternedServerAction {
+                name: table.intern(action.name),
+                range: action.range,
+                parameter_count: action.parameter_count,
+                uses_form_data: action.uses_form_data,
+                bound_in_jsx: action.bound_in_jsx,
+                component: action.component.map(|component| table.intern(component)),
+                binds: action.binds,
+                captures: action
+                    .captures
+                    .into_iter()
+                    .map(|capture| types::InternedCapturedVariable {
+                        name: table.intern(capture.name),
+                        range: capture.range,
+                    })
+                    .collect(),
+            })
+            .collect(),
+        signals: analysis
+            .signals
+            .into_iter()
+            .map(|signal| types::InternedSignal {
+                kind: signal.kind,
+                name: table.intern(signal.name),
+                range: signal.range,
+                hook_category: signal.hook_category,
+            })
+            .collect(),
+        diagnostics: analysis
+            .diagnostics
+            .into_iter()
+            .map(|diagnostic| types::InternedDiagnostic {
+                rule: table.intern(diagnostic.rule),
+                severity: diagnostic.severity,
+                message: table.intern(diagnostic.message),
+                range: diagnostic.range,
+                related_range: diagnostic.related_range,
+            })
+            .collect(),
+        client_weight: analysis.client_weight,
+        client_exports: analysis
+            .client_exports
+            .into_iter()
+            .map(|export| types::InternedClientExport {
+                name: table.intern(export.name),
+                kind: export.kind,
+                range: export.range,
+            })
+            .collect(),
+        partial: analysis.partial,
+        timed_out: analysis.timed_out,
+        parse_errors: analysis
+            .parse_errors
+            .into_iter()
+            .map(|error| types::InternedParseError {
+                message: table.intern(error.message),
+                range: error.range,
+            })
+            .collect(),
+        re_exports: analysis
+            .re_exports
+            .into_iter()
+            .map(|re_export| types::InternedReExport {
+                source: table.intern(re_export.source),
+                source_span: re_export.source_span,
+                imported: table.intern(re_export.imported),
+                exported: re_export.exported.map(|exported| table.intern(exported)),
+                type_only: re_export.type_only,
+                grants_directive: re_export.grants_directive,
+                range: re_export.range,
+            })
+            .collect(),
+        context_consumers: analysis
+            .context_consumers
+            .into_iter()
+            .map(|consumer| types::InternedContextConsumer {
+                local: table.intern(consumer.local),
+                imported: table.intern(consumer.imported),
+                source: table.intern(consumer.source),
+                import_index: consumer.import_index,
+                range: consumer.range,
+            })
+            .collect(),
+        value_exports: analysis
+            .value_exports
+            .into_iter()
+            .map(|export| types::InternedValueExport {
+                name: table.intern(export.name),
+                kind: export.kind,
+                range: export.range,
+            })
+            .collect(),
+        is_client_module: analysis.is_client_module,
+        exported_hooks: analysis
+            .exported_hooks
+            .into_iter()
+            .map(|hook| types::InternedExportedHook {
+                name: table.intern(hook.name),
+                range: hook.range,
+            })
+            .collect(),
+        type_exports: analysis
+            .type_exports
+            .into_iter()
+            .map(|export| types::InternedTypeExport {
+                name: table.intern(export.name),
+                kind: export.kind,
+                members: export
+                    .members
+                    .into_iter()
+                    .map(|member| types::InternedTypeMember {
+                        name: table.intern(member.name),
+                        optional: member.optional,
+                        non_serializable: member.non_serializable,
+                        range: member.range,
+                    })
+                    .collect(),
+                range: export.range,
+            })
+            .collect(),
+        dynamic_boundaries: analysis
+            .dynamic_boundaries
+            .into_iter()
+            .map(|boundary| types::InternedDynamicBoundary {
+                name: table.intern(boundary.name),
+                source: table.intern(boundary.source),
+                source_span: boundary.source_span,
+                ssr: boundary.ssr,
+                range: boundary.range,
+            })
+            .collect(),
+        workers: analysis
+            .workers
+            .into_iter()
+            .map(|worker| types::InternedWorkerReference {
+                kind: worker.kind,
+                source: table.intern(worker.source),
+                source_span: worker.source_span,
+                range: worker.range,
+            })
+            .collect(),
+        conditional_imports: analysis
+            .conditional_imports
+            .into_iter()
+            .map(|import| types::InternedConditionalImport {
+                kind: import.kind,
+                source: table.intern(import.source),
+                source_span: import.source_span,
+                guard: import.guard,
+                range: import.range,
+            })
+            .collect(),
+        debug_usages: analysis
+            .debug_usages
+            .into_iter()
+            .map(|usage| types::InternedDebugUsage {
+                kind: usage.kind,
+                method: usage.method.map(|method| table.intern(method)),
+                component: usage.component.map(|component| table.intern(component)),
+                directive_side: usage.directive_side,
+                range: usage.range,
+            })
+            .collect(),
+        import_meta_accesses: analysis
+            .import_meta_accesses
+            .into_iter()
+            .map(|access| types::InternedImportMetaAccess {
+                property: table.intern(access.property),
+                env_key: access.env_key.map(|key| table.intern(key)),
+                range: access.range,
+            })
+            .collect(),
+        jsx_runtimes: analysis.jsx_runtimes,
+        subcomponents: analysis
+            .subcomponents
+            .into_iter()
+            .map(|subcomponent| types::InternedSubcomponent {
+                parent: table.intern(subcomponent.parent),
+                member: table.intern(subcomponent.member),
+                local: subcomponent.local.map(|local| table.intern(local)),
+                range: subcomponent.range,
+            })
+            .collect(),
+        statistics: analysis.statistics,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::source_file;
+
+    #[test]
+    fn test_intern_batch_shares_strings() {
+        let files = vec![
+            source_file(
+                "a.tsx",
+                r#"import { Button } from "./components"; export const A = () => <Button />;"#,
+            ),
+            source_file(
+                "b.tsx",
+                r#"import { Button } from "./components"; export const B = () => <Button />;"#,
+            ),
+        ];
+
+        let batch = intern_batch(crate::batch::analyze_batch(files, &|| false, &|_, _, _| {}));
+
+        let a = batch.files[0].analysis.as_ref().unwrap();
+        let b = batch.files[1].analysis.as_ref().unwrap();
+
+        // Same strings across files resolve to the same index
+        assert_eq!(a.imports[0].source, b.imports[0].source);
+        assert_eq!(
+            a.jsx_usages[0].component_name,
+            b.jsx_usages[0].component_name
+        );
+        assert_eq!(batch.strings[a.imports[0].source as usize], "./components");
+        assert_eq!(
+            batch.strings[a.jsx_usages[0].component_name as usize],
+            "Button"
+        );
+
+        // Each string is stored only once
+        let button_count = batch.strings.iter().filter(|s| *s == "Button").count();
+        assert_eq!(button_count, 1);
+    }
+
+    #[test]
+    fn test_intern_batch_keeps_errors() {
+        let files = vec![source_file("broken.tsx", "const x = {{{")];
+
+        let batch = intern_batch(crate::batch::analyze_batch(files, &|| false, &|_, _, _| {}));
+
+        assert_eq!(batch.strings[batch.files[0].path as usize], "broken.tsx");
+        assert!(batch.files[0].analysis.is_err());
+    }
+}
@@ -1,17 +1,192 @@
-use oxc::ast::ast::{Expression, JSXElementName, JSXMemberExpressionObject, Statement};
+use crate::analyze_react_boundary::check::types;
+use crate::deadline::Deadline;
+use oxc::ast::ast::{Expression, JSXChild, JSXElementName, JSXMemberExpressionObject, Statement};
+use oxc::ast_visit::{Visit, walk};
 use oxc::span::Span;
+use std::collections::{HashMap, HashSet};
+
+/// Props that change how an element renders or hydrates, by attribute name
+const SENSITIVE_PROPS: [(&str, types::SensitiveProp); 3] = [
+    (
+        "dangerouslySetInnerHTML",
+        types::SensitiveProp::DangerouslySetInnerHtml,
+    ),
+    (
+        "suppressHydrationWarning",
+        types::SensitiveProp::SuppressHydrationWarning,
+    ),
+    ("ref", types::SensitiveProp::Ref),
+];
+
+/// Elements collected between two checks of the analysis deadline
+const DEADLINE_CHECK_INTERVAL: usize = 64;
 
 // ============================================================================
 // PUBLIC API
 // ============================================================================
 
+/// Everything collected from the JSX in a program body
+#[derive(Default)]
+pub(crate) struct JsxCollection {
+    /// PascalCase element names (base identifier for member expressions) with element spans
+    pub(crate) usages: Vec<(String, Span)>,
+    /// Identifiers passed to `action` / `formAction` props, with the attribute spans
+    pub(crate) action_bindings: Vec<(String, Span)>,
+    /// The subset of `action_bindings` passed as `action.bind(null, ...)`
+    pub(crate) bound_actions: Vec<(String, Span)>,
+    /// Sensitive props on any element, host elements included, as (prop, attribute span), by
+    /// element span. The element span matches the span recorded in `usages`
+    pub(crate) sensitive_props: HashMap<Span, Vec<(types::SensitiveProp, Span)>>,
+    /// Member paths of member expression usages (`Check` for `<Icons.Check>`), by element span
+    members: HashMap<Span, String>,
+    /// Spans of the usages found in top-level configuration literals
+    config_spans: HashSet<Span>,
+    /// Named attributes of every element as (attribute name, attribute span), by element span
+    attributes: HashMap<Span, Vec<(String, Span)>>,
+    /// Spread attributes of every element as (spread identifier, spread span), by element span.
+    /// The identifier is the base of member expressions (`props` for `{...props.input}`), and
+    /// `None` for any other spread argument
+    spreads: HashMap<Span, Vec<(Option<String>, Span)>>,
+    /// Attributes passed an inline function that is not a server action (`onSelect={() => ...}`),
+    /// as (element span, attribute name, attribute span)
+    pub(crate) function_props: Vec<(Span, String, Span)>,
+    /// Spans of the elements already collected, so an element reached both through its parent and
+    /// through a walk of a configuration literal is only recorded once
+    elements: HashSet<Span>,
+    /// Whether the walk stopped early because the analysis ran out of time, leaving the
+    /// collection incomplete
+    pub(crate) timed_out: bool,
+    deadline: Deadline,
+}
+
+impl JsxCollection {
+    /// Sensitive props passed to the element at `element_span`
+    pub(crate) fn props_for(&self, element_span: Span) -> Vec<types::SensitiveProp> {
+        self.sensitive_props
+            .get(&element_span)
+            .map_or_else(Vec::new, |props| {
+                props.iter().map(|(prop, _)| *prop).collect()
+            })
+    }
+
+    /// Whether the usage at `span` is inside a top-level configuration literal
+    pub(crate) fn in_config(&self, span: Span) -> bool {
+        self.config_spans.contains(&span)
+    }
+
+    /// Named attributes of the element at `element_span`, as (name, attribute span)
+    pub(crate) fn attributes_for(&self, element_span: Span) -> Vec<(String, Span)> {
+        self.attributes
+            .get(&element_span)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Whether the element at `element_span` passes the named attribute, as `key` or `ref`
+    pub(crate) fn passes(&self, element_span: Span, attribute: &str) -> bool {
+        self.attributes
+            .get(&element_span)
+            .is_some_and(|attributes| attributes.iter().any(|(name, _)| name == attribute))
+    }
+
+    /// Spread attributes of the element at `element_span`, as (identifier, spread span)
+    pub(crate) fn spreads_for(&self, element_span: Span) -> Vec<(Option<String>, Span)> {
+        self.spreads.get(&element_span).cloned().unwrap_or_default()
+    }
+
+    /// Member path of the member expression usage at `element_span`
+    pub(crate) fn member_for(&self, element_span: Span) -> Option<String> {
+        self.members.get(&element_span).cloned()
+    }
+}
+
+/// Local bindings through which `Fragment` from "react" can be rendered
+#[derive(Default)]
+pub(crate) struct FragmentBindings {
+    /// `Fragment` imported by name, possibly renamed
+    names: Vec<String>,
+    /// Default and namespace imports of "react", used as `<React.Fragment>`
+    namespaces: Vec<String>,
+}
+
+impl FragmentBindings {
+    pub(crate) fn collect(statements: &[Statement]) -> Self {
+        use oxc::ast::ast::ImportDeclarationSpecifier;
+
+        let mut bindings = Self {
+            names: Vec::new(),
+            // The classic runtime allows a global React without importing it
+            namespaces: vec!["React".to_string()],
+        };
+        for statement in statements {
+            if let Statement::ImportDeclaration(import_decl) = statement
+                && import_decl.source.value == "react"
+            {
+                for specifier in import_decl.specifiers.iter().flatten() {
+                    match specifier {
+                        ImportDeclarationSpecifier::ImportSpecifier(spec) => {
+                            if spec.imported.name() == "Fragment" {
+                                bindings.names.push(spec.local.name.to_string());
+                            }
+                        }
+                        ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                            bindings.namespaces.push(spec.local.name.to_string());
+                        }
+                        ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
+                            bindings.namespaces.push(spec.local.name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        bindings
+    }
+
+    /// Whether a usage of `name` (with the member path of member expression usages) is a fragment
+    pub(crate) fn is_fragment(&self, name: &str, member: Option<&str>) -> bool {
+        match member {
+            None => self.names.iter().any(|binding| binding == name),
+            Some("Fragment") => self.namespaces.iter().any(|binding| binding == name),
+            Some(_) => false,
+        }
+    }
+}
+
+/// Attribute name of a sensitive prop
+pub(crate) fn sensitive_prop_name(prop: types::SensitiveProp) -> &'static str {
+    SENSITIVE_PROPS
+        .iter()
+        .find(|(_, candidate)| *candidate == prop)
+        .map_or("", |(name, _)| name)
+}
+
 /// Public function to collect all JSX usages from the program body
-pub(crate) fn collect_jsx_usages(statements: &[Statement]) -> Vec<(String, Span)> {
-    let mut usages = Vec::new();
+pub fn collect_jsx_usages(statements: &[Statement]) -> Vec<(String, Span)> {
+    collect_jsx(statements).usages
+}
+
+/// Collect JSX usages along with the other JSX facts tracked in `JsxCollection`
+pub(crate) fn collect_jsx(statements: &[Statement]) -> JsxCollection {
+    collect_jsx_until(statements, Deadline::default())
+}
+
+/// Like [`collect_jsx`], but stop walking once `deadline` has passed or the memory budget is
+/// exceeded, and flag the collection as timed out
+pub(crate) fn collect_jsx_until(statements: &[Statement], deadline: Deadline) -> JsxCollection {
+    let mut collection = JsxCollection {
+        deadline,
+        ..JsxCollection::default()
+    };
     for statement in statements {
-        collect_jsx_from_statement(statement, &mut usages);
+        if collection.timed_out || deadline.expired() || crate::memory::exceeded() {
+            collection.timed_out = true;
+            break;
+        }
+        // Configs first, so elements reached by both walks are recorded as in a config
+        collect_config_usages(statement, &mut collection);
+        collect_jsx_from_statement(statement, &mut collection);
     }
-    usages
+    collection
 }
 
 // ============================================================================
@@ -19,20 +194,20 @@ pub(crate) fn collect_jsx_usages(statements: &[Statement]) -> Vec<(String, Span)
 // ============================================================================
 
 /// Recursively collect JSX element usages from a statement
-fn collect_jsx_from_statement(stmt: &Statement, usages: &mut Vec<(String, Span)>) {
+fn collect_jsx_from_statement(stmt: &Statement, collection: &mut JsxCollection) {
     match stmt {
         Statement::ReturnStatement(ret) => {
             if let Some(arg) = &ret.argument {
-                collect_jsx_from_expression(arg, usages);
+                collect_jsx_from_expression(arg, collection);
             }
         }
         Statement::ExpressionStatement(expr_stmt) => {
-            collect_jsx_from_expression(&expr_stmt.expression, usages);
+            collect_jsx_from_expression(&expr_stmt.expression, collection);
         }
         Statement::VariableDeclaration(var_decl) => {
             for declarator in var_decl.declarations.iter() {
                 if let Some(init) = &declarator.init {
-                    collect_jsx_from_expression(init, usages);
+                    collect_jsx_from_expression(init, collection);
                 }
             }
         }
@@ -43,14 +218,14 @@ fn collect_jsx_from_statement(stmt: &Statement, usages: &mut Vec<(String, Span)>
                     oxc::ast::ast::Declaration::VariableDeclaration(var_decl) => {
                         for declarator in var_decl.declarations.iter() {
                             if let Some(init) = &declarator.init {
-                                collect_jsx_from_expression(init, usages);
+                                collect_jsx_from_expression(init, collection);
                             }
                         }
                     }
                     oxc::ast::ast::Declaration::FunctionDeclaration(func_decl) => {
                         if let Some(body) = &func_decl.body {
                             for stmt in body.statements.iter() {
-                                collect_jsx_from_statement(stmt, usages);
+                                collect_jsx_from_statement(stmt, collection);
                             }
                         }
                     }
@@ -62,7 +237,7 @@ fn collect_jsx_from_statement(stmt: &Statement, usages: &mut Vec<(String, Span)>
             // Handle: export default () => <div/>
             // ExportDefaultDeclarationKind inherits from Expression, so we use as_expression()
             if let Some(expr) = export_decl.declaration.as_expression() {
-                collect_jsx_from_expression(expr, usages);
+                collect_jsx_from_expression(expr, collection);
             } else {
                 // Handle FunctionDeclaration case
                 if let oxc::ast::ast::ExportDefaultDeclarationKind::FunctionDeclaration(func_decl) =
@@ -70,20 +245,20 @@ fn collect_jsx_from_statement(stmt: &Statement, usages: &mut Vec<(String, Span)>
                     && let Some(body) = &func_decl.body
                 {
                     for stmt in body.statements.iter() {
-                        collect_jsx_from_statement(stmt, usages);
+                        collect_jsx_from_statement(stmt, collection);
                     }
                 }
             }
         }
         Statement::BlockStatement(block) => {
             for stmt in block.body.iter() {
-                collect_jsx_from_statement(stmt, usages);
+                collect_jsx_from_statement(stmt, collection);
             }
         }
         Statement::IfStatement(if_stmt) => {
-            collect_jsx_from_statement(&if_stmt.consequent, usages);
+            collect_jsx_from_statement(&if_stmt.consequent, collection);
             if let Some(alternate) = &if_stmt.alternate {
-                collect_jsx_from_statement(alternate, usages);
+                collect_jsx_from_statement(alternate, collection);
             }
         }
         _ => {}
@@ -91,31 +266,70 @@ fn collect_jsx_from_statement(stmt: &Statement, usages: &mut Vec<(String, Span)>
 }
 
 /// Recursively collect JSX element usages from an expression
-fn collect_jsx_from_expression(expr: &Expression, usages: &mut Vec<(String, Span)>) {
+fn collect_jsx_from_expression(expr: &Expression, collection: &mut JsxCollection) {
     match expr {
         Expression::JSXElement(jsx_elem) => {
-            collect_jsx_from_element(jsx_elem, usages);
+            collect_jsx_from_element(jsx_elem, collection);
         }
         Expression::JSXFragment(jsx_frag) => {
-            for child in jsx_frag.children.iter() {
-                if let oxc::ast::ast::JSXChild::Element(child_elem) = child {
-                    collect_jsx_from_element(child_elem, usages);
-                }
-            }
+            collect_jsx_from_children(&jsx_frag.children, collection);
         }
         Expression::ParenthesizedExpression(paren) => {
             // Unwrap the parentheses and process the inner expression
-            collect_jsx_from_expression(&paren.expression, usages);
+            collect_jsx_from_expression(&paren.expression, collection);
         }
         Expression::ArrowFunctionExpression(arrow) => {
             for stmt in arrow.body.statements.iter() {
-                collect_jsx_from_statement(stmt, usages);
+                collect_jsx_from_statement(stmt, collection);
             }
         }
         Expression::FunctionExpression(func) => {
             if let Some(body) = &func.body {
                 for stmt in body.statements.iter() {
-                    collect_jsx_from_statement(stmt, usages);
+                    collect_jsx_from_statement(stmt, collection);
+                }
+            }
+        }
+        // Elements embedded in template slots: html`<p>${<Chip />}</p>`
+        Expression::TemplateLiteral(template) => {
+            for expr in template.expressions.iter() {
+                collect_jsx_from_expression(expr, collection);
+            }
+        }
+        Expression::TaggedTemplateExpression(tagged) => {
+            for expr in tagged.quasi.expressions.iter() {
+                collect_jsx_from_expression(expr, collection);
+            }
+        }
+        // Either branch may render: {open ? <Dialog /> : <Button />}, {open && <Dialog />}
+        Expression::ConditionalExpression(conditional) => {
+            collect_jsx_from_expression(&conditional.consequent, collection);
+            collect_jsx_from_expression(&conditional.alternate, collection);
+        }
+        Expression::LogicalExpression(logical) => {
+            collect_jsx_from_expression(&logical.left, collection);
+            collect_jsx_from_expression(&logical.right, collection);
+        }
+        // Callbacks and elements passed to calls: {items.map((item) => <Item {...item} />)}
+        Expression::CallExpression(call) => {
+            for argument in call.arguments.iter() {
+                if let Some(expr) = argument.as_expression() {
+                    collect_jsx_from_expression(expr, collection);
+                }
+            }
+        }
+        // Lookup tables: new Map([["home", () => <Home />]])
+        Expression::NewExpression(new) => {
+            for argument in new.arguments.iter() {
+                if let Some(expr) = argument.as_expression() {
+                    collect_jsx_from_expression(expr, collection);
+                }
+            }
+        }
+        Expression::ArrayExpression(array) => {
+            for element in array.elements.iter() {
+                if let Some(expr) = element.as_expression() {
+                    collect_jsx_from_expression(expr, collection);
                 }
             }
         }
@@ -124,17 +338,26 @@ fn collect_jsx_from_expression(expr: &Expression, usages: &mut Vec<(String, Span
 }
 
 /// Recursively collect JSX element usages from a JSXElement
-fn collect_jsx_from_element(
-    jsx_elem: &oxc::ast::ast::JSXElement,
-    usages: &mut Vec<(String, Span)>,
-) {
+fn collect_jsx_from_element(jsx_elem: &oxc::ast::ast::JSXElement, collection: &mut JsxCollection) {
+    if collection.timed_out || !collection.elements.insert(jsx_elem.span) {
+        return;
+    }
+    if collection
+        .elements
+        .len()
+        .is_multiple_of(DEADLINE_CHECK_INTERVAL)
+        && (collection.deadline.expired() || crate::memory::exceeded())
+    {
+        collection.timed_out = true;
+        return;
+    }
     match &jsx_elem.opening_element.name {
         JSXElementName::Identifier(ident) => {
             let name = ident.name.to_string();
             // Only track PascalCase components (user-defined components)
             if name.chars().next().is_some_and(|c| c.is_uppercase()) {
                 // Use the entire JSX element's span so decoration appears after closing tag
-                usages.push((name.clone(), jsx_elem.span));
+                collection.usages.push((name.clone(), jsx_elem.span));
             }
         }
         JSXElementName::IdentifierReference(ident) => {
@@ -142,18 +365,26 @@ fn collect_jsx_from_element(
             // Only track PascalCase components (user-defined components)
             if name.chars().next().is_some_and(|c| c.is_uppercase()) {
                 // Use the entire JSX element's span so decoration appears after closing tag
-                usages.push((name.clone(), jsx_elem.span));
+                collection.usages.push((name.clone(), jsx_elem.span));
             }
         }
         JSXElementName::MemberExpression(member_expr) => {
             // For member expressions like <AlertDialog.Root>, we need to extract the base object
-            // We track the base identifier (e.g., "AlertDialog") so we can match it against imports
-            if let JSXMemberExpressionObject::IdentifierReference(base_ident) = &member_expr.object
-            {
+            // We track the base identifier (e.g., "AlertDialog") so we can match it against imports,
+            // and the member path (e.g., "Root") so hosts can tell the parts apart
+            let mut members = vec![member_expr.property.name.as_str()];
+            let mut object = &member_expr.object;
+            while let JSXMemberExpressionObject::MemberExpression(inner) = object {
+                members.push(inner.property.name.as_str());
+                object = &inner.object;
+            }
+            if let JSXMemberExpressionObject::IdentifierReference(base_ident) = object {
                 let base_name = base_ident.name.to_string();
                 if base_name.chars().next().is_some_and(|c| c.is_uppercase()) {
                     // Use the entire JSX element's span so decoration appears after closing tag
-                    usages.push((base_name, jsx_elem.span));
+                    collection.usages.push((base_name, jsx_elem.span));
+                    members.reverse();
+                    collection.members.insert(jsx_elem.span, members.join("."));
                 }
             }
         }
@@ -164,14 +395,270 @@ fn collect_jsx_from_element(
             // Skip this.Component patterns (class component style)
         }
     }
-    // Collect from children
-    for child in jsx_elem.children.iter() {
-        if let oxc::ast::ast::JSXChild::Element(child_elem) = child {
-            collect_jsx_from_element(child_elem, usages);
+    collect_action_bindings(&jsx_elem.opening_element, collection);
+    collect_sensitive_props(&jsx_elem.opening_element, jsx_elem.span, collection);
+    collect_attributes(&jsx_elem.opening_element, jsx_elem.span, collection);
+    collect_jsx_from_children(&jsx_elem.children, collection);
+}
+
+/// Collect JSX element usages from the children of an element or fragment, including the JSX
+/// returned by render props: <DataProvider>{(data) => <Chart data={data} />}</DataProvider>
+fn collect_jsx_from_children(children: &[JSXChild], collection: &mut JsxCollection) {
+    for child in children {
+        match child {
+            JSXChild::Element(child_elem) => {
+                collect_jsx_from_element(child_elem, collection);
+            }
+            JSXChild::Fragment(child_frag) => {
+                collect_jsx_from_children(&child_frag.children, collection);
+            }
+            JSXChild::ExpressionContainer(container) => {
+                if let Some(expr) = container.expression.as_expression() {
+                    collect_jsx_from_expression(expr, collection);
+                }
+            }
+            _ => {}
         }
     }
 }
 
+/// Collect usages from the array and object literals of top-level variables and default exports,
+/// such as route configs (`export const routes = [{ path: "/", element: <Home />, Component:
+/// Settings }]`), and from the literals passed to `new` expressions, such as lookup tables
+/// (`const pages = new Map([["home", () => <Home />]])`). Both JSX elements and PascalCase
+/// identifier references count as usages.
+fn collect_config_usages(stmt: &Statement, collection: &mut JsxCollection) {
+    use oxc::ast::ast::Declaration;
+
+    let mut configs = Vec::new();
+    match stmt {
+        Statement::VariableDeclaration(var_decl) => {
+            configs.extend(
+                var_decl
+                    .declarations
+                    .iter()
+                    .filter_map(|declarator| declarator.init.as_ref()),
+            );
+        }
+        Statement::ExportNamedDeclaration(export_decl) => {
+            if let Some(Declaration::VariableDeclaration(var_decl)) = &export_decl.declaration {
+                configs.extend(
+                    var_decl
+                        .declarations
+                        .iter()
+                        .filter_map(|declarator| declarator.init.as_ref()),
+                );
+            }
+        }
+        Statement::ExportDefaultDeclaration(export_decl) => {
+            configs.extend(export_decl.declaration.as_expression());
+        }
+        _ => {}
+    }
+
+    // The constructor itself (`Map`) is not a usage, so only the literals passed to it are walked
+    let configs = configs
+        .into_iter()
+        .flat_map(|config| match config.get_inner_expression() {
+            Expression::NewExpression(new) => new
+                .arguments
+                .iter()
+                .filter_map(|argument| argument.as_expression())
+                .collect::<Vec<_>>(),
+            _ => vec![config],
+        });
+
+    for config in configs {
+        if !matches!(
+            config.get_inner_expression(),
+            Expression::ArrayExpression(_) | Expression::ObjectExpression(_)
+        ) {
+            continue;
+        }
+
+        let usages_before = collection.usages.len();
+        ConfigUsageCollector {
+            collection: &mut *collection,
+        }
+        .visit_expression(config);
+        let config_spans = collection.usages[usages_before..]
+            .iter()
+            .map(|(_, span)| *span)
+            .collect::<Vec<_>>();
+        collection.config_spans.extend(config_spans);
+    }
+}
+
+/// Collects the elements and PascalCase identifiers of a config literal
+struct ConfigUsageCollector<'c> {
+    collection: &'c mut JsxCollection,
+}
+
+impl<'a> Visit<'a> for ConfigUsageCollector<'_> {
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        match expr {
+            // Nested elements are collected along with their parent, and elements inside
+            // expression containers are visited by the walk itself
+            Expression::JSXElement(jsx_elem) => collect_jsx_from_element(jsx_elem, self.collection),
+            Expression::JSXFragment(jsx_frag) => {
+                for child in jsx_frag.children.iter() {
+                    if let JSXChild::Element(child_elem) = child {
+                        collect_jsx_from_element(child_elem, self.collection);
+                    }
+                }
+            }
+            Expression::Identifier(ident)
+                if ident.name.chars().next().is_some_and(|c| c.is_uppercase()) =>
+            {
+                self.collection
+                    .usages
+                    .push((ident.name.to_string(), ident.span));
+            }
+            _ => {}
+        }
+        walk::walk_expression(self, expr);
+    }
+}
+
+/// Collect identifiers passed to form actions: <form action={save}> or <button formAction={save}>
+fn collect_action_bindings(
+    opening_element: &oxc::ast::ast::JSXOpeningElement,
+    collection: &mut JsxCollection,
+) {
+    use oxc::ast::ast::{JSXAttributeItem, JSXAttributeName, JSXAttributeValue};
+
+    for attribute in opening_element.attributes.iter() {
+        if let JSXAttributeItem::Attribute(attr) = attribute
+            && let JSXAttributeName::Identifier(name) = &attr.name
+            && matches!(name.name.as_str(), "action" | "formAction")
+            && let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value
+            && let Some(expr) = container.expression.as_expression()
+        {
+            // Track the base identifier for member expressions like actions.save
+            let binding = match expr.get_inner_expression() {
+                Expression::Identifier(ident) => Some(ident.name.to_string()),
+                Expression::StaticMemberExpression(member) => match &member.object {
+                    Expression::Identifier(ident) => Some(ident.name.to_string()),
+                    _ => None,
+                },
+                // Pre-filled arguments: action.bind(null, id)
+                Expression::CallExpression(call) => match call.callee.get_inner_expression() {
+                    Expression::StaticMemberExpression(member)
+                        if member.property.name == "bind" =>
+                    {
+                        match &member.object {
+                            Expression::Identifier(ident) => {
+                                let name = ident.name.to_string();
+                                collection.bound_actions.push((name.clone(), attr.span));
+                                Some(name)
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(binding) = binding {
+                collection.action_bindings.push((binding, attr.span));
+            }
+        }
+    }
+}
+
+/// Collect `dangerouslySetInnerHTML`, `suppressHydrationWarning` and `ref` attributes
+fn collect_sensitive_props(
+    opening_element: &oxc::ast::ast::JSXOpeningElement,
+    element_span: Span,
+    collection: &mut JsxCollection,
+) {
+    use oxc::ast::ast::{JSXAttributeItem, JSXAttributeName};
+
+    for attribute in opening_element.attributes.iter() {
+        if let JSXAttributeItem::Attribute(attr) = attribute
+            && let JSXAttributeName::Identifier(name) = &attr.name
+            && let Some((_, prop)) = SENSITIVE_PROPS
+                .iter()
+                .find(|(prop_name, _)| *prop_name == name.name.as_str())
+        {
+            collection
+                .sensitive_props
+                .entry(element_span)
+                .or_default()
+                .push((*prop, attr.span));
+        }
+    }
+}
+
+/// Collect the names and spans of the element's attributes, so diagnostics can point at a single
+/// prop, and the spread attributes, whose props are unknown
+fn collect_attributes(
+    opening_element: &oxc::ast::ast::JSXOpeningElement,
+    element_span: Span,
+    collection: &mut JsxCollection,
+) {
+    use oxc::ast::ast::{JSXAttributeItem, JSXAttributeName};
+
+    for attribute in opening_element.attributes.iter() {
+        match attribute {
+            JSXAttributeItem::Attribute(attr) => {
+                let name = match &attr.name {
+                    JSXAttributeName::Identifier(name) => name.name.to_string(),
+                    JSXAttributeName::NamespacedName(name) => {
+                        format!("{}:{}", name.namespace.name, name.name.name)
+                    }
+                };
+                if is_inline_function(attr.value.as_ref()) {
+                    collection
+                        .function_props
+                        .push((element_span, name.clone(), attr.span));
+                }
+                collection
+                    .attributes
+                    .entry(element_span)
+                    .or_default()
+                    .push((name, attr.span));
+            }
+            JSXAttributeItem::SpreadAttribute(spread) => {
+                let mut object = spread.argument.get_inner_expression();
+                while let Expression::StaticMemberExpression(member) = object {
+                    object = member.object.get_inner_expression();
+                }
+                let name = match object {
+                    Expression::Identifier(ident) => Some(ident.name.to_string()),
+                    _ => None,
+                };
+                collection
+                    .spreads
+                    .entry(element_span)
+                    .or_default()
+                    .push((name, spread.span));
+            }
+        }
+    }
+}
+
+/// Whether an attribute value is an inline function without a "use server" directive
+fn is_inline_function(value: Option<&oxc::ast::ast::JSXAttributeValue>) -> bool {
+    use oxc::ast::ast::{JSXAttributeValue, JSXExpression};
+
+    let Some(JSXAttributeValue::ExpressionContainer(container)) = value else {
+        return false;
+    };
+    let body = match &container.expression {
+        JSXExpression::ArrowFunctionExpression(arrow) => &arrow.body,
+        JSXExpression::FunctionExpression(func) => match &func.body {
+            Some(body) => body,
+            None => return false,
+        },
+        _ => return false,
+    };
+    !body
+        .directives
+        .iter()
+        .any(|directive| directive.directive == "use server")
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -280,6 +767,51 @@ mod tests {
         assert_eq!(usages.len(), 2, "Should find JSX in fragments");
     }
 
+    #[test]
+    fn test_collect_jsx_usages_in_fragment_expression_containers() {
+        let source = r#"
+            const List = ({ items }) => (
+                <>
+                    {items.map((item) => <Item key={item.id} {...item} />)}
+                    <><Footer /></>
+                </>
+            );
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let usages = collect_jsx_usages(&ret.program.body);
+
+        let names = usages
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Item", "Footer"]);
+    }
+
+    #[test]
+    fn test_collect_jsx_usages_in_conditional_and_logical_containers() {
+        let source = r#"
+            const Panel = ({ open, user }) => (
+                <div>
+                    {open ? <Dialog /> : <Button />}
+                    {user && <Avatar />}
+                    {user ?? <Guest />}
+                </div>
+            );
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let usages = collect_jsx_usages(&ret.program.body);
+
+        let names = usages
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Dialog", "Button", "Avatar", "Guest"]);
+    }
+
     #[test]
     fn test_collect_jsx_usages_in_if_statement() {
         let source = r#"
@@ -344,4 +876,392 @@ mod tests {
             "All usages should be 'AlertDialog'"
         );
     }
+
+    #[test]
+    fn test_collect_action_bindings() {
+        let source = r#"
+            const Page = () => {
+                return (
+                    <form action={createPost}>
+                        <button formAction={actions.remove}>Delete</button>
+                        <button formAction="/fallback">Fallback</button>
+                    </form>
+                );
+            };
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let collection = collect_jsx(&ret.program.body);
+
+        let bindings: Vec<&str> = collection
+            .action_bindings
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(bindings, vec!["createPost", "actions"]);
+        assert!(collection.bound_actions.is_empty());
+    }
+
+    #[test]
+    fn test_collect_bound_actions() {
+        let source = r#"
+            const Item = ({ id }) => {
+                return (
+                    <form action={deleteItem.bind(null, id)}>
+                        <button formAction={archiveItem}>Archive</button>
+                    </form>
+                );
+            };
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let collection = collect_jsx(&ret.program.body);
+
+        assert_eq!(collection.action_bindings.len(), 2);
+        assert_eq!(collection.bound_actions.len(), 1);
+        assert_eq!(collection.bound_actions[0].0, "deleteItem");
+    }
+
+    #[test]
+    fn test_collect_sensitive_props() {
+        let source = r#"
+export default function Page() {
+  return (
+    <html suppressHydrationWarning>
+      <Editor ref={editorRef} dangerouslySetInnerHTML={{ __html: html }} />
+      <div key="a" />
+    </html>
+  );
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let collection = collect_jsx(&ret.program.body);
+
+        let mut props = collection
+            .sensitive_props
+            .values()
+            .flatten()
+            .collect::<Vec<_>>();
+        props.sort_by_key(|(_, span)| span.start);
+        let props = props
+            .into_iter()
+            .map(|(prop, _)| sensitive_prop_name(*prop))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            props,
+            vec!["suppressHydrationWarning", "ref", "dangerouslySetInnerHTML"]
+        );
+        assert_eq!(collection.usages.len(), 1);
+        assert_eq!(
+            collection.props_for(collection.usages[0].1),
+            vec![
+                types::SensitiveProp::Ref,
+                types::SensitiveProp::DangerouslySetInnerHtml
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_member_paths() {
+        let source = r#"
+export default function App() {
+  return (
+    <Icons.Check>
+      <UI.Dialog.Title />
+      <Plain />
+    </Icons.Check>
+  );
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let collection = collect_jsx(&ret.program.body);
+
+        let usages = collection
+            .usages
+            .iter()
+            .map(|(name, span)| (name.as_str(), collection.member_for(*span)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            usages,
+            vec![
+                ("Icons", Some("Check".to_string())),
+                ("UI", Some("Dialog.Title".to_string())),
+                ("Plain", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fragment_bindings() {
+        let source = r#"
+import ReactDefault, { Fragment as F } from "react";
+import * as R from "react";
+import { Fragment } from "./fragment";
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let bindings = FragmentBindings::collect(&ret.program.body);
+
+        assert!(bindings.is_fragment("F", None));
+        assert!(bindings.is_fragment("R", Some("Fragment")));
+        assert!(bindings.is_fragment("ReactDefault", Some("Fragment")));
+        assert!(bindings.is_fragment("React", Some("Fragment")));
+        assert!(!bindings.is_fragment("Fragment", None));
+        assert!(!bindings.is_fragment("R", Some("StrictMode")));
+    }
+
+    #[test]
+    fn test_collect_config_usages() {
+        let source = r#"
+export const routes = [
+  { path: "/", element: <Layout><Home /></Layout> },
+  { path: "/settings", Component: Settings, loader: () => fetch("/api") },
+] satisfies RouteObject[];
+
+export const title = "Routes";
+
+export default function App() {
+  return <Router />;
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let collection = collect_jsx(&ret.program.body);
+
+        let usages = collection
+            .usages
+            .iter()
+            .map(|(name, span)| (name.as_str(), collection.in_config(*span)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            usages,
+            vec![
+                ("Layout", true),
+                ("Home", true),
+                ("Settings", true),
+                ("Router", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_config_usages_in_new_expressions() {
+        let source = r#"
+export const PAGES = new Map([
+  ["home", () => <Home />],
+  ["settings", Settings],
+]);
+export const LAZY = new WeakMap(Object.entries({ chart: Chart }));
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let collection = collect_jsx(&ret.program.body);
+
+        let usages = collection
+            .usages
+            .iter()
+            .map(|(name, span)| (name.as_str(), collection.in_config(*span)))
+            .collect::<Vec<_>>();
+        assert_eq!(usages, vec![("Home", true), ("Settings", true)]);
+    }
+
+    #[test]
+    fn test_collect_config_usages_in_local_literals() {
+        let source = r#"
+const COMPONENTS = new Map([["home", () => <Home />]]);
+const items = [<Card />, <Card />];
+export function Page() {
+  const rows = [<Row />];
+  return new Layout([<Footer />]);
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let collection = collect_jsx(&ret.program.body);
+
+        let usages = collection
+            .usages
+            .iter()
+            .map(|(name, span)| (name.as_str(), collection.in_config(*span)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            usages,
+            vec![
+                ("Home", true),
+                ("Card", true),
+                ("Card", true),
+                ("Row", false),
+                ("Footer", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_attribute_spans() {
+        let source = r#"
+export default function Page({ user }) {
+  return <Profile name={user.name} onSelect={() => {}} {...user} xlink:href="/a" />;
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let collection = collect_jsx(&ret.program.body);
+
+        let attributes = collection
+            .attributes_for(collection.usages[0].1)
+            .into_iter()
+            .map(|(name, span)| (name, &source[span.start as usize..span.end as usize]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            attributes,
+            vec![
+                ("name".to_string(), "name={user.name}"),
+                ("onSelect".to_string(), "onSelect={() => {}}"),
+                ("xlink:href".to_string(), "xlink:href=\"/a\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_jsx_usages_in_render_props() {
+        let source = r#"
+export default function Dashboard() {
+  return (
+    <DataProvider>
+      {(data) => <Chart data={data} />}
+      {function renderLegend() {
+        return <Legend />;
+      }}
+      {(<Footer />)}
+    </DataProvider>
+  );
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let usages = collect_jsx_usages(&ret.program.body);
+
+        let names = usages
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["DataProvider", "Chart", "Legend", "Footer"]);
+    }
+
+    #[test]
+    fn test_collect_jsx_usages_in_template_literals() {
+        let source = r#"
+const message = html`<p>${<Chip label="new" />}</p>`;
+export function Banner() {
+  return md`# Release ${(<Version />)} ${`nested ${<Badge />}`}`;
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let usages = collect_jsx_usages(&ret.program.body);
+
+        let names = usages
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Chip", "Version", "Badge"]);
+    }
+
+    #[test]
+    fn test_collect_render_prop_usages_in_config_once() {
+        let source = r#"
+export const routes = [
+  { path: "/", element: <Layout>{(user) => <Home user={user} />}</Layout> },
+];
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let usages = collect_jsx_usages(&ret.program.body);
+
+        let names = usages
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Layout", "Home"]);
+    }
+
+    #[test]
+    fn test_collect_key_and_ref() {
+        let source = r#"
+export default function Form({ inputRef }) {
+  return (
+    <>
+      <Input ref={inputRef} />
+      <Row key="total" />
+      <Footer />
+    </>
+  );
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let collection = collect_jsx(&ret.program.body);
+
+        let usages = collection
+            .usages
+            .iter()
+            .map(|(name, span)| {
+                (
+                    name.as_str(),
+                    collection.passes(*span, "key"),
+                    collection.passes(*span, "ref"),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            usages,
+            vec![
+                ("Input", false, true),
+                ("Row", true, false),
+                ("Footer", false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_spread_attributes() {
+        let source = r#"
+export default function Field({ field, ...rest }) {
+  return <Input {...rest} {...field.props.input} {...getProps()} id="a" />;
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let collection = collect_jsx(&ret.program.body);
+
+        let spreads = collection
+            .spreads_for(collection.usages[0].1)
+            .into_iter()
+            .map(|(name, span)| (name, &source[span.start as usize..span.end as usize]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            spreads,
+            vec![
+                (Some("rest".to_string()), "{...rest}"),
+                (Some("field".to_string()), "{...field.props.input}"),
+                (None, "{...getProps()}"),
+            ]
+        );
+    }
 }
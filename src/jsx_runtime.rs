@@ -0,0 +1,251 @@
+use crate::analyze_react_boundary::check::types;
+use oxc::ast::ast::{
+    Expression, ImportDeclarationSpecifier, JSXElement, JSXFragment, ModuleExportName, Statement,
+};
+use oxc::ast_visit::{Visit, walk};
+use oxc::span::Span;
+
+/// Modules of the automatic runtime, which compiled JSX calls (`jsx`, `jsxs`, `jsxDEV`) come from
+const RUNTIME_MODULES: [(&str, types::JsxRuntime); 2] = [
+    ("react/jsx-runtime", types::JsxRuntime::Automatic),
+    ("react/jsx-dev-runtime", types::JsxRuntime::Development),
+];
+
+/// Module providing `createElement` to the classic runtime
+const REACT: &str = "react";
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Detect the ways a module creates elements: JSX syntax, calls into the automatic runtime
+/// (`react/jsx-runtime`) or its development variant, or classic `createElement` calls. Each
+/// variant found comes with the spans of the import declarations providing it, in the order of
+/// `types::JsxRuntime`. Compiled output only shows up as calls, so knowing which variant was seen
+/// helps telling a missed component from a module that doesn't render at all
+pub(crate) fn detect_jsx_runtimes(statements: &[Statement]) -> Vec<(types::JsxRuntime, Vec<Span>)> {
+    let mut runtimes = Vec::new();
+
+    if has_jsx_syntax(statements) {
+        runtimes.push((types::JsxRuntime::Syntax, Vec::new()));
+    }
+
+    for (module, runtime) in RUNTIME_MODULES {
+        let spans = statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::ImportDeclaration(import_decl)
+                    if import_decl.source.value == module && !import_decl.import_kind.is_type() =>
+                {
+                    Some(import_decl.span)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if !spans.is_empty() {
+            runtimes.push((runtime, spans));
+        }
+    }
+
+    if let Some(spans) = classic_imports(statements) {
+        runtimes.push((types::JsxRuntime::Classic, spans));
+    }
+
+    runtimes
+}
+
+/// Whether the statements contain a JSX element or fragment
+pub(crate) fn has_jsx_syntax(statements: &[Statement]) -> bool {
+    let mut finder = JsxSyntaxFinder::default();
+    for statement in statements {
+        if finder.found {
+            break;
+        }
+        finder.visit_statement(statement);
+    }
+    finder.found
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+#[derive(Default)]
+struct JsxSyntaxFinder {
+    found: bool,
+}
+
+impl<'a> Visit<'a> for JsxSyntaxFinder {
+    fn visit_jsx_element(&mut self, _element: &JSXElement<'a>) {
+        self.found = true;
+    }
+
+    fn visit_jsx_fragment(&mut self, _fragment: &JSXFragment<'a>) {
+        self.found = true;
+    }
+}
+
+/// `createElement` calls, by the span of the import they go through (`None` for the global
+/// `React`)
+struct CreateElementFinder<'c> {
+    functions: &'c [(&'c str, Span)],
+    namespaces: &'c [(&'c str, Option<Span>)],
+    calls: Vec<Option<Span>>,
+}
+
+impl<'a> Visit<'a> for CreateElementFinder<'_> {
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        if let Expression::CallExpression(call) = expr {
+            let span = match call.callee.get_inner_expression() {
+                Expression::Identifier(ident) => self
+                    .functions
+                    .iter()
+                    .find(|(local, _)| *local == ident.name.as_str())
+                    .map(|(_, span)| Some(*span)),
+                Expression::StaticMemberExpression(member)
+                    if member.property.name == "createElement" =>
+                {
+                    match &member.object {
+                        Expression::Identifier(object) => self
+                            .namespaces
+                            .iter()
+                            .rev()
+                            .find(|(local, _)| *local == object.name.as_str())
+                            .map(|(_, span)| *span),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            self.calls.extend(span);
+        }
+        walk::walk_expression(self, expr);
+    }
+}
+
+/// Spans of the `react` imports the module's `createElement` calls go through, or `None` when it
+/// makes none. Calls on the global `React` count without an import
+fn classic_imports(statements: &[Statement]) -> Option<Vec<Span>> {
+    // Local names bound to `createElement` and to the whole of `react`, with their declarations
+    let mut functions = Vec::new();
+    let mut namespaces = vec![("React", None)];
+
+    for statement in statements {
+        let Statement::ImportDeclaration(import_decl) = statement else {
+            continue;
+        };
+        if import_decl.source.value != REACT || import_decl.import_kind.is_type() {
+            continue;
+        }
+        for specifier in import_decl.specifiers.iter().flatten() {
+            match specifier {
+                ImportDeclarationSpecifier::ImportSpecifier(spec)
+                    if !spec.import_kind.is_type()
+                        && matches!(&spec.imported, ModuleExportName::IdentifierName(name)
+                            if name.name == "createElement") =>
+                {
+                    functions.push((spec.local.name.as_str(), import_decl.span));
+                }
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                    namespaces.push((spec.local.name.as_str(), Some(import_decl.span)));
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
+                    namespaces.push((spec.local.name.as_str(), Some(import_decl.span)));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut finder = CreateElementFinder {
+        functions: &functions,
+        namespaces: &namespaces,
+        calls: Vec::new(),
+    };
+    for statement in statements {
+        finder.visit_statement(statement);
+    }
+    (!finder.calls.is_empty()).then(|| {
+        let mut spans = finder.calls.into_iter().flatten().collect::<Vec<_>>();
+        spans.sort_by_key(|span| span.start);
+        spans.dedup();
+        spans
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    fn detect(source: &str) -> Vec<(types::JsxRuntime, Vec<&str>)> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        detect_jsx_runtimes(&ret.program.body)
+            .into_iter()
+            .map(|(runtime, spans)| {
+                (
+                    runtime,
+                    spans
+                        .iter()
+                        .map(|span| &source[span.start as usize..span.end as usize])
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_jsx_runtimes() {
+        let source = r#"
+import { jsx as _jsx } from "react/jsx-runtime";
+import { jsxDEV } from "react/jsx-dev-runtime";
+import * as R from "react";
+import { createElement as h, useState } from "react";
+export const A = () => _jsx("div", {});
+export const B = () => jsxDEV("span", {});
+export const C = () => R.createElement("p");
+export const D = () => h("p");
+export const E = () => <></>;
+        "#;
+
+        assert_eq!(
+            detect(source),
+            vec![
+                (types::JsxRuntime::Syntax, vec![]),
+                (
+                    types::JsxRuntime::Automatic,
+                    vec!["import { jsx as _jsx } from \"react/jsx-runtime\";"]
+                ),
+                (
+                    types::JsxRuntime::Development,
+                    vec!["import { jsxDEV } from \"react/jsx-dev-runtime\";"]
+                ),
+                (
+                    types::JsxRuntime::Classic,
+                    vec![
+                        "import * as R from \"react\";",
+                        "import { createElement as h, useState } from \"react\";"
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_jsx_runtimes_classic_global() {
+        assert_eq!(
+            detect("export const A = () => React.createElement(\"div\");"),
+            vec![(types::JsxRuntime::Classic, vec![])]
+        );
+        // Importing `createElement` without calling it is not a runtime in use
+        assert!(detect("import { createElement } from \"react\";").is_empty());
+        assert!(detect("export const n = 1;").is_empty());
+    }
+}
@@ -1,338 +1,1876 @@
+mod action;
+mod annotation;
+mod asset;
+#[cfg(feature = "astro")]
+mod astro;
+mod batch;
+mod classify;
+mod client_export;
 mod component;
+mod compound;
+mod conditional;
+mod context;
+mod deadline;
+mod debug_usage;
+mod diagram;
+mod dynamic;
+mod entry;
+mod fix;
+mod flow;
+#[cfg(test)]
+mod golden;
+mod graph;
+mod import_meta;
+mod intern;
 mod jsx;
+mod jsx_runtime;
+mod location;
+mod memory;
+mod module_kind;
+#[cfg(feature = "native")]
+pub mod native;
+mod nested;
+mod package;
+mod prologue;
+mod props;
 mod range;
+mod re_export;
+mod reference;
+mod resolve;
+mod route;
+mod rules;
+mod session;
+mod signal;
+mod summary;
+#[cfg(test)]
+mod test_support;
+mod type_export;
+mod unused;
+mod value_export;
+mod vfs;
+mod walk;
+mod weight;
+mod worker;
 
 use crate::analyze_react_boundary::check::types;
+use crate::rules::Rule;
 use oxc::allocator::Allocator;
-use oxc::ast::ast::{BindingPatternKind, ExportDefaultDeclarationKind, ImportDeclarationSpecifier};
 use oxc::ast::ast::{
-    Declaration, Expression, ImportOrExportKind, ObjectPropertyKind, PropertyKey, Statement,
+    AssignmentOperator, AssignmentTarget, Declaration, Expression, ImportOrExportKind,
+    ModuleExportName, ObjectExpression, ObjectPropertyKind, PropertyKey, Statement,
 };
+use oxc::ast::ast::{BindingPatternKind, ExportDefaultDeclarationKind, ImportDeclarationSpecifier};
 use oxc::parser::{ParseOptions, Parser};
 use oxc::span::{SourceType, Span};
 use std::collections::{HashMap, HashSet};
 
-wit_bindgen::generate!();
+mod bindings {
+    // The bindings flatten records into the parameters of the exported ABI functions
+    #![allow(clippy::too_many_arguments)]
+
+    #[cfg(not(feature = "serde"))]
+    wit_bindgen::generate!({
+        default_bindings_module: "crate::bindings",
+    });
+
+    // Native consumers exchange results as JSON, so the generated records also derive serde
+    #[cfg(feature = "serde")]
+    wit_bindgen::generate!({
+        additional_derives: [serde::Serialize, serde::Deserialize],
+        default_bindings_module: "crate::bindings",
+    });
+}
+
+use bindings::*;
 
 struct AnalyzeReactBoundary;
 
-impl Guest for AnalyzeReactBoundary {
-    fn analyze(content: Vec<u8>, extension: String) -> Result<AnalysisResult, String> {
-        let source_text = String::from_utf8(content).unwrap();
-        let source_type = SourceType::from_extension(&extension)
-            .map_err(|e| format!("{}: {}", e.to_string().replace("\"", ""), extension))?;
-
-        let allocator = Allocator::default();
-        let ret = Parser::new(&allocator, &source_text, source_type)
-            .with_options(ParseOptions {
-                parse_regular_expression: true,
-                ..ParseOptions::default()
-            })
-            .parse();
+/// An exported component collected while walking the module's export statements
+struct ExportedComponent {
+    /// Local name of the component
+    name: String,
+    /// Span of the component declaration identifier
+    span: Span,
+    kind: types::ExportKind,
+    /// Name the component is exported as, when it differs from the local name
+    exported_name: Option<String>,
+    /// Span of the first export statement that exports the component
+    export_span: Span,
+    /// Whether the component is one of several candidates of a conditional default export
+    /// (`export default isTouch ? MobileNav : DesktopNav`)
+    conditional: bool,
+    /// Whether the component is a property of an object exported as default
+    /// (`export default { Button, Link }`), rendered elsewhere as `<UI.Button>`
+    grouped: bool,
+}
 
-        if ret.panicked
-            && let Some(error) = ret.errors.into_iter().next()
-        {
-            let source_code_error = error.clone().with_source_code(source_text.clone());
-            #[cfg(not(test))]
-            log(&format!(
-                "Error: {} with code {}",
-                error.message, source_code_error
-            ));
+/// Register an exported component, merging it with an earlier export of the same component
+/// (e.g. `export const X = ...` followed by `export default X`)
+fn register_export(exported_components: &mut Vec<ExportedComponent>, export: ExportedComponent) {
+    let Some(existing) = exported_components.iter_mut().find(|existing| {
+        existing.name == export.name
+            && existing.span == export.span
+            && existing.grouped == export.grouped
+    }) else {
+        exported_components.push(export);
+        return;
+    };
+
+    if existing.kind != export.kind {
+        existing.kind = types::ExportKind::Both;
+    }
+    if existing.exported_name.is_none() {
+        existing.exported_name = export.exported_name;
+    }
+    existing.conditional |= export.conditional;
+}
 
-            return Err(format!(
-                "Error: {} with code {}",
-                error.message, source_code_error
-            ));
-        }
+impl ExportedComponent {
+    /// Identifier that stays the same across edits as long as the component is exported the same
+    /// way, so hosts can correlate components between analyses without relying on ranges
+    fn id(&self) -> String {
+        let (kind, public_name) = match self.kind {
+            // Candidates share the default export, so tell them apart by name
+            types::ExportKind::Default if self.conditional => ("conditional", self.name.as_str()),
+            // Members of the default export are reached by their key
+            types::ExportKind::Default if self.grouped => ("grouped", self.public_name()),
+            types::ExportKind::Default => ("default", "default"),
+            types::ExportKind::Named => ("named", self.public_name()),
+            types::ExportKind::Both => ("both", self.public_name()),
+        };
+        // Subcomponents share their parent's export, so add the member they are reached through
+        let public_name = match self.name.split_once('.') {
+            Some((_, member)) if self.kind == types::ExportKind::Default => {
+                format!("{}.{}", public_name, member)
+            }
+            _ => public_name.to_string(),
+        };
+        format!(
+            "{:016x}",
+            fnv1a_hash(format!("{}:{}", kind, public_name).as_bytes())
+        )
+    }
 
-        let program = ret.program;
+    /// Name other modules import the component by
+    fn public_name(&self) -> &str {
+        self.exported_name.as_deref().unwrap_or(&self.name)
+    }
+}
 
-        let has_use_client_directive = program
-            .directives
-            .iter()
-            .any(|directive| directive.directive == "use client");
+/// 64-bit FNV-1a hash, used instead of `DefaultHasher` because its output must not change
+/// between Rust versions
+fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
 
-        let imports = program
-            .body
-            .iter()
-            .filter_map(|statement| {
-                if let Statement::ImportDeclaration(import_declaration) = statement {
-                    // We can just ignore type imports as it doesn't have a runtime impact
-                    if import_declaration.import_kind == ImportOrExportKind::Type {
-                        return None;
-                    }
-                    Some(types::ImportAnalysis {
-                        identifier: import_declaration
-                            .specifiers
-                            .iter()
-                            .flat_map(|specifier| {
-                                specifier
-                                    .into_iter()
-                                    .filter_map(|specifier| match specifier {
-                                        ImportDeclarationSpecifier::ImportSpecifier(spec) => {
-                                            if spec.import_kind == ImportOrExportKind::Type {
-                                                return None;
-                                            }
-                                            Some(spec.local.name.clone().to_string())
-                                        }
-                                        ImportDeclarationSpecifier::ImportDefaultSpecifier(
-                                            spec,
-                                        ) => Some(spec.local.name.clone().to_string()),
-                                        ImportDeclarationSpecifier::ImportNamespaceSpecifier(
-                                            spec,
-                                        ) => Some(spec.local.name.clone().to_string()),
-                                    })
-                                    .collect::<Vec<_>>()
-                            })
-                            .collect::<Vec<_>>(),
-                        source: import_declaration.source.value.clone().to_string(),
-                        source_span: range::string_literal_to_range(
-                            &source_text,
-                            import_declaration.source.span,
-                        ),
-                    })
-                } else {
-                    None
+/// Whether a PascalCase variable is initialized with an application of a local HOC
+/// (`const Protected = withAuth(Dashboard)`)
+fn is_hoc_component(name: &str, init: &Option<Expression>, local_hocs: &HashSet<String>) -> bool {
+    name.starts_with(|c: char| c.is_uppercase())
+        && init
+            .as_ref()
+            .is_some_and(|init| component::hoc_application(init, local_hocs).is_some())
+}
+
+/// Top-level statements, along with the statements of top-level blocks, `if` statements and
+/// `try` statements, where transpiled interop code puts its exports
+/// (`if (typeof module !== "undefined") { ... }`)
+fn interop_statements<'s, 'a>(statements: &'s [Statement<'a>]) -> Vec<&'s Statement<'a>> {
+    fn visit<'s, 'a>(statement: &'s Statement<'a>, flattened: &mut Vec<&'s Statement<'a>>) {
+        match statement {
+            Statement::BlockStatement(block) => {
+                block.body.iter().for_each(|inner| visit(inner, flattened));
+            }
+            Statement::IfStatement(if_stmt) => {
+                visit(&if_stmt.consequent, flattened);
+                if let Some(alternate) = &if_stmt.alternate {
+                    visit(alternate, flattened);
+                }
+            }
+            Statement::TryStatement(try_stmt) => {
+                let handler = try_stmt.handler.as_ref().map(|handler| &handler.body);
+                for block in std::iter::once(&try_stmt.block)
+                    .chain(handler)
+                    .chain(try_stmt.finalizer.as_ref())
+                {
+                    block.body.iter().for_each(|inner| visit(inner, flattened));
                 }
+            }
+            statement => flattened.push(statement),
+        }
+    }
+
+    let mut flattened = Vec::new();
+    statements
+        .iter()
+        .for_each(|statement| visit(statement, &mut flattened));
+    flattened
+}
+
+/// Initializer of a top-level `const` binding declared as an object literal
+fn object_initializer<'s, 'a>(
+    statements: &'s [Statement<'a>],
+    name: &str,
+) -> Option<&'s ObjectExpression<'a>> {
+    statements.iter().find_map(|statement| {
+        let declaration = match statement {
+            Statement::ExportNamedDeclaration(export_decl) => export_decl.declaration.as_ref(),
+            statement => statement.as_declaration(),
+        };
+        let Some(Declaration::VariableDeclaration(var_decl)) = declaration else {
+            return None;
+        };
+        if !var_decl.kind.is_const() {
+            return None;
+        }
+        var_decl.declarations.iter().find_map(|declarator| {
+            match (
+                &declarator.id.kind,
+                declarator.init.as_ref()?.get_inner_expression(),
+            ) {
+                (
+                    BindingPatternKind::BindingIdentifier(ident),
+                    Expression::ObjectExpression(object),
+                ) if ident.name == name => Some(&**object),
+                _ => None,
+            }
+        })
+    })
+}
+
+/// Components exported as properties of the default-exported object, by shorthand (`{ Button }`)
+/// or under a key of their own (`{ Primary: Button }`). Other properties are skipped
+fn grouped_exports(
+    object: &ObjectExpression,
+    component_declarations: &HashMap<String, Span>,
+    export_span: Span,
+) -> Vec<ExportedComponent> {
+    object
+        .properties
+        .iter()
+        .filter_map(|property| {
+            let ObjectPropertyKind::ObjectProperty(property) = property else {
+                return None;
+            };
+            let key = match &property.key {
+                PropertyKey::StaticIdentifier(key) => key.name.to_string(),
+                PropertyKey::StringLiteral(key) => key.value.to_string(),
+                _ => return None,
+            };
+            let Expression::Identifier(value) = property.value.get_inner_expression() else {
+                return None;
+            };
+            let span = *component_declarations.get(value.name.as_str())?;
+            Some(ExportedComponent {
+                name: value.name.to_string(),
+                span,
+                kind: types::ExportKind::Default,
+                exported_name: (key != value.name.as_str()).then_some(key),
+                export_span,
+                conditional: false,
+                grouped: true,
             })
-            .collect::<Vec<_>>();
+        })
+        .collect()
+}
+
+/// Get the string name of an import/export specifier name
+fn module_export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::IdentifierName(ident) => ident.name.to_string(),
+        ModuleExportName::IdentifierReference(ident) => ident.name.to_string(),
+        ModuleExportName::StringLiteral(lit) => lit.value.to_string(),
+    }
+}
+
+/// Names a top-level statement binds, imports aside, with the span of the declaration binding each
+fn top_level_bindings<'s>(statement: &'s Statement) -> Vec<(&'s str, Span)> {
+    let declaration = match statement {
+        Statement::ExportNamedDeclaration(export_decl) => export_decl.declaration.as_ref(),
+        Statement::ExportDefaultDeclaration(export_decl) => {
+            return match &export_decl.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => func
+                    .id
+                    .iter()
+                    .map(|id| (id.name.as_str(), func.span))
+                    .collect(),
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => class
+                    .id
+                    .iter()
+                    .map(|id| (id.name.as_str(), class.span))
+                    .collect(),
+                _ => Vec::new(),
+            };
+        }
+        statement => statement.as_declaration(),
+    };
 
-        // Collect jsx runtime identifiers (functions imported from "react/jsx-runtime")
-        // These can be renamed: import { jsx as foobar } from "react/jsx-runtime"
-        let jsx_runtime_identifiers: HashSet<String> = imports
+    match declaration {
+        Some(Declaration::VariableDeclaration(var_decl)) => var_decl
+            .declarations
             .iter()
-            .filter(|import| import.source == "react/jsx-runtime")
-            .flat_map(|import| import.identifier.iter().cloned())
-            .collect();
+            .flat_map(|declarator| {
+                declarator
+                    .id
+                    .get_binding_identifiers()
+                    .into_iter()
+                    .map(|ident| (ident.name.as_str(), declarator.span))
+            })
+            .collect(),
+        Some(Declaration::FunctionDeclaration(func)) => func
+            .id
+            .iter()
+            .map(|id| (id.name.as_str(), func.span))
+            .collect(),
+        Some(Declaration::ClassDeclaration(class)) => class
+            .id
+            .iter()
+            .map(|id| (id.name.as_str(), class.span))
+            .collect(),
+        Some(Declaration::TSInterfaceDeclaration(interface)) => {
+            vec![(interface.id.name.as_str(), interface.span)]
+        }
+        Some(Declaration::TSTypeAliasDeclaration(alias)) => {
+            vec![(alias.id.name.as_str(), alias.span)]
+        }
+        Some(Declaration::TSEnumDeclaration(enum_decl)) => {
+            vec![(enum_decl.id.name.as_str(), enum_decl.span)]
+        }
+        _ => Vec::new(),
+    }
+}
+
+impl Guest for AnalyzeReactBoundary {
+    fn initialize() {
+        session::initialize();
+    }
+
+    fn analyze(content: Vec<u8>, extension: String) -> Result<AnalysisResult, String> {
+        analyze_source(content, extension, &host_cancelled)
+    }
+
+    fn analyze_with_options(
+        content: Vec<u8>,
+        extension: String,
+        options: AnalyzeOptions,
+    ) -> Result<AnalysisResult, String> {
+        analyze_source_with_options(content, extension, &options, &host_cancelled)
+    }
+
+    fn analyze_batch(files: Vec<SourceFile>) -> Vec<FileAnalysis> {
+        batch::analyze_batch(files, &host_cancelled, &host_report_progress)
+    }
+
+    fn scan_directives(content: Vec<u8>) -> DirectiveScan {
+        prologue::scan_directives(&content)
+    }
+
+    fn classify_module(
+        content: Vec<u8>,
+        extension: String,
+    ) -> Result<ModuleClassification, String> {
+        classify::classify_module(content, &extension)
+    }
+
+    fn analyze_batch_with_options(
+        files: Vec<SourceFile>,
+        options: AnalyzeOptions,
+    ) -> Vec<FileAnalysis> {
+        batch::analyze_batch_with_options(files, &options, &host_cancelled, &host_report_progress)
+    }
+
+    fn analyze_batch_interned(files: Vec<SourceFile>) -> InternedBatch {
+        intern::intern_batch(batch::analyze_batch(
+            files,
+            &host_cancelled,
+            &host_report_progress,
+        ))
+    }
+
+    fn partition_batch(sizes: Vec<u64>, workers: u32) -> Vec<Vec<u32>> {
+        batch::partition_batch(&sizes, workers)
+    }
+
+    fn analyze_cached(content: Vec<u8>, extension: String) -> Result<AnalysisResult, String> {
+        session::analyze_cached(content, extension, &host_cancelled)
+    }
+
+    fn dump_cache() -> CacheSnapshot {
+        session::dump_cache()
+    }
+
+    fn restore_cache(snapshot: CacheSnapshot) -> bool {
+        session::restore_cache(snapshot)
+    }
+
+    fn clear_cache() {
+        session::clear_cache()
+    }
+
+    fn analyze_with_imports(
+        path: String,
+        content: Vec<u8>,
+        extension: String,
+    ) -> Result<CrossFileAnalysis, String> {
+        vfs::analyze_with_imports(
+            &path,
+            content,
+            extension,
+            &vfs::HostFileSystem,
+            &host_cancelled,
+        )
+    }
+
+    fn check_boundaries(files: Vec<SourceFile>) -> Vec<BoundaryViolation> {
+        graph::check_boundaries(files, &host_cancelled)
+    }
+
+    fn summarize(files: Vec<SourceFile>) -> ProjectSummary {
+        summary::summarize(files, &host_cancelled)
+    }
+
+    fn find_unused_components(files: Vec<SourceFile>) -> Vec<UnusedComponent> {
+        unused::find_unused_components(files, &host_cancelled)
+    }
+
+    fn render_graph(files: Vec<SourceFile>, format: GraphFormat) -> String {
+        diagram::render_graph(files, format)
+    }
+
+    fn check_entry(
+        path: String,
+        content: Vec<u8>,
+        extension: String,
+        rules: Vec<RuleSetting>,
+    ) -> Result<EntryCheck, String> {
+        entry::check_entry(&path, content, extension, &rules, &host_cancelled)
+    }
+
+    fn client_references(
+        path: String,
+        content: Vec<u8>,
+        extension: String,
+    ) -> Result<Vec<ClientReference>, String> {
+        reference::client_references(&path, content, extension, &host_cancelled)
+    }
+
+    fn resolve_package_export(
+        package_json: String,
+        subpath: String,
+        conditions: Vec<String>,
+    ) -> Result<ExportResolution, String> {
+        package::resolve_package_export(&package_json, &subpath, &conditions)
+    }
+
+    fn validate_rules(rules: Vec<RuleSetting>) -> Vec<String> {
+        rules::validate_rules(&rules)
+    }
+
+    fn to_lsp_location(location: FileLocation) -> LspLocation {
+        location::to_lsp_location(location)
+    }
+
+    fn position_at(content: String, offset: u32, encoding: PositionEncoding) -> Position {
+        range::position_at(&content, offset, encoding)
+    }
+
+    fn offset_at(content: String, position: Position, encoding: PositionEncoding) -> u32 {
+        range::offset_at(&content, position, encoding)
+    }
+
+    fn dynamic_import_fix(
+        content: Vec<u8>,
+        extension: String,
+        import_range: Range,
+    ) -> Result<Vec<TextEdit>, String> {
+        fix::dynamic_import_fix(content, extension, import_range)
+    }
+
+    fn extract_client_island(
+        path: String,
+        content: Vec<u8>,
+        extension: String,
+        component: String,
+    ) -> Result<WorkspaceEdit, String> {
+        fix::extract_client_island(&path, content, extension, &component)
+    }
+
+    fn resolve_path_alias(
+        paths: Vec<PathAlias>,
+        base_url: Option<String>,
+        specifier: String,
+    ) -> Vec<String> {
+        resolve::resolve_path_alias(&paths, base_url.as_deref(), &specifier)
+    }
+}
+
+/// Poll the host to check whether it abandoned the analysis (e.g. the user switched files)
+fn host_cancelled() -> bool {
+    #[cfg(target_arch = "wasm32")]
+    return is_cancelled();
+    #[cfg(not(target_arch = "wasm32"))]
+    false
+}
+
+/// Tell the host how far a batch got
+fn host_report_progress(done: u32, total: u32, current_path: &str) {
+    #[cfg(target_arch = "wasm32")]
+    report_progress(done, total, current_path);
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = (done, total, current_path);
+}
+
+/// Return early if the host asked to stop. Checked at pass boundaries, so a cancelled analysis of
+/// a huge bundle stops after the current pass instead of running to completion
+fn check_cancelled(is_cancelled: &dyn Fn() -> bool) -> Result<(), String> {
+    if is_cancelled() {
+        return Err("Analysis cancelled".to_string());
+    }
+    Ok(())
+}
+
+fn analyze_source(
+    content: Vec<u8>,
+    extension: String,
+    is_cancelled: &dyn Fn() -> bool,
+) -> Result<AnalysisResult, String> {
+    analyze_source_with_options(content, extension, &default_options(), is_cancelled)
+}
+
+/// Options used by the exports that don't take any
+fn default_options() -> AnalyzeOptions {
+    AnalyzeOptions {
+        include_local_jsx_usages: false,
+        byte_spans: false,
+        one_based_positions: false,
+        memory_limit: None,
+        include_statistics: false,
+        client_packages: Vec::new(),
+        server_packages: Vec::new(),
+        timeout: None,
+        rules: Vec::new(),
+        component_types: Vec::new(),
+        include_type_exports: false,
+        group_default_exports: true,
+        include_debug_usages: false,
+        loose_components: false,
+    }
+}
 
-        // Track all React component declarations with their spans
-        let mut component_declarations: HashMap<String, Span> = HashMap::new();
+fn analyze_source_with_options(
+    content: Vec<u8>,
+    extension: String,
+    options: &AnalyzeOptions,
+    is_cancelled: &dyn Fn() -> bool,
+) -> Result<AnalysisResult, String> {
+    let budget = memory::MemoryBudget::start(options.memory_limit);
+    budget.check_source(content.len())?;
+    let deadline = deadline::Deadline::start(options.timeout);
+
+    let source_text =
+        String::from_utf8(content).map_err(|e| format!("Invalid UTF-8 source: {}", e))?;
+    let mut source_type = match SourceType::from_extension(&extension) {
+        Ok(source_type) => source_type,
+        // The component script of an Astro component is TypeScript
+        #[cfg(feature = "astro")]
+        Err(_) if extension == astro::ASTRO_EXTENSION => SourceType::ts(),
+        Err(e) => {
+            return Err(format!(
+                "{}: {}",
+                e.to_string().replace("\"", ""),
+                extension
+            ));
+        }
+    };
+
+    // oxc rejects Flow files outright, so strip the Flow-only syntax and parse the rest as TSX,
+    // which covers the type annotations Flow shares with TypeScript. Offsets are preserved, so
+    // ranges are still computed against the original source
+    let flow_source = flow::has_flow_pragma(&source_text).then(|| {
+        source_type = SourceType::tsx();
+        flow::strip_flow_syntax(&source_text)
+    });
+    // Astro components are parsed through their component script, with the template blanked out
+    // the same way
+    #[cfg(feature = "astro")]
+    let astro_source =
+        (extension == astro::ASTRO_EXTENSION).then(|| astro::frontmatter_source(&source_text));
+    #[cfg(not(feature = "astro"))]
+    let astro_source: Option<String> = None;
+    let parse_source = flow_source
+        .as_deref()
+        .or(astro_source.as_deref())
+        .unwrap_or(&source_text);
+
+    check_cancelled(is_cancelled)?;
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, parse_source, source_type)
+        .with_options(ParseOptions {
+            #[cfg(feature = "regular-expression")]
+            parse_regular_expression: true,
+            ..ParseOptions::default()
+        })
+        .parse();
+
+    if ret.panicked
+        && let Some(error) = ret.errors.first().cloned()
+    {
+        #[cfg(feature = "fancy-errors")]
+        let message = format!(
+            "Error: {} with code {}",
+            error.message,
+            error.clone().with_source_code(source_text.clone())
+        );
+        #[cfg(not(feature = "fancy-errors"))]
+        let message = format!("Error: {}", error.message);
+        #[cfg(target_arch = "wasm32")]
+        log(&message);
 
-        // First pass: identify all React component variable declarations and function declarations
-        for statement in program.body.iter() {
-            match statement {
-                Statement::VariableDeclaration(var_decl) => {
-                    for declarator in var_decl.declarations.iter() {
-                        if let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind {
-                            let name = ident.name.to_string();
+        return Err(message);
+    }
 
-                            // This now handles both JSX syntax and jsx/jsxs runtime calls
-                            let is_component = component::is_react_component(
+    check_cancelled(is_cancelled)?;
+    budget.check()?;
+
+    let ranges = range::RangeConverter::new(
+        &source_text,
+        options.byte_spans,
+        options.one_based_positions,
+    );
+
+    // Recoverable errors (e.g. a file mid-edit) still leave a usable AST, so every pass runs over
+    // what parsed and the errors are reported alongside the partial result
+    let parse_errors = ret
+        .errors
+        .iter()
+        .map(|error| types::ParseError {
+            message: error.message.to_string(),
+            range: error
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.first())
+                .map(|label| {
+                    let start = label.offset() as u32;
+                    ranges.span(Span::new(start, start + label.len() as u32))
+                }),
+        })
+        .collect::<Vec<_>>();
+
+    let program = ret.program;
+
+    let use_client_directive = program
+        .directives
+        .iter()
+        .find(|directive| directive.directive == "use client");
+    let has_use_client_directive = use_client_directive.is_some();
+
+    let imports = program
+        .body
+        .iter()
+        .filter_map(|statement| {
+            if let Statement::ImportDeclaration(import_declaration) = statement {
+                // We can just ignore type imports as it doesn't have a runtime impact
+                if import_declaration.import_kind == ImportOrExportKind::Type {
+                    return None;
+                }
+                let (identifier, specifier_spans): (Vec<_>, Vec<_>) = import_declaration
+                    .specifiers
+                    .iter()
+                    .flatten()
+                    .filter_map(|specifier| match specifier {
+                        ImportDeclarationSpecifier::ImportSpecifier(spec) => {
+                            if spec.import_kind == ImportOrExportKind::Type {
+                                return None;
+                            }
+                            Some((spec.local.name.to_string(), spec.span))
+                        }
+                        ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                            Some((spec.local.name.to_string(), spec.span))
+                        }
+                        ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
+                            Some((spec.local.name.to_string(), spec.span))
+                        }
+                    })
+                    .unzip();
+                Some(types::ImportAnalysis {
+                    identifier,
+                    asset: asset::asset_kind(&import_declaration.source.value),
+                    source: import_declaration.source.value.clone().to_string(),
+                    source_span: ranges.string_literal(import_declaration.source.span),
+                    range: ranges.span(import_declaration.span),
+                    specifier_ranges: specifier_spans
+                        .into_iter()
+                        .map(|span| ranges.span(span))
+                        .collect(),
+                })
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Collect jsx runtime identifiers (functions imported from "react/jsx-runtime")
+    // These can be renamed: import { jsx as foobar } from "react/jsx-runtime"
+    let jsx_runtime_identifiers: HashSet<String> = imports
+        .iter()
+        .filter(|import| import.source == "react/jsx-runtime")
+        .flat_map(|import| import.identifier.iter().cloned())
+        .collect();
+
+    check_cancelled(is_cancelled)?;
+    budget.check()?;
+
+    // Track all React component declarations with their spans
+    let mut component_declarations: HashMap<String, Span> = HashMap::new();
+
+    // Higher-order components defined in this module, whose applications are components too
+    let local_hocs = component::collect_local_hocs(&program.body, &jsx_runtime_identifiers);
+
+    // First pass: identify all React component variable declarations and function declarations,
+    // exported or not, so exports resolve whichever way around the declaration and the export
+    // statement come (`export default App; function App() {}`)
+    let mut uninitialized: HashMap<&str, Span> = HashMap::new();
+    for statement in program.body.iter() {
+        let declaration = match statement {
+            Statement::ExportNamedDeclaration(export_decl) => export_decl.declaration.as_ref(),
+            Statement::ExportDefaultDeclaration(export_decl) => {
+                let component = match &export_decl.declaration {
+                    ExportDefaultDeclarationKind::FunctionDeclaration(func_decl) => {
+                        component::analyze_function_declaration(
+                            func_decl,
+                            &jsx_runtime_identifiers,
+                            &options.component_types,
+                        )
+                    }
+                    ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                        component::analyze_class_declaration(class)
+                    }
+                    _ => None,
+                };
+                if let Some((name, span)) = component {
+                    component_declarations.insert(name, span);
+                }
+                continue;
+            }
+            statement => statement.as_declaration(),
+        };
+        match declaration {
+            Some(Declaration::VariableDeclaration(var_decl)) => {
+                for declarator in var_decl.declarations.iter() {
+                    if let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind {
+                        let name = ident.name.to_string();
+
+                        // This now handles both JSX syntax and jsx/jsxs runtime calls
+                        let is_component =
+                            component::is_react_component(
                                 &name,
                                 &declarator.id,
                                 &declarator.init,
                                 &jsx_runtime_identifiers,
-                            );
+                                &options.component_types,
+                            ) || is_hoc_component(&name, &declarator.init, &local_hocs);
 
-                            if is_component {
-                                component_declarations.insert(name, ident.span);
-                            }
+                        if is_component {
+                            component_declarations.insert(name, ident.span);
+                        } else if declarator.init.is_none() && !var_decl.kind.is_const() {
+                            uninitialized.insert(ident.name.as_str(), ident.span);
                         }
                     }
                 }
-                Statement::FunctionDeclaration(func_decl) => {
-                    if let Some(id) = &func_decl.id {
-                        let name = id.name.to_string();
-
-                        // Check if this is a React function component
-                        if component::is_react_function_component(
-                            &name,
-                            &func_decl.return_type,
-                            &func_decl.body,
-                            &jsx_runtime_identifiers,
-                        ) {
-                            component_declarations.insert(name, id.span);
-                        }
-                    }
+            }
+            Some(Declaration::FunctionDeclaration(func_decl)) => {
+                if let Some((name, span)) = component::analyze_function_declaration(
+                    func_decl,
+                    &jsx_runtime_identifiers,
+                    &options.component_types,
+                ) {
+                    component_declarations.insert(name, span);
+                }
+            }
+            Some(Declaration::ClassDeclaration(class)) => {
+                if let Some((name, span)) = component::analyze_class_declaration(class) {
+                    component_declarations.insert(name, span);
                 }
-                _ => {}
             }
+            _ => {}
         }
+    }
 
-        // Second pass: extract exported component names with their spans
-        let mut exported_components: Vec<(String, Span)> = Vec::new();
-
-        // Helper function to register a component as exported
-        fn register_component(
-            name: String,
-            span: Span,
-            exported_components: &mut Vec<(String, Span)>,
-            component_declarations: &mut HashMap<String, Span>,
-        ) {
-            exported_components.push((name.clone(), span));
-            component_declarations.insert(name, span);
+    // Bindings declared without a value and assigned a component later, as compiled output
+    // hoists them (`var App; ... App = function () { return <div /> }`)
+    for statement in program.body.iter() {
+        if let Statement::ExpressionStatement(expr_stmt) = statement
+            && let Expression::AssignmentExpression(assign) = &expr_stmt.expression
+            && assign.operator == AssignmentOperator::Assign
+            && let AssignmentTarget::AssignmentTargetIdentifier(target) = &assign.left
+            && let Some(&span) = uninitialized.get(target.name.as_str())
+            && component::is_react_component_assignment(
+                &target.name,
+                &assign.right,
+                &jsx_runtime_identifiers,
+            )
+        {
+            component_declarations
+                .entry(target.name.to_string())
+                .or_insert(span);
         }
+    }
 
-        // Parse __export() calls to extract exports (common in bundled/compiled code)
-        for statement in program.body.iter() {
-            if let Statement::ExpressionStatement(expr_stmt) = statement
-                && let Expression::CallExpression(call_expr) = &expr_stmt.expression
-                && let Expression::Identifier(callee) = &call_expr.callee
-                && callee.name == "__export"
-                && call_expr.arguments.len() >= 2
-                && let Some(second_arg) = call_expr.arguments.get(1)
-                && let Some(expr) = second_arg.as_expression()
-                && let Expression::ObjectExpression(obj_expr) = expr
-            {
-                // Extract export names from object properties
-                for property in obj_expr.properties.iter() {
-                    if let ObjectPropertyKind::ObjectProperty(prop) = property
-                        && let PropertyKey::StaticIdentifier(key) = &prop.key
-                    {
-                        let export_name = key.name.to_string();
+    // Declarations annotated as client components count as components even when the heuristics
+    // miss them
+    let annotations =
+        annotation::collect_annotations(parse_source, &program.comments, &program.body);
+    for annotation in annotations.iter() {
+        if annotation.kind == types::BoundaryAnnotation::Client {
+            component_declarations
+                .entry(annotation.name.clone())
+                .or_insert(annotation.span);
+        }
+    }
 
-                        // Check if this export is a component we detected
-                        if let Some(&span) = component_declarations.get(&export_name) {
-                            exported_components.push((export_name, span));
-                        }
+    // Second pass: extract exported components with their spans and how they are exported
+    let mut exported_components: Vec<ExportedComponent> = Vec::new();
+
+    // Parse __export() calls to extract exports (common in bundled/compiled code), also inside
+    // the conditional and `try` blocks of interop code
+    for statement in interop_statements(&program.body) {
+        if let Statement::ExpressionStatement(expr_stmt) = statement
+            && let Expression::CallExpression(call_expr) = &expr_stmt.expression
+            && let Expression::Identifier(callee) = &call_expr.callee
+            && callee.name == "__export"
+            && call_expr.arguments.len() >= 2
+            && let Some(second_arg) = call_expr.arguments.get(1)
+            && let Some(expr) = second_arg.as_expression()
+            && let Expression::ObjectExpression(obj_expr) = expr
+        {
+            // Extract export names from object properties
+            for property in obj_expr.properties.iter() {
+                if let ObjectPropertyKind::ObjectProperty(prop) = property
+                    && let PropertyKey::StaticIdentifier(key) = &prop.key
+                {
+                    let export_name = key.name.to_string();
+
+                    // Check if this export is a component we detected
+                    if let Some(&span) = component_declarations.get(&export_name) {
+                        let kind = if export_name == "default" {
+                            types::ExportKind::Default
+                        } else {
+                            types::ExportKind::Named
+                        };
+                        register_export(
+                            &mut exported_components,
+                            ExportedComponent {
+                                name: export_name,
+                                span,
+                                kind,
+                                exported_name: None,
+                                export_span: expr_stmt.span,
+                                conditional: false,
+                                grouped: false,
+                            },
+                        );
                     }
                 }
             }
         }
+    }
 
-        for statement in program.body.iter() {
-            match statement {
-                // Handle default exports: export default ComponentName
-                Statement::ExportDefaultDeclaration(export_decl) => {
-                    match &export_decl.declaration {
-                        ExportDefaultDeclarationKind::Identifier(ident) => {
-                            let name = ident.name.to_string();
-                            if let Some(&span) = component_declarations.get(&name) {
-                                exported_components.push((name, span));
-                            }
-                        }
-                        // Handle inline function declaration: export default function MyComponent() {}
-                        ExportDefaultDeclarationKind::FunctionDeclaration(func_decl) => {
-                            if let Some((name, span)) = component::analyze_function_declaration(
-                                func_decl,
-                                &jsx_runtime_identifiers,
-                            ) {
-                                register_component(
+    for statement in program.body.iter() {
+        match statement {
+            // Handle default exports: export default ComponentName
+            Statement::ExportDefaultDeclaration(export_decl) => {
+                match &export_decl.declaration {
+                    ExportDefaultDeclarationKind::Identifier(ident) => {
+                        let name = ident.name.to_string();
+                        if let Some(&span) = component_declarations.get(&name) {
+                            register_export(
+                                &mut exported_components,
+                                ExportedComponent {
                                     name,
                                     span,
+                                    kind: types::ExportKind::Default,
+                                    exported_name: None,
+                                    export_span: export_decl.span,
+                                    conditional: false,
+                                    grouped: false,
+                                },
+                            );
+                        } else if options.group_default_exports
+                            && let Some(object) = object_initializer(&program.body, &name)
+                        {
+                            // Handle an object of components declared first: export default ui
+                            for export in
+                                grouped_exports(object, &component_declarations, export_decl.span)
+                            {
+                                register_export(&mut exported_components, export);
+                            }
+                        }
+                    }
+                    // Handle an object of components: export default { Button, Link }
+                    ExportDefaultDeclarationKind::ObjectExpression(object)
+                        if options.group_default_exports =>
+                    {
+                        for export in
+                            grouped_exports(object, &component_declarations, export_decl.span)
+                        {
+                            register_export(&mut exported_components, export);
+                        }
+                    }
+                    // Handle conditional selection: export default isTouch ? MobileNav : DesktopNav
+                    ExportDefaultDeclarationKind::ConditionalExpression(_)
+                    | ExportDefaultDeclarationKind::LogicalExpression(_) => {
+                        let mut candidates = Vec::new();
+                        if let Some(expr) = export_decl.declaration.as_expression() {
+                            component::conditional_candidates(expr, &mut candidates);
+                        }
+                        for name in candidates {
+                            if let Some(&span) = component_declarations.get(&name) {
+                                register_export(
                                     &mut exported_components,
-                                    &mut component_declarations,
+                                    ExportedComponent {
+                                        name,
+                                        span,
+                                        kind: types::ExportKind::Default,
+                                        exported_name: None,
+                                        export_span: export_decl.span,
+                                        conditional: true,
+                                        grouped: false,
+                                    },
                                 );
                             }
                         }
-                        _ => {}
                     }
-                }
-                // Handle named exports: export const ComponentName = ... or export function ComponentName() {}
-                Statement::ExportNamedDeclaration(export_decl) => {
-                    if let Some(declaration) = &export_decl.declaration {
-                        match declaration {
-                            Declaration::VariableDeclaration(var_decl) => {
-                                for declarator in var_decl.declarations.iter() {
-                                    if let BindingPatternKind::BindingIdentifier(ident) =
-                                        &declarator.id.kind
-                                    {
-                                        let name = ident.name.to_string();
-
-                                        // Check if this is a React component
-                                        if component::is_react_component(
-                                            &name,
-                                            &declarator.id,
-                                            &declarator.init,
-                                            &jsx_runtime_identifiers,
-                                        ) {
-                                            register_component(
-                                                name,
-                                                ident.span,
-                                                &mut exported_components,
-                                                &mut component_declarations,
-                                            );
-                                        }
-                                    }
+                    // Handle a local HOC applied in place: export default withAuth(Dashboard)
+                    ExportDefaultDeclarationKind::CallExpression(call) => {
+                        if let Some(expr) = export_decl.declaration.as_expression()
+                            && let Some(wrapped) = component::hoc_application(expr, &local_hocs)
+                        {
+                            register_export(
+                                &mut exported_components,
+                                ExportedComponent {
+                                    name: wrapped.name.to_string(),
+                                    span: call.span,
+                                    kind: types::ExportKind::Default,
+                                    exported_name: None,
+                                    export_span: export_decl.span,
+                                    conditional: false,
+                                    grouped: false,
+                                },
+                            );
+                        }
+                    }
+                    // Handle inline class declaration: export default class MyComponent extends Component {}
+                    ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                        if let Some((name, span)) = component::analyze_class_declaration(class) {
+                            component_declarations.insert(name.clone(), span);
+                            register_export(
+                                &mut exported_components,
+                                ExportedComponent {
+                                    name,
+                                    span,
+                                    kind: types::ExportKind::Default,
+                                    exported_name: None,
+                                    export_span: export_decl.span,
+                                    conditional: false,
+                                    grouped: false,
+                                },
+                            );
+                        }
+                    }
+                    // Handle inline function declaration: export default function MyComponent() {}
+                    ExportDefaultDeclarationKind::FunctionDeclaration(func_decl) => {
+                        if let Some((name, span)) = component::analyze_function_declaration(
+                            func_decl,
+                            &jsx_runtime_identifiers,
+                            &options.component_types,
+                        ) {
+                            component_declarations.insert(name.clone(), span);
+                            register_export(
+                                &mut exported_components,
+                                ExportedComponent {
+                                    name,
+                                    span,
+                                    kind: types::ExportKind::Default,
+                                    exported_name: None,
+                                    export_span: export_decl.span,
+                                    conditional: false,
+                                    grouped: false,
+                                },
+                            );
+                        }
+                    }
+                    // Handle wrapped forms of the above, as emitted by compilers:
+                    // export default (function Page() {}) or export default (0, Page)
+                    ExportDefaultDeclarationKind::ParenthesizedExpression(_)
+                    | ExportDefaultDeclarationKind::SequenceExpression(_) => {
+                        let Some(expr) = export_decl.declaration.as_expression() else {
+                            continue;
+                        };
+                        let mut exported = Vec::new();
+                        match component::default_export_value(expr) {
+                            Expression::Identifier(ident) => {
+                                if let Some(&span) = component_declarations.get(ident.name.as_str())
+                                {
+                                    exported.push((ident.name.to_string(), span, false));
                                 }
                             }
-                            Declaration::FunctionDeclaration(func_decl) => {
+                            Expression::FunctionExpression(func) => {
                                 if let Some((name, span)) = component::analyze_function_declaration(
-                                    func_decl,
+                                    func,
                                     &jsx_runtime_identifiers,
+                                    &options.component_types,
                                 ) {
-                                    register_component(
-                                        name,
-                                        span,
-                                        &mut exported_components,
-                                        &mut component_declarations,
-                                    );
+                                    component_declarations.insert(name.clone(), span);
+                                    exported.push((name, span, false));
+                                }
+                            }
+                            Expression::ClassExpression(class) => {
+                                if let Some((name, span)) =
+                                    component::analyze_class_declaration(class)
+                                {
+                                    component_declarations.insert(name.clone(), span);
+                                    exported.push((name, span, false));
+                                }
+                            }
+                            value @ Expression::CallExpression(call) => {
+                                if let Some(wrapped) =
+                                    component::hoc_application(value, &local_hocs)
+                                {
+                                    exported.push((wrapped.name.to_string(), call.span, false));
+                                }
+                            }
+                            value @ (Expression::ConditionalExpression(_)
+                            | Expression::LogicalExpression(_)) => {
+                                let mut candidates = Vec::new();
+                                component::conditional_candidates(value, &mut candidates);
+                                for name in candidates {
+                                    if let Some(&span) = component_declarations.get(&name) {
+                                        exported.push((name, span, true));
+                                    }
                                 }
                             }
                             _ => {}
                         }
-                    } else if !export_decl.specifiers.is_empty() {
-                        // Handle export { ComponentName } (re-export of already declared variable)
-                        use oxc::ast::ast::ModuleExportName;
-                        for specifier in export_decl.specifiers.iter() {
-                            // Get the exported name from the specifier
-                            let exported_name = match &specifier.exported {
-                                ModuleExportName::IdentifierName(ident) => ident.name.to_string(),
-                                ModuleExportName::IdentifierReference(ident) => {
-                                    ident.name.to_string()
+                        for (name, span, conditional) in exported {
+                            register_export(
+                                &mut exported_components,
+                                ExportedComponent {
+                                    name,
+                                    span,
+                                    kind: types::ExportKind::Default,
+                                    exported_name: None,
+                                    export_span: export_decl.span,
+                                    conditional,
+                                    grouped: false,
+                                },
+                            );
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            // Handle named exports: export const ComponentName = ... or export function ComponentName() {}
+            Statement::ExportNamedDeclaration(export_decl) => {
+                if let Some(declaration) = &export_decl.declaration {
+                    let mut declared: Vec<(String, Span)> = Vec::new();
+                    // Render functions of exported lookup tables, as `COMPONENTS.home`
+                    let mut entries: Vec<(String, Span)> = Vec::new();
+                    match declaration {
+                        Declaration::VariableDeclaration(var_decl) => {
+                            for declarator in var_decl.declarations.iter() {
+                                if let BindingPatternKind::BindingIdentifier(ident) =
+                                    &declarator.id.kind
+                                {
+                                    let name = ident.name.to_string();
+
+                                    // Check if this is a React component
+                                    if component::is_react_component(
+                                        &name,
+                                        &declarator.id,
+                                        &declarator.init,
+                                        &jsx_runtime_identifiers,
+                                        &options.component_types,
+                                    ) || is_hoc_component(&name, &declarator.init, &local_hocs)
+                                    {
+                                        declared.push((name, ident.span));
+                                    } else if options.loose_components
+                                        && let Some(init) = &declarator.init
+                                    {
+                                        entries.extend(
+                                            component::config_entry_components(
+                                                init,
+                                                &jsx_runtime_identifiers,
+                                            )
+                                            .into_iter()
+                                            .map(|(key, span)| (format!("{}.{}", name, key), span)),
+                                        );
+                                    }
                                 }
-                                ModuleExportName::StringLiteral(lit) => lit.value.to_string(),
-                            };
-                            // Check if this is a component we already declared
-                            if let Some(&span) = component_declarations.get(&exported_name) {
-                                exported_components.push((exported_name, span));
                             }
                         }
+                        Declaration::FunctionDeclaration(func_decl) => {
+                            if let Some(component) = component::analyze_function_declaration(
+                                func_decl,
+                                &jsx_runtime_identifiers,
+                                &options.component_types,
+                            ) {
+                                declared.push(component);
+                            }
+                        }
+                        Declaration::ClassDeclaration(class) => {
+                            if let Some(component) = component::analyze_class_declaration(class) {
+                                declared.push(component);
+                            }
+                        }
+                        _ => {}
+                    }
+
+                    for (name, span) in declared {
+                        component_declarations.insert(name.clone(), span);
+                        register_export(
+                            &mut exported_components,
+                            ExportedComponent {
+                                name,
+                                span,
+                                kind: types::ExportKind::Named,
+                                exported_name: None,
+                                export_span: export_decl.span,
+                                conditional: false,
+                                grouped: false,
+                            },
+                        );
+                    }
+                    for (name, span) in entries {
+                        register_export(
+                            &mut exported_components,
+                            ExportedComponent {
+                                name,
+                                span,
+                                kind: types::ExportKind::Named,
+                                exported_name: None,
+                                export_span: export_decl.span,
+                                conditional: false,
+                                grouped: false,
+                            },
+                        );
+                    }
+                } else if !export_decl.specifiers.is_empty() && export_decl.source.is_none() {
+                    // Handle export { ComponentName } and export { ComponentName as Alias }
+                    // (re-export of already declared variable)
+                    for specifier in export_decl.specifiers.iter() {
+                        let local_name = module_export_name(&specifier.local);
+                        let exported_name = module_export_name(&specifier.exported);
+
+                        // Check if this is a component we already declared
+                        if let Some(&span) = component_declarations.get(&local_name) {
+                            let (kind, exported_name) = if exported_name == "default" {
+                                (types::ExportKind::Default, None)
+                            } else if exported_name != local_name {
+                                (types::ExportKind::Named, Some(exported_name))
+                            } else {
+                                (types::ExportKind::Named, None)
+                            };
+                            register_export(
+                                &mut exported_components,
+                                ExportedComponent {
+                                    name: local_name,
+                                    span,
+                                    kind,
+                                    exported_name,
+                                    export_span: export_decl.span,
+                                    conditional: false,
+                                    grouped: false,
+                                },
+                            );
+                        }
                     }
                 }
-                _ => {}
             }
+            _ => {}
         }
+    }
 
-        let components = exported_components
-            .into_iter()
-            .map(|(name, span)| types::ComponentAnalysis {
-                name,
-                // Mark as client component ONLY if the "use client" directive is present
-                is_client_component: has_use_client_directive,
-                range: range::span_to_range(&source_text, span),
-            })
-            .collect::<Vec<_>>();
-
-        #[cfg(not(test))]
-        if !components.is_empty() && has_use_client_directive {
-            log(&format!(
-                "✓ Detected {} client component{}: {}",
-                components.len(),
-                if components.len() == 1 { "" } else { "s" },
-                components
-                    .iter()
-                    .map(|c| c.name.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            ));
+    // Inline exports of annotated client declarations (`export const X`, `export default function
+    // X`) the heuristics missed, and suppression of ignored declarations
+    for annotation in annotations.iter() {
+        if annotation.kind == types::BoundaryAnnotation::Client
+            && let Some((kind, export_span)) = annotation.export
+        {
+            register_export(
+                &mut exported_components,
+                ExportedComponent {
+                    name: annotation.name.clone(),
+                    span: annotation.span,
+                    kind,
+                    exported_name: None,
+                    export_span,
+                    conditional: false,
+                    grouped: false,
+                },
+            );
         }
+    }
+    exported_components.retain(|component| {
+        annotation::annotation_for(&annotations, &component.name, component.span)
+            != Some(types::BoundaryAnnotation::Ignore)
+    });
+
+    // Local components attached to an exported one (`Card.Header = CardHeader`) are exported
+    // through it, and rendered elsewhere as `<Card.Header>`
+    let subcomponents = compound::collect_subcomponents(&program.body);
+    let attached = subcomponents
+        .iter()
+        .filter_map(|subcomponent| {
+            let span = *component_declarations.get(subcomponent.local.as_deref()?)?;
+            Some((subcomponent, span))
+        })
+        .flat_map(|(subcomponent, span)| {
+            exported_components
+                .iter()
+                .filter(|parent| parent.name == subcomponent.parent)
+                .map(move |parent| ExportedComponent {
+                    name: format!("{}.{}", parent.name, subcomponent.member),
+                    span,
+                    kind: parent.kind,
+                    exported_name: parent
+                        .exported_name
+                        .as_ref()
+                        .map(|exported| format!("{}.{}", exported, subcomponent.member)),
+                    export_span: parent.export_span,
+                    conditional: false,
+                    grouped: parent.grouped,
+                })
+        })
+        .collect::<Vec<_>>();
+    for subcomponent in attached {
+        register_export(&mut exported_components, subcomponent);
+    }
+
+    // Route handlers, middleware and instrumentation never render, so skip the component
+    // heuristics that would otherwise pick up e.g. `export const GET = () => ...`
+    let route_handlers = route::collect_route_handlers(&program.body)
+        .into_iter()
+        .map(|(method, span)| types::RouteHandler {
+            method,
+            range: ranges.span(span),
+        })
+        .collect::<Vec<_>>();
+    let pages_data_functions = route::collect_pages_data_functions(&program.body)
+        .into_iter()
+        .map(|(name, span)| types::PagesDataFunction {
+            name,
+            range: ranges.span(span),
+        })
+        .collect::<Vec<_>>();
+    let module_kind = module_kind::classify_module(
+        &route::exported_bindings(&program.body),
+        &route_handlers,
+        &pages_data_functions,
+    );
+    if !matches!(
+        module_kind,
+        types::ModuleKind::Component | types::ModuleKind::PagesRoute
+    ) {
+        exported_components.clear();
+    }
 
-        // Collect all imported identifiers
-        let imported_identifiers: HashSet<String> = imports
-            .iter()
-            .flat_map(|import| import.identifier.iter().cloned())
-            .collect();
-
-        // Collect JSX element usages
-        let jsx_usages_raw = jsx::collect_jsx_usages(&program.body);
+    let props_types = props::collect_props_types(&program.body);
+    let components = exported_components
+        .into_iter()
+        .map(|component| {
+            let annotation =
+                annotation::annotation_for(&annotations, &component.name, component.span);
+            types::ComponentAnalysis {
+                id: component.id(),
+                name: component.name,
+                // Mark as client component ONLY if the "use client" directive is present, or the
+                // declaration is annotated as one
+                is_client_component: has_use_client_directive
+                    || annotation == Some(types::BoundaryAnnotation::Client),
+                range: ranges.span(component.span),
+                export_kind: component.kind,
+                exported_name: component.exported_name,
+                export_range: ranges.span(component.export_span),
+                conditional: component.conditional,
+                grouped: component.grouped,
+                annotation,
+                props_type: props_types
+                    .get(&component.span)
+                    .map(|(name, span)| types::PropsType {
+                        name: name.clone(),
+                        range: ranges.span(*span),
+                    }),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    #[cfg(target_arch = "wasm32")]
+    if !components.is_empty() && has_use_client_directive {
+        log(&format!(
+            "✓ Detected {} client component{}: {}",
+            components.len(),
+            if components.len() == 1 { "" } else { "s" },
+            components
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
 
-        // Filter JSX usages to only those that match imports
-        let jsx_usages = jsx_usages_raw
-            .into_iter()
-            .filter(|(name, _)| imported_identifiers.contains(name))
-            .map(|(name, span)| types::JsxUsage {
-                component_name: name,
-                range: range::span_to_range(&source_text, span),
+    check_cancelled(is_cancelled)?;
+    budget.check()?;
+    let use_client_range =
+        use_client_directive.map(|directive| ranges.span(directive.expression.span));
+    if deadline.expired() {
+        return Ok(AnalysisResult {
+            imports,
+            components,
+            route_handlers,
+            module_kind,
+            pages_data_functions,
+            parse_errors,
+            ..timed_out_result(use_client_range)
+        });
+    }
+
+    // Map each imported identifier to the index of the import declaring it
+    let import_indices: HashMap<&str, u32> = imports
+        .iter()
+        .enumerate()
+        .flat_map(|(index, import)| {
+            import
+                .identifier
+                .iter()
+                .map(move |identifier| (identifier.as_str(), index as u32))
+        })
+        .collect();
+
+    // Collect JSX element usages. A component passed to a local HOC is rendered by it, so the
+    // application counts as a usage of the wrapped component
+    let mut jsx = jsx::collect_jsx_until(&program.body, deadline);
+    jsx.usages
+        .extend(component::hoc_applications(&program.body, &local_hocs));
+    let fragments = jsx::FragmentBindings::collect(&program.body);
+
+    // Filter JSX usages to only those that match imports, unless the host asked for local ones too
+    let jsx_usages = jsx
+        .usages
+        .iter()
+        .filter_map(|(name, span)| {
+            // Link the usage to the import declaring its (base) identifier
+            let import_index = import_indices.get(name.as_str()).copied();
+            let source = if import_index.is_some() {
+                types::UsageSource::Imported
+            } else if options.include_local_jsx_usages {
+                types::UsageSource::Local
+            } else {
+                return None;
+            };
+            let member = jsx.member_for(*span);
+            Some(types::JsxUsage {
+                component_name: name.clone(),
+                range: ranges.span(*span),
+                props: jsx.props_for(*span),
+                attributes: jsx
+                    .attributes_for(*span)
+                    .into_iter()
+                    .map(|(name, span)| types::JsxAttribute {
+                        name,
+                        range: ranges.span(span),
+                    })
+                    .collect(),
+                spreads: jsx
+                    .spreads_for(*span)
+                    .into_iter()
+                    .map(|(name, span)| types::JsxSpread {
+                        name,
+                        range: ranges.span(span),
+                    })
+                    .collect(),
+                has_key: jsx.passes(*span, "key"),
+                has_ref: jsx.passes(*span, "ref"),
+                source,
+                // Fragments render no component, so they never cross a boundary
+                is_fragment: fragments.is_fragment(name, member.as_deref()),
+                in_config: jsx.in_config(*span),
+                member,
+                import_index,
             })
-            .collect::<Vec<_>>();
+        })
+        .collect::<Vec<_>>();
+
+    // The islands of an Astro template are the framework components it hydrates in the browser
+    #[cfg(feature = "astro")]
+    let jsx_usages = {
+        let mut jsx_usages = jsx_usages;
+        if astro_source.is_some() {
+            jsx_usages.extend(astro::collect_islands(&source_text).into_iter().filter_map(
+                |island| {
+                    let (name, member) = match island.name.split_once('.') {
+                        Some((name, member)) => (name.to_string(), Some(member.to_string())),
+                        None => (island.name, None),
+                    };
+                    let import_index = import_indices.get(name.as_str()).copied();
+                    let source = if import_index.is_some() {
+                        types::UsageSource::Imported
+                    } else if options.include_local_jsx_usages {
+                        types::UsageSource::Local
+                    } else {
+                        return None;
+                    };
+                    Some(types::JsxUsage {
+                        component_name: name,
+                        range: ranges.span(island.span),
+                        props: Vec::new(),
+                        attributes: vec![types::JsxAttribute {
+                            name: island.directive,
+                            range: ranges.span(island.directive_span),
+                        }],
+                        spreads: Vec::new(),
+                        has_key: false,
+                        has_ref: false,
+                        source,
+                        member,
+                        import_index,
+                        is_fragment: false,
+                        in_config: false,
+                    })
+                },
+            ));
+        }
+        jsx_usages
+    };
 
-        Ok(AnalysisResult {
+    // The walk also stops early once the memory budget is exceeded
+    budget.check()?;
+    if jsx.timed_out || deadline.expired() {
+        return Ok(AnalysisResult {
             imports,
             components,
             jsx_usages,
+            route_handlers,
+            module_kind,
+            pages_data_functions,
+            parse_errors,
+            ..timed_out_result(use_client_range)
+        });
+    }
+
+    let module_use_server = program
+        .directives
+        .iter()
+        .any(|directive| directive.directive == "use server");
+    let component_names = component_declarations
+        .keys()
+        .map(String::as_str)
+        .collect::<HashSet<_>>();
+    let server_actions =
+        action::collect_server_actions(&program, module_use_server, &component_names)
+            .into_iter()
+            .map(|action| types::ServerAction {
+                bound_in_jsx: jsx
+                    .bound_actions
+                    .iter()
+                    .any(|(name, _)| *name == action.name),
+                name: action.name,
+                range: ranges.span(action.span),
+                parameter_count: action.parameter_count,
+                uses_form_data: action.uses_form_data,
+                component: action.component,
+                binds: action
+                    .binds
+                    .into_iter()
+                    .map(|(span, argument_count)| types::ActionBind {
+                        range: ranges.span(span),
+                        argument_count,
+                    })
+                    .collect(),
+                captures: action
+                    .captures
+                    .into_iter()
+                    .map(|(name, span)| types::CapturedVariable {
+                        name,
+                        range: ranges.span(span),
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+
+    let mut signals = signal::collect_signals(&program.body, has_use_client_directive);
+    if !has_use_client_directive {
+        signal::collect_client_package_signals(
+            &program.body,
+            &options.client_packages,
+            &mut signals,
+        );
+    }
+    signal::collect_server_package_signals(
+        &program.body,
+        &options.server_packages,
+        has_use_client_directive,
+        &mut signals,
+    );
+    let mut sensitive_props = jsx.sensitive_props.values().flatten().collect::<Vec<_>>();
+    sensitive_props.sort_by_key(|(_, span)| span.start);
+    signals
+        .signals
+        .extend(sensitive_props.into_iter().map(|(prop, span)| {
+            (
+                types::SignalKind::SensitiveProp,
+                jsx::sensitive_prop_name(*prop).to_string(),
+                *span,
+            )
+        }));
+
+    let client_weight =
+        has_use_client_directive.then(|| weight::client_weight(&source_text, &imports));
+
+    // Names other modules import the components by
+    let component_exports = components
+        .iter()
+        .flat_map(|component| {
+            let named = component
+                .exported_name
+                .clone()
+                .unwrap_or_else(|| component.name.clone());
+            match component.export_kind {
+                types::ExportKind::Default => vec!["default".to_string()],
+                types::ExportKind::Named => vec![named],
+                types::ExportKind::Both => vec!["default".to_string(), named],
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Only the exports of a client module are boundary roots
+    let client_exports = if has_use_client_directive {
+        client_export::collect_client_exports(&program.body, &component_exports)
+    } else {
+        client_export::ClientExports::default()
+    };
+    signals.diagnostics.extend(client_exports.diagnostics);
+    if module_use_server {
+        signals
+            .diagnostics
+            .extend(asset::collect_server_stylesheet_diagnostics(&program.body));
+    }
+    // Pages router pages render on the client too, so the server component rules don't apply
+    if !has_use_client_directive && module_kind != types::ModuleKind::PagesRoute {
+        signals
+            .diagnostics
+            .extend(context::collect_provider_diagnostics(&program.body, &jsx));
+
+        // Props of imported components may cross into a client module, where only server actions
+        // can be received as functions
+        let imported_usages = jsx
+            .usages
+            .iter()
+            .filter(|(name, _)| import_indices.contains_key(name.as_str()))
+            .map(|(name, span)| (*span, name))
+            .collect::<HashMap<_, _>>();
+        signals
+            .diagnostics
+            .extend(jsx.function_props.iter().filter_map(
+            |(element_span, attribute, span)| {
+                let name = imported_usages.get(element_span)?;
+                Some((
+                    Rule::FunctionPropToClient,
+                    format!(
+                        "Function passed to {} of imported component {} cannot be serialized \
+                         across the client boundary unless it is a server action",
+                        attribute, name
+                    ),
+                    *span,
+                ))
+            },
+        ));
+    }
+
+    // Components declared in render functions are remounted on every render of their parent
+    let nested_components = nested::collect_nested_components(
+        &program.body,
+        &jsx_runtime_identifiers,
+        &options.component_types,
+    );
+
+    let context_consumers = context::collect_context_consumers(&program.body)
+        .into_iter()
+        .filter_map(|consumer| {
+            Some(types::ContextConsumer {
+                import_index: *import_indices.get(consumer.local.as_str())?,
+                local: consumer.local,
+                imported: consumer.imported,
+                source: consumer.source,
+                range: ranges.span(consumer.span),
+            })
+        })
+        .collect();
+
+    let value_exports = value_export::collect_value_exports(&program.body, &component_exports)
+        .into_iter()
+        .map(|(name, kind, span)| types::ValueExport {
+            name,
+            kind,
+            range: ranges.span(span),
         })
+        .collect::<Vec<_>>();
+
+    let debug_usages = if options.include_debug_usages {
+        // The side the module's own directive declares. A module without "use client" imported
+        // by a client module still reports Server, since resolving that needs the module graph
+        let directive_side = if has_use_client_directive {
+            types::BoundarySide::Client
+        } else {
+            types::BoundarySide::Server
+        };
+        debug_usage::collect_debug_usages(&program.body, &component_names)
+            .into_iter()
+            .map(|usage| types::DebugUsage {
+                kind: usage.kind,
+                method: usage.method,
+                component: usage.component,
+                directive_side,
+                range: ranges.span(usage.span),
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let type_exports = if options.include_type_exports {
+        type_export::collect_type_exports(&program.body)
+            .into_iter()
+            .map(|export| types::TypeExport {
+                name: export.name,
+                kind: export.kind,
+                members: export
+                    .members
+                    .into_iter()
+                    .map(|member| types::TypeMember {
+                        name: member.name,
+                        optional: member.optional,
+                        non_serializable: member.non_serializable,
+                        range: ranges.span(member.span),
+                    })
+                    .collect(),
+                range: ranges.span(export.span),
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // A client module loading a component lazily doesn't cross the boundary
+    let dynamic_boundaries = if has_use_client_directive {
+        Vec::new()
+    } else {
+        dynamic::collect_dynamic_boundaries(&program.body)
+            .into_iter()
+            .map(|boundary| types::DynamicBoundary {
+                name: boundary.name,
+                source: boundary.source,
+                source_span: ranges.string_literal(boundary.source_span),
+                ssr: boundary.ssr,
+                range: ranges.span(boundary.span),
+            })
+            .collect()
+    };
+
+    let workers = worker::collect_workers(&program.body)
+        .into_iter()
+        .map(|worker| types::WorkerReference {
+            kind: worker.kind,
+            source: worker.source,
+            source_span: ranges.string_literal(worker.source_span),
+            range: ranges.span(worker.span),
+        })
+        .collect();
+
+    let conditional_imports = conditional::collect_conditional_imports(&program.body)
+        .into_iter()
+        .map(|import| types::ConditionalImport {
+            kind: import.kind,
+            source: import.source,
+            source_span: ranges.string_literal(import.source_span),
+            guard: ranges.span(import.guard_span),
+            range: ranges.span(import.span),
+        })
+        .collect();
+
+    let import_meta_accesses = import_meta::collect_import_meta_accesses(&program.body)
+        .into_iter()
+        .map(|access| types::ImportMetaAccess {
+            property: access.property,
+            env_key: access.env_key,
+            range: ranges.span(access.span),
+        })
+        .collect();
+
+    let jsx_runtimes = jsx_runtime::detect_jsx_runtimes(&program.body)
+        .into_iter()
+        .map(|(runtime, spans)| types::JsxRuntimeUsage {
+            runtime,
+            import_ranges: spans.into_iter().map(|span| ranges.span(span)).collect(),
+        })
+        .collect();
+
+    let statistics = options.include_statistics.then(|| {
+        let counts = walk::count_nodes(&program.body);
+        types::AstStatistics {
+            function_count: counts.functions,
+            jsx_element_count: counts.jsx_elements,
+            call_expression_count: counts.calls,
+            max_depth: counts.max_depth,
+        }
+    });
+
+    Ok(AnalysisResult {
+        imports,
+        components,
+        jsx_usages,
+        use_client_directive: use_client_range,
+        route_handlers,
+        module_kind,
+        pages_data_functions,
+        server_actions,
+        signals: signals
+            .signals
+            .into_iter()
+            .map(|(kind, name, span)| types::Signal {
+                hook_category: (kind == types::SignalKind::ClientHook)
+                    .then(|| signal::hook_category(&name))
+                    .flatten(),
+                kind,
+                name,
+                range: ranges.span(span),
+            })
+            .collect(),
+        diagnostics: signals
+            .diagnostics
+            .into_iter()
+            .map(|(rule, message, span)| (rule, message, span, None))
+            .chain(nested_components.into_iter().map(|nested| {
+                (
+                    Rule::NestedComponent,
+                    format!(
+                        "{} is declared inside {}, so every render of {} creates a new component \
+                         type and remounts it. Move it to the top level of the module",
+                        nested.name, nested.parent, nested.parent
+                    ),
+                    nested.span,
+                    Some(nested.parent_span),
+                )
+            }))
+            .filter_map(|(rule, message, span, related_span)| {
+                Some(types::Diagnostic {
+                    severity: rule.severity(&options.rules)?,
+                    rule: rule.id().to_string(),
+                    message,
+                    range: ranges.span(span),
+                    related_range: related_span.map(|span| ranges.span(span)),
+                })
+            })
+            .collect(),
+        client_weight,
+        client_exports: client_exports
+            .exports
+            .into_iter()
+            .map(|(name, kind, span)| types::ClientExport {
+                name,
+                kind,
+                range: ranges.span(span),
+            })
+            .collect(),
+        partial: !parse_errors.is_empty(),
+        timed_out: false,
+        parse_errors,
+        re_exports: re_export::collect_re_exports(&program.body)
+            .into_iter()
+            .map(|re_export| types::ReExport {
+                // A "use client" wrapper re-exporting a package (`export { Button } from
+                // "@mui/material"`) is the client entry of components defined elsewhere
+                grants_directive: has_use_client_directive
+                    && !re_export.type_only
+                    && weight::is_package_specifier(&re_export.source),
+                source: re_export.source,
+                source_span: ranges.string_literal(re_export.source_span),
+                imported: re_export.imported,
+                exported: re_export.exported,
+                type_only: re_export.type_only,
+                range: ranges.span(re_export.span),
+            })
+            .collect(),
+        context_consumers,
+        is_client_module: has_use_client_directive,
+        // Hook libraries export no components, so their hooks are what crosses the boundary
+        exported_hooks: value_exports
+            .iter()
+            .filter(|export| {
+                export.kind == types::ValueKind::Function
+                    && value_export::is_hook_name(&export.name)
+            })
+            .map(|export| types::ExportedHook {
+                name: export.name.clone(),
+                range: export.range,
+            })
+            .collect(),
+        value_exports,
+        type_exports,
+        dynamic_boundaries,
+        workers,
+        conditional_imports,
+        debug_usages,
+        import_meta_accesses,
+        jsx_runtimes,
+        subcomponents: subcomponents
+            .into_iter()
+            .map(|subcomponent| types::Subcomponent {
+                parent: subcomponent.parent,
+                member: subcomponent.member,
+                local: subcomponent.local,
+                range: ranges.span(subcomponent.span),
+            })
+            .collect(),
+        statistics,
+    })
+}
+
+/// Result of an analysis whose deadline passed. Callers fill in what the passes that finished
+/// found with struct update syntax; the passes after the deadline are skipped, so their fields
+/// stay empty
+fn timed_out_result(use_client_directive: Option<types::Range>) -> AnalysisResult {
+    AnalysisResult {
+        imports: Vec::new(),
+        components: Vec::new(),
+        jsx_usages: Vec::new(),
+        use_client_directive,
+        route_handlers: Vec::new(),
+        module_kind: types::ModuleKind::Component,
+        pages_data_functions: Vec::new(),
+        server_actions: Vec::new(),
+        signals: Vec::new(),
+        diagnostics: Vec::new(),
+        client_weight: None,
+        client_exports: Vec::new(),
+        partial: true,
+        timed_out: true,
+        parse_errors: Vec::new(),
+        re_exports: Vec::new(),
+        context_consumers: Vec::new(),
+        value_exports: Vec::new(),
+        is_client_module: use_client_directive.is_some(),
+        exported_hooks: Vec::new(),
+        type_exports: Vec::new(),
+        dynamic_boundaries: Vec::new(),
+        workers: Vec::new(),
+        conditional_imports: Vec::new(),
+        debug_usages: Vec::new(),
+        import_meta_accesses: Vec::new(),
+        jsx_runtimes: Vec::new(),
+        subcomponents: Vec::new(),
+        statistics: None,
     }
 }
 
@@ -403,584 +1941,2300 @@ export const Button = () => {
 
         let result = analyze_tsx(source).unwrap();
 
-        assert_eq!(result.components.len(), 1);
-        assert_eq!(result.components[0].name, "Button");
+        assert_eq!(result.components.len(), 1);
+        assert_eq!(result.components[0].name, "Button");
+        assert!(result.components[0].is_client_component);
+    }
+
+    #[test]
+    fn test_analyze_multiple_exports() {
+        let source = r##"
+"use client";
+
+export const Button = () => <button>Click</button>;
+export const Link = () => <a href="#">Link</a>;
+
+const Header = () => <header>Header</header>;
+export default Header;
+        "##;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(result.components.len(), 3);
+
+        let names: Vec<&str> = result.components.iter().map(|c| c.name.as_str()).collect();
+        assert!(names.contains(&"Button"));
+        assert!(names.contains(&"Link"));
+        assert!(names.contains(&"Header"));
+
+        // All should be client components
+        assert!(result.components.iter().all(|c| c.is_client_component));
+    }
+
+    #[test]
+    fn test_analyze_use_client_directive_range() {
+        let source = r#"// Header comment
+"use client";
+
+export const Button = () => <button>Click</button>;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        // Range should cover the quoted directive literal on line 1
+        let directive = result.use_client_directive.unwrap();
+        assert_eq!(directive.start.line, 1);
+        assert_eq!(directive.start.character, 0);
+        assert_eq!(directive.end.line, 1);
+        assert_eq!(directive.end.character, 12);
+    }
+
+    #[test]
+    fn test_analyze_no_use_client_directive_range() {
+        let source = r#"
+"use strict";
+
+export const Button = () => <button>Click</button>;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert!(result.use_client_directive.is_none());
+    }
+
+    #[test]
+    fn test_analyze_export_kind_named_and_default() {
+        let source = r#"
+"use client";
+
+export const Button = () => <button>Click</button>;
+const Header = () => <header>Header</header>;
+export default Header;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(result.components.len(), 2);
+
+        let button = result
+            .components
+            .iter()
+            .find(|c| c.name == "Button")
+            .unwrap();
+        assert_eq!(button.export_kind, types::ExportKind::Named);
+        assert_eq!(button.exported_name, None);
+        assert_eq!(button.export_range.start.line, 3);
+
+        let header = result
+            .components
+            .iter()
+            .find(|c| c.name == "Header")
+            .unwrap();
+        assert_eq!(header.export_kind, types::ExportKind::Default);
+        // Export statement is on its own line, after the declaration
+        assert_eq!(header.range.start.line, 4);
+        assert_eq!(header.export_range.start.line, 5);
+    }
+
+    #[test]
+    fn test_analyze_export_kind_both() {
+        let source = r#"
+export const Button = () => <button>Click</button>;
+export default Button;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        // Should merge both exports into a single component
+        assert_eq!(result.components.len(), 1);
+        assert_eq!(result.components[0].export_kind, types::ExportKind::Both);
+        // The export range points at the first export statement
+        assert_eq!(result.components[0].export_range.start.line, 1);
+    }
+
+    #[test]
+    fn test_analyze_aliased_export() {
+        let source = r#"
+const InternalButton = () => <button>Click</button>;
+const Header = () => <header>Header</header>;
+export { InternalButton as Button, Header as default };
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(result.components.len(), 2);
+
+        let button = &result.components[0];
+        assert_eq!(button.name, "InternalButton");
+        assert_eq!(button.export_kind, types::ExportKind::Named);
+        assert_eq!(button.exported_name.as_deref(), Some("Button"));
+
+        let header = &result.components[1];
+        assert_eq!(header.name, "Header");
+        assert_eq!(header.export_kind, types::ExportKind::Default);
+        assert_eq!(header.exported_name, None);
+    }
+
+    #[test]
+    fn test_analyze_component_id_stable_across_edits() {
+        let before = r#"
+export const Button = () => <button>Click</button>;
+export default function Header() {
+  return <header>Header</header>;
+}
+        "#;
+        let after = r#"
+import { useState } from "react";
+
+export const Button = () => {
+  const [count] = useState(0);
+  return <button>{count}</button>;
+};
+
+export default function RenamedHeader() {
+  return <header>Header</header>;
+}
+        "#;
+
+        let before = analyze_tsx(before).unwrap();
+        let after = analyze_tsx(after).unwrap();
+
+        // Ranges shift, but ids stay the same
+        assert_eq!(before.components[0].id, after.components[0].id);
+        assert_eq!(before.components[1].id, after.components[1].id);
+        assert_ne!(before.components[0].id, before.components[1].id);
+    }
+
+    #[test]
+    fn test_analyze_component_id_changes_with_export_kind() {
+        let named = analyze_tsx("export const Button = () => <button />;").unwrap();
+        let both =
+            analyze_tsx("export const Button = () => <button />;\nexport default Button;").unwrap();
+
+        assert_ne!(named.components[0].id, both.components[0].id);
+    }
+
+    #[test]
+    fn test_analyze_imports_default_specifier() {
+        let source = r#"
+import ClientComponent from "./client";
+import AnotherComponent from "./another";
+
+const App = () => {
+  return <div>App</div>;
+};
+
+export default App;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(result.imports.len(), 2);
+
+        // Check first import
+        assert_eq!(result.imports[0].identifier.len(), 1);
+        assert_eq!(result.imports[0].identifier[0], "ClientComponent");
+        assert_eq!(result.imports[0].source, "./client");
+
+        // Check second import
+        assert_eq!(result.imports[1].identifier.len(), 1);
+        assert_eq!(result.imports[1].identifier[0], "AnotherComponent");
+        assert_eq!(result.imports[1].source, "./another");
+    }
+
+    #[test]
+    fn test_analyze_imports_named_specifier() {
+        let source = r#"
+import { Button, Link } from "./components";
+import { FC } from "react";
+
+const App: FC = () => <div>App</div>;
+export default App;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(result.imports.len(), 2);
+
+        // Check named imports from components
+        assert_eq!(result.imports[0].identifier.len(), 2);
+        assert!(result.imports[0].identifier.contains(&"Button".to_string()));
+        assert!(result.imports[0].identifier.contains(&"Link".to_string()));
+        assert_eq!(result.imports[0].source, "./components");
+
+        // Check FC import from react
+        assert_eq!(result.imports[1].identifier.len(), 1);
+        assert_eq!(result.imports[1].identifier[0], "FC");
+        assert_eq!(result.imports[1].source, "react");
+    }
+
+    #[test]
+    fn test_analyze_imports_namespace_specifier() {
+        let source = r#"
+import * as React from "react";
+import * as Components from "./components";
+
+const App = () => <div>App</div>;
+export default App;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(result.imports.len(), 2);
+
+        // Check namespace imports
+        assert_eq!(result.imports[0].identifier[0], "React");
+        assert_eq!(result.imports[1].identifier[0], "Components");
+    }
+
+    #[test]
+    fn test_analyze_ignores_type_imports() {
+        let source = r#"
+import type { FC } from "react";
+import type { Props } from "./types";
+import { Button } from "./button";
+
+const App: FC = () => <div>App</div>;
+export default App;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        // Should only include the Button import, not type imports
+        assert_eq!(result.imports.len(), 1);
+        assert_eq!(result.imports[0].identifier[0], "Button");
+        assert_eq!(result.imports[0].source, "./button");
+    }
+
+    #[test]
+    fn test_analyze_jsx_usages() {
+        let source = r#"
+import ClientComponent from "./client";
+import { Button, Link } from "./components";
+
+const App = () => {
+  return (
+    <div>
+      <ClientComponent />
+      <Button />
+      <Link />
+    </div>
+  );
+};
+
+export default App;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        // Should find all three JSX usages
+        assert_eq!(result.jsx_usages.len(), 3);
+
+        let usage_names: Vec<&str> = result
+            .jsx_usages
+            .iter()
+            .map(|u| u.component_name.as_str())
+            .collect();
+
+        assert!(usage_names.contains(&"ClientComponent"));
+        assert!(usage_names.contains(&"Button"));
+        assert!(usage_names.contains(&"Link"));
+    }
+
+    #[test]
+    fn test_analyze_jsx_usages_filtered_to_imports() {
+        let source = r#"
+import { Button } from "./components";
+
+// LocalComponent is NOT imported, so it should NOT be in jsx_usages
+const LocalComponent = () => <div>Local</div>;
+
+const App = () => {
+  return (
+    <div>
+      <Button />
+      <LocalComponent />
+    </div>
+  );
+};
+
+export default App;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        // Should only include Button (imported), not LocalComponent (local)
+        assert_eq!(result.jsx_usages.len(), 1);
+        assert_eq!(result.jsx_usages[0].component_name, "Button");
+    }
+
+    #[test]
+    fn test_analyze_with_local_jsx_usages() {
+        let source = r#"
+import { Button } from "./button";
+
+const Card = () => <div />;
+
+export function Page() {
+  return (
+    <Card>
+      <Button />
+    </Card>
+  );
+}
+        "#;
+
+        let result = AnalyzeReactBoundary::analyze_with_options(
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            AnalyzeOptions {
+                include_local_jsx_usages: true,
+                byte_spans: false,
+                one_based_positions: false,
+                memory_limit: None,
+                include_statistics: false,
+                client_packages: Vec::new(),
+                server_packages: Vec::new(),
+                timeout: None,
+                rules: Vec::new(),
+                component_types: Vec::new(),
+                include_type_exports: false,
+                group_default_exports: true,
+                include_debug_usages: false,
+                loose_components: false,
+            },
+        )
+        .unwrap();
+
+        let usages = result
+            .jsx_usages
+            .iter()
+            .map(|usage| (usage.component_name.as_str(), usage.source))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            usages,
+            vec![
+                ("Card", types::UsageSource::Local),
+                ("Button", types::UsageSource::Imported)
+            ]
+        );
+
+        let result = analyze_tsx(source).unwrap();
+        assert_eq!(result.jsx_usages.len(), 1);
+        assert_eq!(result.jsx_usages[0].source, types::UsageSource::Imported);
+    }
+
+    #[test]
+    fn test_analyze_complete_flow() {
+        let source = r#"
+"use client";
+import { ServerButton } from "./server";
+import type { FC } from "react";
+
+export const ClientButton: FC = () => {
+  return (
+    <button>
+      <ServerButton />
+    </button>
+  );
+};
+
+const ClientHeader = () => {
+  return <header>Header</header>;
+};
+
+export default ClientHeader;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        // Check imports
+        assert_eq!(result.imports.len(), 1); // type imports are ignored
+        assert_eq!(result.imports[0].identifier[0], "ServerButton");
+
+        // Check components
+        assert_eq!(result.components.len(), 2);
+        assert!(result.components.iter().all(|c| c.is_client_component));
+
+        // Check JSX usages
+        assert_eq!(result.jsx_usages.len(), 1);
+        assert_eq!(result.jsx_usages[0].component_name, "ServerButton");
+    }
+
+    #[test]
+    fn test_analyze_invalid_syntax() {
+        let source = "const x = {{{";
+
+        let result = analyze_tsx(source);
+
+        // Should return an error
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_analyze_invalid_extension() {
+        let source = "const x = 10;";
+
+        let result = analyze_with_extension(source, "invalid");
+
+        // Should return an error for invalid extension
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_analyze_invalid_utf8() {
+        let content = vec![0x63, 0x6f, 0x6e, 0x73, 0x74, 0x20, 0xff, 0xfe];
+
+        let result = AnalyzeReactBoundary::analyze(content, "tsx".to_string());
+
+        // Should return an error instead of panicking
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_analyze_cancelled() {
+        let source = "export const Button = () => <button />;";
+
+        let result = analyze_source(source.as_bytes().to_vec(), "tsx".to_string(), &|| true);
+
+        assert_eq!(result.unwrap_err(), "Analysis cancelled");
+    }
+
+    #[test]
+    fn test_analyze_cancelled_between_passes() {
+        let source = "export const Button = () => <button />;";
+        let polls = std::cell::Cell::new(0);
+
+        // Cancel on the second poll, after parsing has already finished
+        let result = analyze_source(source.as_bytes().to_vec(), "tsx".to_string(), &|| {
+            polls.set(polls.get() + 1);
+            polls.get() > 1
+        });
+
+        assert!(result.is_err());
+        assert_eq!(polls.get(), 2);
+    }
+
+    #[test]
+    fn test_analyze_range_positions() {
+        let source = r#"
+const MyComponent = () => <div />;
+export default MyComponent;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        // Check that ranges are set correctly
+        assert_eq!(result.components.len(), 1);
+        let component = &result.components[0];
+
+        // Component name should be on line 1 (0-indexed)
+        assert_eq!(component.range.start.line, 1);
+        assert!(component.range.start.character > 0);
+        assert_eq!(component.range.end.line, 1);
+    }
+
+    #[test]
+    fn test_analyze_import_source_span() {
+        let source = r#"import X from "./client";"#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(result.imports.len(), 1);
+
+        // source_span should point inside the string (after opening quote)
+        let import = &result.imports[0];
+        assert_eq!(import.source_span.start.line, 0);
+
+        // Character should be after the opening quote
+        // "import X from "./client";"
+        //                ^--- should be around character 15
+        assert!(import.source_span.start.character >= 14);
+        assert!(import.source_span.start.character <= 16);
+    }
+
+    #[test]
+    fn test_analyze_function_declaration_export() {
+        let source = r#"
+"use client";
+
+export function MyComponent() {
+  return <div>Function Declaration</div>;
+}
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(result.components.len(), 1);
+        assert_eq!(result.components[0].name, "MyComponent");
+        assert!(result.components[0].is_client_component);
+    }
+
+    #[test]
+    fn test_analyze_mixed_exports() {
+        let source = r#"
+"use client";
+
+export const ArrowComponent = () => <div>Arrow</div>;
+export function FunctionComponent() {
+  return <div>Function</div>;
+}
+const DefaultComponent = () => <div>Default</div>;
+export default DefaultComponent;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(result.components.len(), 3);
+
+        let names: Vec<&str> = result.components.iter().map(|c| c.name.as_str()).collect();
+        assert!(names.contains(&"ArrowComponent"));
+        assert!(names.contains(&"FunctionComponent"));
+        assert!(names.contains(&"DefaultComponent"));
+
+        // All should be client components
+        assert!(result.components.iter().all(|c| c.is_client_component));
+    }
+
+    #[test]
+    fn test_analyze_jsx_runtime_calls() {
+        // Test that bundled code using jsx() runtime calls is detected
+        let source = r#"
+"use client";
+import { jsx as _jsx } from "react/jsx-runtime";
+
+const Button = () => {
+  return _jsx("button", { children: "Click me" });
+};
+
+export default Button;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(
+            result.components.len(),
+            1,
+            "Should detect component using jsx runtime call"
+        );
+        assert_eq!(result.components[0].name, "Button");
+        assert!(result.components[0].is_client_component);
+    }
+
+    #[test]
+    fn test_analyze_jsxs_runtime_calls() {
+        // Test jsxs (for multiple children)
+        let source = r#"
+"use client";
+import { jsxs as _jsxs } from "react/jsx-runtime";
+
+export const Container = () => {
+  return _jsxs("div", { children: ["Hello", "World"] });
+};
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(
+            result.components.len(),
+            1,
+            "Should detect component using jsxs runtime call"
+        );
+        assert_eq!(result.components[0].name, "Container");
+        assert!(result.components[0].is_client_component);
+    }
+
+    #[test]
+    fn test_analyze_react_forwardref() {
+        // Test React.forwardRef with jsx runtime calls
+        let source = r#"
+"use client";
+import React from "react";
+import { jsx as _jsx } from "react/jsx-runtime";
+
+const Button = React.forwardRef((props, ref) => {
+  return _jsx("button", { ref, ...props });
+});
+
+export default Button;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(
+            result.components.len(),
+            1,
+            "Should detect React.forwardRef component"
+        );
+        assert_eq!(result.components[0].name, "Button");
+        assert!(result.components[0].is_client_component);
+    }
+
+    #[test]
+    fn test_analyze_forwardref_direct_import() {
+        // Test forwardRef (direct import) with jsx runtime calls
+        let source = r#"
+"use client";
+import { forwardRef } from "react";
+import { jsx as _jsx } from "react/jsx-runtime";
+
+const Input = forwardRef((props, ref) => {
+  return _jsx("input", { ref, ...props });
+});
+
+export { Input };
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(
+            result.components.len(),
+            1,
+            "Should detect forwardRef component"
+        );
+        assert_eq!(result.components[0].name, "Input");
+        assert!(result.components[0].is_client_component);
+    }
+
+    #[test]
+    fn test_analyze_jsx_member_expressions() {
+        let source = r#"
+import { AlertDialog } from "radix-ui";
+
+const App = () => {
+  return (
+    <div>
+      <AlertDialog.Root>
+        <AlertDialog.Trigger>Open</AlertDialog.Trigger>
+        <AlertDialog.Content>
+          <AlertDialog.Title>Alert</AlertDialog.Title>
+        </AlertDialog.Content>
+      </AlertDialog.Root>
+    </div>
+  );
+};
+
+export default App;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        // Should detect the AlertDialog import
+        assert_eq!(result.imports.len(), 1);
+        assert_eq!(result.imports[0].identifier[0], "AlertDialog");
+        assert_eq!(result.imports[0].source, "radix-ui");
+
+        // Should detect all 4 member expression usages (Root, Trigger, Content, Title)
+        // All of them should be tracked as "AlertDialog" (the base identifier)
+        assert_eq!(
+            result.jsx_usages.len(),
+            4,
+            "Should detect 4 AlertDialog member expression usages"
+        );
+        assert!(
+            result
+                .jsx_usages
+                .iter()
+                .all(|u| u.component_name == "AlertDialog")
+        );
+    }
+
+    #[test]
+    fn test_analyze_namespace_import_usages() {
+        let source = r#"
+import { Button } from "./button";
+import * as Icons from "./icons";
+
+export function Toolbar() {
+  return (
+    <Button>
+      <Icons.Check />
+    </Button>
+  );
+}
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        let usages = result
+            .jsx_usages
+            .iter()
+            .map(|usage| {
+                (
+                    usage.component_name.as_str(),
+                    usage.member.as_deref(),
+                    usage.import_index,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            usages,
+            vec![("Button", None, Some(0)), ("Icons", Some("Check"), Some(1))]
+        );
+    }
+
+    #[test]
+    fn test_analyze_flow_source() {
+        let source = r#"/**
+ * @flow strict
+ */
+"use client";
+import type { Node } from "react";
+import typeof Theme from "./theme";
+import { Button } from "./button";
+
+type Props = {| +label: ?string, items: Array<*> |};
+
+export default function Toolbar(props: Props): Node {
+  return <Button />;
+}
+"#;
+
+        let result = analyze_with_extension(source, "js").unwrap();
+
+        assert!(!result.partial, "{:?}", result.parse_errors);
+        assert!(result.use_client_directive.is_some());
+        assert_eq!(result.imports.len(), 1);
+        assert_eq!(result.imports[0].source, "./button");
+        assert_eq!(result.components.len(), 1);
+        assert_eq!(result.components[0].name, "Toolbar");
+        assert_eq!(result.components[0].range.start.line, 10);
+        assert_eq!(result.jsx_usages.len(), 1);
+    }
+
+    #[test]
+    fn test_analyze_recoverable_parse_errors() {
+        let source = r#"
+import { Button } from "./button";
+
+export function Toolbar() {
+  const label;
+  return <Button />;
+}
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert!(result.partial);
+        assert_eq!(result.parse_errors.len(), 1);
+        let range = result.parse_errors[0]
+            .range
+            .expect("parse errors carry a range");
+        assert_eq!(range.start.line, 4);
+        assert_eq!(result.components.len(), 1);
+        assert_eq!(result.jsx_usages.len(), 1);
+
+        let result = analyze_tsx("export const Page = () => <main />;").unwrap();
+        assert!(!result.partial);
+        assert!(result.parse_errors.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_conditional_default_export() {
+        let source = r#"
+const MobileNav = () => <nav />;
+const DesktopNav = () => <nav />;
+const isTouch = typeof navigator !== "undefined";
+
+export default isTouch ? MobileNav : DesktopNav;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        let components = result
+            .components
+            .iter()
+            .map(|component| {
+                (
+                    component.name.as_str(),
+                    component.export_kind,
+                    component.conditional,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            components,
+            vec![
+                ("MobileNav", types::ExportKind::Default, true),
+                ("DesktopNav", types::ExportKind::Default, true),
+            ]
+        );
+        assert_ne!(result.components[0].id, result.components[1].id);
+    }
+
+    #[test]
+    fn test_analyze_decorated_class_components() {
+        let source = r#"
+import React, { Component } from "react";
+import { observer } from "mobx-react";
+
+@observer
+export class Counter extends Component {
+  render() {
+    return <span>{this.props.count}</span>;
+  }
+}
+
+export default @observer class Store extends React.PureComponent {}
+
+@observer
+class Panel extends Component {}
+
+export { Panel };
+
+export class Model {}
+        "#;
+
+        for extension in ["tsx", "jsx"] {
+            let result = analyze_with_extension(source, extension).unwrap();
+
+            let names = result
+                .components
+                .iter()
+                .map(|component| (component.name.as_str(), component.export_kind))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                names,
+                vec![
+                    ("Counter", types::ExportKind::Named),
+                    ("Store", types::ExportKind::Default),
+                    ("Panel", types::ExportKind::Named),
+                ],
+                "extension: {}",
+                extension
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "tracking-allocator")]
+    fn test_analyze_memory_limit() {
+        let small = "export const Button = () => <button />;";
+        let large = small.repeat(200);
+
+        let files = [small, &large]
+            .iter()
+            .map(|source| SourceFile {
+                path: "/app/button.tsx".to_string(),
+                content: source.as_bytes().to_vec(),
+                extension: "tsx".to_string(),
+            })
+            .collect();
+        let options = AnalyzeOptions {
+            include_local_jsx_usages: false,
+            byte_spans: false,
+            one_based_positions: false,
+            memory_limit: Some(256 * 1024),
+            include_statistics: false,
+            client_packages: Vec::new(),
+            server_packages: Vec::new(),
+            timeout: None,
+            rules: Vec::new(),
+            component_types: Vec::new(),
+            include_type_exports: false,
+            group_default_exports: true,
+            include_debug_usages: false,
+            loose_components: false,
+        };
+        let batch = AnalyzeReactBoundary::analyze_batch_with_options(files, options);
+
+        // Only the file over the limit fails, the rest of the batch is still analyzed
+        assert!(batch[0].analysis.is_ok());
+        let error = batch[1].analysis.as_ref().unwrap_err();
+        assert!(
+            error.starts_with(memory::FILE_TOO_COMPLEX),
+            "error: {}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_analyze_timeout_returns_partial_result() {
+        let source = r#"
+"use client";
+import { Button } from "./button";
+
+export const Page = () => <Button />;
+"#;
+
+        let analyze = |timeout| {
+            let options = AnalyzeOptions {
+                timeout,
+                ..default_options()
+            };
+            analyze_source_with_options(
+                source.as_bytes().to_vec(),
+                "tsx".to_string(),
+                &options,
+                &|| false,
+            )
+            .unwrap()
+        };
+
+        // The passes before the first deadline check still report their findings
+        let result = analyze(Some(0));
+        assert!(result.timed_out);
+        assert!(result.partial);
+        assert_eq!(result.imports.len(), 1);
+        assert_eq!(result.components.len(), 1);
+        assert!(result.jsx_usages.is_empty());
+        assert!(result.client_exports.is_empty());
+
+        let result = analyze(Some(60_000));
+        assert!(!result.timed_out);
+        assert!(!result.partial);
+        assert_eq!(result.jsx_usages.len(), 1);
+        assert_eq!(result.client_exports.len(), 1);
+    }
+
+    #[test]
+    fn test_analyze_wrapped_default_exports() {
+        let exported = |source: &str| {
+            analyze_source(source.as_bytes().to_vec(), "jsx".to_string(), &|| false)
+                .unwrap()
+                .components
+                .into_iter()
+                .map(|component| (component.name, component.export_kind))
+                .collect::<Vec<_>>()
+        };
+        let page = vec![("Page".to_string(), types::ExportKind::Default)];
+
+        assert_eq!(
+            exported("export default (function Page() { return <div />; });"),
+            page
+        );
+        assert_eq!(
+            exported("export default (0, function Page() { return <div />; });"),
+            page
+        );
+        assert_eq!(
+            exported("function Page() { return <div />; }\nexport default ((0, Page));"),
+            page
+        );
+        assert_eq!(
+            exported(
+                "import { Component } from \"react\";\nexport default (class Page extends Component {});"
+            ),
+            page
+        );
+        assert!(exported("export default (0, function helper() { return 1; });").is_empty());
+    }
+
+    #[test]
+    fn test_analyze_grouped_default_exports() {
+        let source = r#"
+const Button = () => <button />;
+const Link = () => <a />;
+export default { Button, Primary: Link, version: "1.0" };
+"#;
+
+        let result = analyze_tsx(source).unwrap();
+        let components = result
+            .components
+            .iter()
+            .map(|component| {
+                (
+                    component.name.as_str(),
+                    component.export_kind,
+                    component.exported_name.as_deref(),
+                    component.grouped,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            components,
+            vec![
+                ("Button", types::ExportKind::Default, None, true),
+                ("Link", types::ExportKind::Default, Some("Primary"), true),
+            ]
+        );
+        assert_ne!(result.components[0].id, result.components[1].id);
+
+        let declared_first = r#"
+export const Button = () => <button />;
+const ui = { Button };
+export default ui;
+"#;
+        let result = analyze_tsx(declared_first).unwrap();
+        let components = result
+            .components
+            .iter()
+            .map(|component| (component.export_kind, component.grouped))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            components,
+            vec![
+                (types::ExportKind::Named, false),
+                (types::ExportKind::Default, true),
+            ]
+        );
+
+        let options = AnalyzeOptions {
+            group_default_exports: false,
+            ..default_options()
+        };
+        let result = analyze_source_with_options(
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            &options,
+            &|| false,
+        )
+        .unwrap();
+        assert!(result.components.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_static_member_subcomponents() {
+        let source = r#"
+function Card() { return <article />; }
+function CardHeader() { return <header />; }
+Card.Header = CardHeader;
+Card.Footer = ImportedFooter;
+export default Card;
+
+export function Tabs() { return <div />; }
+const TabList = () => <ul />;
+Tabs.List = TabList;
+"#;
+
+        let result =
+            analyze_source(source.as_bytes().to_vec(), "jsx".to_string(), &|| false).unwrap();
+        let components = result
+            .components
+            .iter()
+            .map(|component| {
+                (
+                    component.name.as_str(),
+                    component.export_kind,
+                    component.range.start.line,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            components,
+            vec![
+                ("Card", types::ExportKind::Default, 1),
+                ("Tabs", types::ExportKind::Named, 7),
+                ("Card.Header", types::ExportKind::Default, 2),
+                ("Tabs.List", types::ExportKind::Named, 8),
+            ]
+        );
+
+        // Each subcomponent keeps an id of its own
+        let ids = result
+            .components
+            .iter()
+            .map(|component| component.id.as_str())
+            .collect::<HashSet<_>>();
+        assert_eq!(ids.len(), 4);
+    }
+
+    #[test]
+    fn test_analyze_inline_function_props_of_imported_components() {
+        let source = r#"
+import { Picker } from "./picker";
+
+function Local({ onSelect }) { return <div />; }
+
+export default function Page() {
+  return (
+    <>
+      <Picker onSelect={() => console.log("picked")} label="Pick" />
+      <Picker action={async (data) => { "use server"; await save(data); }} />
+      <Local onSelect={() => {}} />
+      <button onClick={function () {}} />
+    </>
+  );
+}
+"#;
+
+        let result =
+            analyze_source(source.as_bytes().to_vec(), "jsx".to_string(), &|| false).unwrap();
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(
+            result.diagnostics[0].message,
+            "Function passed to onSelect of imported component Picker cannot be serialized across \
+             the client boundary unless it is a server action"
+        );
+        assert_eq!(result.diagnostics[0].range.start.line, 8);
+        assert_eq!(result.diagnostics[0].range.start.character, 14);
+
+        let result = analyze_source(
+            format!("\"use client\";{}", source).into_bytes(),
+            "jsx".to_string(),
+            &|| false,
+        )
+        .unwrap();
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_rule_settings() {
+        let source = r#"
+"use client";
+import "server-only";
+import { taintUniqueValue } from "react";
+        "#;
+
+        let result =
+            analyze_source(source.as_bytes().to_vec(), "tsx".to_string(), &|| false).unwrap();
+        let rules = result
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.rule.as_str(), diagnostic.severity))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rules,
+            vec![
+                ("server-only-import", types::Severity::Error),
+                ("server-only-api", types::Severity::Error),
+            ]
+        );
+
+        let options = AnalyzeOptions {
+            rules: vec![
+                types::RuleSetting {
+                    rule: "server-only-import".to_string(),
+                    level: types::RuleLevel::Off,
+                },
+                types::RuleSetting {
+                    rule: "server-only-api".to_string(),
+                    level: types::RuleLevel::Hint,
+                },
+                types::RuleSetting {
+                    rule: "unknown-rule".to_string(),
+                    level: types::RuleLevel::Off,
+                },
+            ],
+            ..default_options()
+        };
+        let result = analyze_source_with_options(
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            &options,
+            &|| false,
+        )
+        .unwrap();
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].rule, "server-only-api");
+        assert_eq!(result.diagnostics[0].severity, types::Severity::Hint);
+    }
+
+    #[test]
+    fn test_analyze_nested_component_diagnostics() {
+        let source = r#"
+export function List({ items }) {
+  const Row = ({ item }) => <li>{item}</li>;
+  return <ul>{items.map((item) => <Row item={item} />)}</ul>;
+}
+"#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(result.diagnostics.len(), 1);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.rule, "nested-component");
+        assert_eq!(diagnostic.severity, types::Severity::Warning);
+        assert!(
+            diagnostic
+                .message
+                .starts_with("Row is declared inside List")
+        );
+        assert_eq!(diagnostic.range.start.line, 2);
+        assert_eq!(
+            diagnostic.related_range.map(|range| range.start.line),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_analyze_byte_spans() {
+        let source = "import { Button } from \"./button\";\nexport const Page = () => <Button />;";
+
+        let files = vec![SourceFile {
+            path: "/app/page.tsx".to_string(),
+            content: source.as_bytes().to_vec(),
+            extension: "tsx".to_string(),
+        }];
+        let options = AnalyzeOptions {
+            include_local_jsx_usages: false,
+            byte_spans: true,
+            one_based_positions: false,
+            memory_limit: None,
+            include_statistics: false,
+            client_packages: Vec::new(),
+            server_packages: Vec::new(),
+            timeout: None,
+            rules: Vec::new(),
+            component_types: Vec::new(),
+            include_type_exports: false,
+            group_default_exports: true,
+            include_debug_usages: false,
+            loose_components: false,
+        };
+        let batch = AnalyzeReactBoundary::analyze_batch_with_options(files, options);
+
+        let result = batch[0].analysis.as_ref().unwrap();
+        let source_span = result.imports[0].source_span;
+        assert_eq!(source_span.start.line, 0);
+        assert_eq!(
+            &source[source_span.start.character as usize..source_span.end.character as usize],
+            "./button"
+        );
+        let usage = result.jsx_usages[0].range;
+        assert_eq!(
+            &source[usage.start.character as usize..usage.end.character as usize],
+            "<Button />"
+        );
+    }
+
+    #[test]
+    fn test_analyze_import_statement_and_specifier_ranges() {
+        let source = r#"import Dialog, { type DialogProps, Trigger as Open } from "./dialog";"#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        let import = &result.imports[0];
+        assert_eq!(import.identifier, vec!["Dialog", "Open"]);
+        assert_eq!(import.range.start.character, 0);
+        assert_eq!(import.range.end.character, source.len() as u32);
+        let specifiers = import
+            .specifier_ranges
+            .iter()
+            .map(|range| (range.start.character, range.end.character))
+            .collect::<Vec<_>>();
+        assert_eq!(specifiers, vec![(7, 13), (35, 50)]);
+    }
+
+    #[test]
+    fn test_analyze_route_config_usages() {
+        let source = r#"
+import { Home } from "./home";
+import { Settings } from "./settings";
+
+export const routes = [
+  { path: "/", element: <Home /> },
+  { path: "/settings", Component: Settings },
+];
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        let usages = result
+            .jsx_usages
+            .iter()
+            .map(|usage| {
+                (
+                    usage.component_name.as_str(),
+                    usage.in_config,
+                    usage.import_index,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            usages,
+            vec![("Home", true, Some(0)), ("Settings", true, Some(1))]
+        );
+    }
+
+    #[test]
+    fn test_analyze_lookup_table_components() {
+        let source = r#"
+import { Home } from "./home";
+import { Settings } from "./settings";
+
+export const COMPONENTS = new Map([
+  ["home", () => <Home />],
+  ["settings", Settings],
+]);
+export const PANELS = { stats: function () { return <div />; }, title: "Stats" };
+const items = [<Home />];
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+        assert!(result.components.is_empty());
+        assert_eq!(
+            result
+                .jsx_usages
+                .iter()
+                .map(|usage| (usage.component_name.as_str(), usage.in_config))
+                .collect::<Vec<_>>(),
+            vec![("Home", true), ("Settings", true), ("Home", true)]
+        );
+
+        let options = AnalyzeOptions {
+            loose_components: true,
+            ..default_options()
+        };
+        let result = analyze_source_with_options(
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            &options,
+            &|| false,
+        )
+        .unwrap();
+        assert_eq!(
+            result
+                .components
+                .iter()
+                .map(|component| (component.name.as_str(), component.export_kind))
+                .collect::<Vec<_>>(),
+            vec![
+                ("COMPONENTS.home", types::ExportKind::Named),
+                ("PANELS.stats", types::ExportKind::Named),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_analyze_fragment_usages() {
+        let source = r#"
+import React, { Fragment } from "react";
+import { Row } from "./row";
+
+export function List() {
+  return (
+    <Fragment>
+      <React.Fragment key="first">
+        <Row />
+      </React.Fragment>
+    </Fragment>
+  );
+}
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        let usages = result
+            .jsx_usages
+            .iter()
+            .map(|usage| (usage.component_name.as_str(), usage.is_fragment))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            usages,
+            vec![("Fragment", true), ("React", true), ("Row", false)]
+        );
+    }
+
+    #[test]
+    fn test_analyze_compiled_jsx_sequence_expression() {
+        // Test the compiled pattern found in npm packages like radix-ui:
+        // (0, _jsx)("div", props) - SequenceExpression wrapped in parentheses
+        let source = r#"
+"use client";
+import { jsx as _jsx } from "react/jsx-runtime";
+
+const AlertDialog = (props) => {
+  return (0, _jsx)("div", {children: "test"});
+};
+
+export default AlertDialog;
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(
+            result.components.len(),
+            1,
+            "Should detect component using compiled jsx runtime pattern"
+        );
+        assert_eq!(result.components[0].name, "AlertDialog");
+        assert!(result.components[0].is_client_component);
+    }
+
+    #[test]
+    fn test_analyze_renamed_jsx_import() {
+        // Test that renamed jsx runtime imports work correctly
+        // import { jsx as foobar } from "react/jsx-runtime"
+        let source = r#"
+"use client";
+import { jsx as foobar, jsxs as barfoo } from "react/jsx-runtime";
+
+const MyComponent = () => {
+  return foobar("div", {children: "Hello"});
+};
+
+const MyOtherComponent = () => {
+  return barfoo("div", {children: ["Hello", "World"]});
+};
+
+export { MyComponent, MyOtherComponent };
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(
+            result.components.len(),
+            2,
+            "Should detect both components with renamed jsx imports"
+        );
+        assert!(result.components.iter().any(|c| c.name == "MyComponent"));
+        assert!(
+            result
+                .components
+                .iter()
+                .any(|c| c.name == "MyOtherComponent")
+        );
+        assert!(result.components.iter().all(|c| c.is_client_component));
+    }
+
+    #[test]
+    fn test_analyze_inline_export_default_function() {
+        // Test export default function (inline declaration, not reference)
+        let source = r#"
+"use client";
+
+export default function MyComponent() {
+  return <div>Hello</div>;
+}
+        "#;
+
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(
+            result.components.len(),
+            1,
+            "Should detect inline export default function declaration"
+        );
+        assert_eq!(result.components[0].name, "MyComponent");
         assert!(result.components[0].is_client_component);
     }
 
     #[test]
-    fn test_analyze_multiple_exports() {
-        let source = r##"
+    fn test_analyze_multiple_components_with_inline_default() {
+        // Test file with both named exports and inline default export
+        let source = r#"
 "use client";
+import type { FC } from "react";
 
-export const Button = () => <button>Click</button>;
-export const Link = () => <a href="#">Link</a>;
+export const ClientUsesClientNamedFunction: FC = () => {
+  return <div>Named export</div>;
+};
 
-const Header = () => <header>Header</header>;
-export default Header;
-        "##;
+export default function ClientUsesClientDefaultFunction() {
+  return <div>Default export</div>;
+}
+        "#;
 
         let result = analyze_tsx(source).unwrap();
 
-        assert_eq!(result.components.len(), 3);
+        assert_eq!(
+            result.components.len(),
+            2,
+            "Should detect both named and inline default export"
+        );
 
         let names: Vec<&str> = result.components.iter().map(|c| c.name.as_str()).collect();
-        assert!(names.contains(&"Button"));
-        assert!(names.contains(&"Link"));
-        assert!(names.contains(&"Header"));
+        assert!(names.contains(&"ClientUsesClientNamedFunction"));
+        assert!(names.contains(&"ClientUsesClientDefaultFunction"));
 
         // All should be client components
         assert!(result.components.iter().all(|c| c.is_client_component));
     }
 
     #[test]
-    fn test_analyze_imports_default_specifier() {
+    fn test_analyze_exports_before_hoisted_declarations() {
         let source = r#"
-import ClientComponent from "./client";
-import AnotherComponent from "./another";
-
-const App = () => {
-  return <div>App</div>;
-};
+"use client";
 
 export default App;
-        "#;
-
-        let result = analyze_tsx(source).unwrap();
-
-        assert_eq!(result.imports.len(), 2);
-
-        // Check first import
-        assert_eq!(result.imports[0].identifier.len(), 1);
-        assert_eq!(result.imports[0].identifier[0], "ClientComponent");
-        assert_eq!(result.imports[0].source, "./client");
+export { Button as PrimaryButton };
 
-        // Check second import
-        assert_eq!(result.imports[1].identifier.len(), 1);
-        assert_eq!(result.imports[1].identifier[0], "AnotherComponent");
-        assert_eq!(result.imports[1].source, "./another");
-    }
-
-    #[test]
-    fn test_analyze_imports_named_specifier() {
-        let source = r#"
-import { Button, Link } from "./components";
-import { FC } from "react";
+function App() {
+  return <main />;
+}
 
-const App: FC = () => <div>App</div>;
-export default App;
+export const Button = () => <button />;
         "#;
 
         let result = analyze_tsx(source).unwrap();
 
-        assert_eq!(result.imports.len(), 2);
-
-        // Check named imports from components
-        assert_eq!(result.imports[0].identifier.len(), 2);
-        assert!(result.imports[0].identifier.contains(&"Button".to_string()));
-        assert!(result.imports[0].identifier.contains(&"Link".to_string()));
-        assert_eq!(result.imports[0].source, "./components");
-
-        // Check FC import from react
-        assert_eq!(result.imports[1].identifier.len(), 1);
-        assert_eq!(result.imports[1].identifier[0], "FC");
-        assert_eq!(result.imports[1].source, "react");
+        let exports = result
+            .components
+            .iter()
+            .map(|component| {
+                (
+                    component.name.as_str(),
+                    component.export_kind,
+                    component.exported_name.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert!(exports.contains(&("App", types::ExportKind::Default, None)));
+        assert!(exports.contains(&("Button", types::ExportKind::Named, Some("PrimaryButton"))));
+        let app = result.components.iter().find(|c| c.name == "App").unwrap();
+        assert_eq!(app.range.start.line, 6);
     }
 
     #[test]
-    fn test_analyze_imports_namespace_specifier() {
+    fn test_analyze_late_var_initialization() {
         let source = r#"
-import * as React from "react";
-import * as Components from "./components";
-
-const App = () => <div>App</div>;
-export default App;
+var Layout, count;
+export { Layout as default };
+Layout = function () {
+  return <section />;
+};
+count = 1;
         "#;
 
         let result = analyze_tsx(source).unwrap();
 
-        assert_eq!(result.imports.len(), 2);
-
-        // Check namespace imports
-        assert_eq!(result.imports[0].identifier[0], "React");
-        assert_eq!(result.imports[1].identifier[0], "Components");
+        assert_eq!(result.components.len(), 1);
+        assert_eq!(result.components[0].name, "Layout");
+        assert_eq!(result.components[0].export_kind, types::ExportKind::Default);
+        assert_eq!(result.components[0].range.start.line, 1);
     }
 
     #[test]
-    fn test_analyze_ignores_type_imports() {
+    fn test_analyze_interop_exports_in_conditional_blocks() {
         let source = r#"
-import type { FC } from "react";
-import type { Props } from "./types";
-import { Button } from "./button";
-
-const App: FC = () => <div>App</div>;
-export default App;
+"use client";
+var Badge = () => <span />;
+var Chip = () => <span />;
+var exports_ = {};
+if (typeof module !== "undefined") {
+  __export(exports_, { Badge: () => Badge });
+}
+try {
+  __export(exports_, { Chip: () => Chip });
+} catch {}
         "#;
 
         let result = analyze_tsx(source).unwrap();
 
-        // Should only include the Button import, not type imports
-        assert_eq!(result.imports.len(), 1);
-        assert_eq!(result.imports[0].identifier[0], "Button");
-        assert_eq!(result.imports[0].source, "./button");
+        let names = result
+            .components
+            .iter()
+            .map(|component| component.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Badge", "Chip"]);
     }
 
     #[test]
-    fn test_analyze_jsx_usages() {
+    fn test_analyze_route_handlers_suppress_components() {
         let source = r#"
-import ClientComponent from "./client";
-import { Button, Link } from "./components";
-
-const App = () => {
-  return (
-    <div>
-      <ClientComponent />
-      <Button />
-      <Link />
-    </div>
-  );
-};
-
-export default App;
+export const GET = () => Response.json({ ok: true });
+export async function POST(request) {
+  return new Response(null, { status: 201 });
+}
+export const Preview = () => <div />;
         "#;
 
         let result = analyze_tsx(source).unwrap();
 
-        // Should find all three JSX usages
-        assert_eq!(result.jsx_usages.len(), 3);
-
-        let usage_names: Vec<&str> = result
-            .jsx_usages
+        let methods = result
+            .route_handlers
             .iter()
-            .map(|u| u.component_name.as_str())
-            .collect();
-
-        assert!(usage_names.contains(&"ClientComponent"));
-        assert!(usage_names.contains(&"Button"));
-        assert!(usage_names.contains(&"Link"));
+            .map(|handler| handler.method.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(methods, vec!["GET", "POST"]);
+        assert_eq!(result.route_handlers[0].range.start.line, 1);
+        assert!(
+            result.components.is_empty(),
+            "Route modules should not report components"
+        );
+        assert_eq!(result.module_kind, types::ModuleKind::RouteHandler);
     }
 
     #[test]
-    fn test_analyze_jsx_usages_filtered_to_imports() {
-        let source = r#"
-import { Button } from "./components";
-
-// LocalComponent is NOT imported, so it should NOT be in jsx_usages
-const LocalComponent = () => <div>Local</div>;
+    fn test_analyze_middleware_and_instrumentation_modules() {
+        let middleware = r#"
+import { NextResponse } from "next/server";
+const Redirect = () => NextResponse.redirect(new URL("/login"));
+export { Redirect as middleware };
+export const config = { matcher: ["/dashboard/:path*"] };
+        "#;
 
-const App = () => {
-  return (
-    <div>
-      <Button />
-      <LocalComponent />
-    </div>
-  );
-};
+        let result = analyze_tsx(middleware).unwrap();
+        assert_eq!(result.module_kind, types::ModuleKind::Middleware);
+        assert!(result.components.is_empty());
 
-export default App;
+        let instrumentation = r#"
+export async function register() {
+  await import("./tracing");
+}
         "#;
 
-        let result = analyze_tsx(source).unwrap();
-
-        // Should only include Button (imported), not LocalComponent (local)
-        assert_eq!(result.jsx_usages.len(), 1);
-        assert_eq!(result.jsx_usages[0].component_name, "Button");
+        let result = analyze_tsx(instrumentation).unwrap();
+        assert_eq!(result.module_kind, types::ModuleKind::Instrumentation);
     }
 
     #[test]
-    fn test_analyze_complete_flow() {
+    fn test_analyze_pages_router_page() {
         let source = r#"
-"use client";
-import { ServerButton } from "./server";
-import type { FC } from "react";
+import { ThemeContext } from "../theme";
 
-export const ClientButton: FC = () => {
+export default function Post({ post }) {
   return (
-    <button>
-      <ServerButton />
-    </button>
+    <ThemeContext.Provider value="dark">
+      <Article post={post} onShare={() => {}} />
+    </ThemeContext.Provider>
   );
-};
-
-const ClientHeader = () => {
-  return <header>Header</header>;
-};
+}
 
-export default ClientHeader;
+export async function getServerSideProps() {
+  return { props: { post: null } };
+}
         "#;
 
         let result = analyze_tsx(source).unwrap();
 
-        // Check imports
-        assert_eq!(result.imports.len(), 1); // type imports are ignored
-        assert_eq!(result.imports[0].identifier[0], "ServerButton");
+        assert_eq!(result.module_kind, types::ModuleKind::PagesRoute);
+        let functions = result
+            .pages_data_functions
+            .iter()
+            .map(|function| (function.name.as_str(), function.range.start.line))
+            .collect::<Vec<_>>();
+        assert_eq!(functions, vec![("getServerSideProps", 11)]);
+        assert_eq!(result.components.len(), 1);
+        assert!(result.diagnostics.is_empty());
 
-        // Check components
-        assert_eq!(result.components.len(), 2);
-        assert!(result.components.iter().all(|c| c.is_client_component));
+        // The same module in the app router renders a provider on the server
+        let app_router = source.replace("getServerSideProps", "loadPost");
+        let result = analyze_tsx(&app_router).unwrap();
+        assert_eq!(result.module_kind, types::ModuleKind::Component);
+        assert_eq!(result.diagnostics.len(), 1);
+    }
+
+    #[cfg(feature = "astro")]
+    #[test]
+    fn test_analyze_astro_islands() {
+        let source = r#"---
+import Counter from "../components/Counter";
+import Header from "../components/Header.astro";
+const { title } = Astro.props;
+---
+<Header title={title} />
+<Counter client:idle initial={3} />
+"#;
+
+        let result = analyze_with_extension(source, "astro").unwrap();
+
+        let imports = result
+            .imports
+            .iter()
+            .map(|import| import.source.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            imports,
+            vec!["../components/Counter", "../components/Header.astro"]
+        );
+        assert_eq!(result.imports[0].range.start.line, 1);
 
-        // Check JSX usages
         assert_eq!(result.jsx_usages.len(), 1);
-        assert_eq!(result.jsx_usages[0].component_name, "ServerButton");
+        let island = &result.jsx_usages[0];
+        assert_eq!(island.component_name, "Counter");
+        assert_eq!(island.import_index, Some(0));
+        assert_eq!(island.attributes[0].name, "client:idle");
+        assert_eq!(island.range.start.line, 6);
+        assert!(result.parse_errors.is_empty());
     }
 
     #[test]
-    fn test_analyze_invalid_syntax() {
-        let source = "const x = {{{";
-
-        let result = analyze_tsx(source);
+    fn test_analyze_jsx_runtimes() {
+        let source = r#"
+import { jsx as _jsx } from "react/jsx-runtime";
+export function Button() {
+  return _jsx("button", {});
+}
+        "#;
 
-        // Should return an error
-        assert!(result.is_err());
+        let result = analyze_tsx(source).unwrap();
+        assert_eq!(result.jsx_runtimes.len(), 1);
+        let usage = &result.jsx_runtimes[0];
+        assert_eq!(usage.runtime, types::JsxRuntime::Automatic);
+        assert_eq!(usage.import_ranges.len(), 1);
+        assert_eq!(usage.import_ranges[0].start.line, 1);
+
+        let result = analyze_tsx("export const App = () => <main />;").unwrap();
+        assert_eq!(result.jsx_runtimes.len(), 1);
+        assert_eq!(result.jsx_runtimes[0].runtime, types::JsxRuntime::Syntax);
+        assert!(result.jsx_runtimes[0].import_ranges.is_empty());
     }
 
     #[test]
-    fn test_analyze_invalid_extension() {
-        let source = "const x = 10;";
+    fn test_analyze_client_exports() {
+        let source = r#""use client";
+import { renderToString } from "react-dom/server";
+
+export const Button = () => <button />;
+export const html = renderToString(<Button />);
+export default function Dialog() {
+  return <dialog />;
+}
+"#;
 
-        let result = analyze_with_extension(source, "invalid");
+        let result = analyze_tsx(source).unwrap();
 
-        // Should return an error for invalid extension
-        assert!(result.is_err());
+        let exports = result
+            .client_exports
+            .iter()
+            .map(|export| (export.name.as_str(), export.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            exports,
+            vec![
+                ("Button", types::ClientExportKind::Component),
+                ("html", types::ClientExportKind::Value),
+                ("default", types::ClientExportKind::Component),
+            ]
+        );
+        assert!(result.diagnostics.iter().any(|diagnostic| diagnostic.message
+            == "Export \"html\" of a \"use client\" module references server-only \"react-dom/server\""));
+
+        let result = analyze_tsx("export const Page = () => <main />;").unwrap();
+        assert!(result.client_exports.is_empty());
     }
 
     #[test]
-    fn test_analyze_range_positions() {
-        let source = r#"
-const MyComponent = () => <div />;
-export default MyComponent;
-        "#;
+    fn test_analyze_directive_granting_re_exports() {
+        let source = r#""use client";
+export { Button, type ButtonProps } from "@mui/material";
+export { default as Dialog } from "@radix-ui/react-dialog";
+export * from "@/components/card";
+export { Icon } from "./icon";
+export { join } from "path";
+"#;
 
         let result = analyze_tsx(source).unwrap();
 
-        // Check that ranges are set correctly
-        assert_eq!(result.components.len(), 1);
-        let component = &result.components[0];
+        let re_exports = result
+            .re_exports
+            .iter()
+            .map(|re_export| {
+                (
+                    re_export.exported.as_deref().unwrap_or("*"),
+                    re_export.grants_directive,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            re_exports,
+            vec![
+                ("Button", true),
+                ("ButtonProps", false),
+                ("Dialog", true),
+                ("*", false),
+                ("Icon", false),
+                ("join", false),
+            ]
+        );
 
-        // Component name should be on line 1 (0-indexed)
-        assert_eq!(component.range.start.line, 1);
-        assert!(component.range.start.character > 0);
-        assert_eq!(component.range.end.line, 1);
+        let result = analyze_tsx(r#"export { Button } from "@mui/material";"#).unwrap();
+        assert!(!result.re_exports[0].grants_directive);
     }
 
     #[test]
-    fn test_analyze_import_source_span() {
-        let source = r#"import X from "./client";"#;
-
-        let result = analyze_tsx(source).unwrap();
+    fn test_analyze_client_weight() {
+        let source = r#""use client";
+import { useState } from "react";
+import type { Props } from "./types";
+import { cn } from "@/lib/utils";
+import { Dialog } from "@radix-ui/react-dialog";
 
-        assert_eq!(result.imports.len(), 1);
+export function Modal() {
+  return <Dialog />;
+}
+"#;
 
-        // source_span should point inside the string (after opening quote)
-        let import = &result.imports[0];
-        assert_eq!(import.source_span.start.line, 0);
+        let result = analyze_tsx(source).unwrap();
 
-        // Character should be after the opening quote
-        // "import X from "./client";"
-        //                ^--- should be around character 15
-        assert!(import.source_span.start.character >= 14);
-        assert!(import.source_span.start.character <= 16);
+        let weight = result
+            .client_weight
+            .expect("client modules report their weight");
+        assert_eq!(weight.byte_length, source.len() as u32);
+        assert_eq!(weight.import_count, 3);
+        assert_eq!(weight.third_party_import_count, 2);
+
+        let result = analyze_tsx("export function Page() { return <div />; }").unwrap();
+        assert!(result.client_weight.is_none());
     }
 
     #[test]
-    fn test_analyze_function_declaration_export() {
+    fn test_analyze_server_actions_bound_in_jsx() {
         let source = r#"
-"use client";
-
-export function MyComponent() {
-  return <div>Function Declaration</div>;
+export default function Item({ id }) {
+  async function remove(id: string, formData: FormData) {
+    "use server";
+  }
+  async function rename(formData: FormData) {
+    "use server";
+  }
+  return (
+    <form action={rename}>
+      <button formAction={remove.bind(null, id)}>Delete</button>
+    </form>
+  );
 }
         "#;
 
         let result = analyze_tsx(source).unwrap();
 
-        assert_eq!(result.components.len(), 1);
-        assert_eq!(result.components[0].name, "MyComponent");
-        assert!(result.components[0].is_client_component);
+        assert_eq!(result.server_actions.len(), 2);
+        let remove = &result.server_actions[0];
+        assert_eq!(remove.name, "remove");
+        assert_eq!(remove.parameter_count, 2);
+        assert!(remove.uses_form_data);
+        assert!(remove.bound_in_jsx);
+        assert_eq!(remove.range.start.line, 2);
+        assert!(!result.server_actions[1].bound_in_jsx);
     }
 
     #[test]
-    fn test_analyze_mixed_exports() {
+    fn test_analyze_nested_inline_action_component() {
         let source = r#"
-"use client";
-
-export const ArrowComponent = () => <div>Arrow</div>;
-export function FunctionComponent() {
-  return <div>Function</div>;
+export function TodoList({ todos }) {
+  const items = todos.map((todo) => {
+    const toggle = async (formData) => {
+      "use server";
+      await setDone(todo.id, formData.get("done"));
+    };
+    return <form action={toggle} />;
+  });
+  return <ul>{items}</ul>;
 }
-const DefaultComponent = () => <div>Default</div>;
-export default DefaultComponent;
         "#;
 
         let result = analyze_tsx(source).unwrap();
 
-        assert_eq!(result.components.len(), 3);
+        assert_eq!(result.server_actions.len(), 1);
+        let toggle = &result.server_actions[0];
+        assert_eq!(toggle.name, "toggle");
+        assert_eq!(toggle.component.as_deref(), Some("TodoList"));
+        assert_eq!(toggle.captures.len(), 1);
+        assert_eq!(toggle.captures[0].name, "todo");
+        assert_eq!(toggle.captures[0].range.start.line, 5);
+        assert_eq!(toggle.range.start.line, 3);
+    }
 
-        let names: Vec<&str> = result.components.iter().map(|c| c.name.as_str()).collect();
-        assert!(names.contains(&"ArrowComponent"));
-        assert!(names.contains(&"FunctionComponent"));
-        assert!(names.contains(&"DefaultComponent"));
+    #[test]
+    fn test_analyze_context_consumers() {
+        let source = r#""use client";
+import { useContext } from "react";
+import { ThemeContext } from "./theme";
+
+export function Toolbar() {
+  const theme = useContext(ThemeContext);
+  return <div className={theme} />;
+}
+"#;
 
-        // All should be client components
-        assert!(result.components.iter().all(|c| c.is_client_component));
+        let result = analyze_tsx(source).unwrap();
+
+        assert_eq!(result.context_consumers.len(), 1);
+        let consumer = &result.context_consumers[0];
+        assert_eq!(consumer.local, "ThemeContext");
+        assert_eq!(consumer.imported, "ThemeContext");
+        assert_eq!(consumer.source, "./theme");
+        assert_eq!(consumer.import_index, 1);
+        assert_eq!(consumer.range.start.line, 5);
     }
 
     #[test]
-    fn test_analyze_jsx_runtime_calls() {
-        // Test that bundled code using jsx() runtime calls is detected
+    fn test_analyze_value_exports() {
         let source = r#"
-"use client";
-import { jsx as _jsx } from "react/jsx-runtime";
-
-const Button = () => {
-  return _jsx("button", { children: "Click me" });
-};
-
-export default Button;
-        "#;
+export const metadata = { title: "Settings" };
+export const dynamic = "force-dynamic";
+export const Panel = () => <section />;
+export default function Page() {
+  return <Panel />;
+}
+"#;
 
         let result = analyze_tsx(source).unwrap();
 
+        assert_eq!(result.components.len(), 2);
+        let exports = result
+            .value_exports
+            .iter()
+            .map(|export| (export.name.as_str(), export.kind))
+            .collect::<Vec<_>>();
         assert_eq!(
-            result.components.len(),
-            1,
-            "Should detect component using jsx runtime call"
+            exports,
+            vec![
+                ("metadata", types::ValueKind::Object),
+                ("dynamic", types::ValueKind::String),
+            ]
         );
-        assert_eq!(result.components[0].name, "Button");
-        assert!(result.components[0].is_client_component);
+        assert_eq!(result.value_exports[0].range.start.line, 1);
     }
 
     #[test]
-    fn test_analyze_jsxs_runtime_calls() {
-        // Test jsxs (for multiple children)
+    fn test_analyze_type_exports_option() {
         let source = r#"
-"use client";
-import { jsxs as _jsxs } from "react/jsx-runtime";
-
-export const Container = () => {
-  return _jsxs("div", { children: ["Hello", "World"] });
-};
-        "#;
+export interface CardProps {
+  title: string;
+  onSelect: () => void;
+}
+export const Card = (props: CardProps) => <div />;
+"#;
 
         let result = analyze_tsx(source).unwrap();
-
+        assert!(result.type_exports.is_empty());
         assert_eq!(
-            result.components.len(),
-            1,
-            "Should detect component using jsxs runtime call"
+            result.components[0]
+                .props_type
+                .as_ref()
+                .map(|props_type| props_type.name.as_str()),
+            Some("CardProps")
         );
-        assert_eq!(result.components[0].name, "Container");
-        assert!(result.components[0].is_client_component);
+
+        let options = AnalyzeOptions {
+            include_type_exports: true,
+            ..default_options()
+        };
+        let result = analyze_source_with_options(
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            &options,
+            &|| false,
+        )
+        .unwrap();
+        assert_eq!(result.type_exports.len(), 1);
+        let members = result.type_exports[0]
+            .members
+            .iter()
+            .map(|member| (member.name.as_str(), member.non_serializable))
+            .collect::<Vec<_>>();
+        assert_eq!(members, vec![("title", false), ("onSelect", true)]);
     }
 
     #[test]
-    fn test_analyze_react_forwardref() {
-        // Test React.forwardRef with jsx runtime calls
+    fn test_analyze_debug_usages_option() {
         let source = r#"
-"use client";
-import React from "react";
-import { jsx as _jsx } from "react/jsx-runtime";
-
-const Button = React.forwardRef((props, ref) => {
-  return _jsx("button", { ref, ...props });
-});
-
-export default Button;
-        "#;
+export default function Page() {
+  console.log("rendering");
+  debugger;
+  return <div />;
+}
+"#;
 
         let result = analyze_tsx(source).unwrap();
-
+        assert!(result.debug_usages.is_empty());
+
+        let options = AnalyzeOptions {
+            include_debug_usages: true,
+            ..default_options()
+        };
+        let result = analyze_source_with_options(
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            &options,
+            &|| false,
+        )
+        .unwrap();
+        let summary = result
+            .debug_usages
+            .iter()
+            .map(|usage| {
+                (
+                    usage.kind,
+                    usage.method.as_deref(),
+                    usage.component.as_deref(),
+                    usage.directive_side,
+                )
+            })
+            .collect::<Vec<_>>();
         assert_eq!(
-            result.components.len(),
-            1,
-            "Should detect React.forwardRef component"
+            summary,
+            vec![
+                (
+                    types::DebugUsageKind::Console,
+                    Some("log"),
+                    Some("Page"),
+                    types::BoundarySide::Server
+                ),
+                (
+                    types::DebugUsageKind::Debugger,
+                    None,
+                    Some("Page"),
+                    types::BoundarySide::Server
+                ),
+            ]
+        );
+
+        let client_source = format!("\"use client\";\n{source}");
+        let result = analyze_source_with_options(
+            client_source.into_bytes(),
+            "tsx".to_string(),
+            &options,
+            &|| false,
+        )
+        .unwrap();
+        assert!(
+            result
+                .debug_usages
+                .iter()
+                .all(|usage| usage.directive_side == types::BoundarySide::Client)
         );
-        assert_eq!(result.components[0].name, "Button");
-        assert!(result.components[0].is_client_component);
     }
 
     #[test]
-    fn test_analyze_forwardref_direct_import() {
-        // Test forwardRef (direct import) with jsx runtime calls
+    fn test_analyze_hook_library() {
+        let source = r#""use client";
+import { useEffect, useState } from "react";
+
+export function useDebounce(value, delay) {
+  const [debounced, setDebounced] = useState(value);
+  useEffect(() => {
+    const timer = setTimeout(() => setDebounced(value), delay);
+    return () => clearTimeout(timer);
+  }, [value, delay]);
+  return debounced;
+}
+export const useThrottle = (value) => value;
+export const user = { name: "guest" };
+export const username = () => "guest";
+"#;
+
+        let result =
+            analyze_source(source.as_bytes().to_vec(), "js".to_string(), &|| false).unwrap();
+
+        assert!(result.components.is_empty());
+        assert!(result.is_client_module);
+        let hooks = result
+            .exported_hooks
+            .iter()
+            .map(|hook| hook.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(hooks, vec!["useDebounce", "useThrottle"]);
+        assert_eq!(result.exported_hooks[0].range.start.line, 3);
+    }
+
+    #[test]
+    fn test_analyze_local_hoc_applications() {
         let source = r#"
-"use client";
-import { forwardRef } from "react";
-import { jsx as _jsx } from "react/jsx-runtime";
+import { Dashboard } from "./dashboard";
+import { Settings } from "./settings";
 
-const Input = forwardRef((props, ref) => {
-  return _jsx("input", { ref, ...props });
-});
+const withAuth = (C) => (props) => <C {...props} />;
 
-export { Input };
-        "#;
+export const ProtectedSettings = withAuth(Settings);
+export default withAuth(Dashboard);
+"#;
 
         let result = analyze_tsx(source).unwrap();
 
+        let components = result
+            .components
+            .iter()
+            .map(|component| (component.name.as_str(), component.export_kind))
+            .collect::<Vec<_>>();
         assert_eq!(
-            result.components.len(),
-            1,
-            "Should detect forwardRef component"
+            components,
+            vec![
+                ("ProtectedSettings", types::ExportKind::Named),
+                ("Dashboard", types::ExportKind::Default),
+            ]
         );
-        assert_eq!(result.components[0].name, "Input");
-        assert!(result.components[0].is_client_component);
+
+        let usages = result
+            .jsx_usages
+            .iter()
+            .map(|usage| (usage.component_name.as_str(), usage.import_index))
+            .collect::<Vec<_>>();
+        assert_eq!(usages, vec![("Settings", Some(1)), ("Dashboard", Some(0))]);
     }
 
     #[test]
-    fn test_analyze_jsx_member_expressions() {
+    fn test_analyze_boundary_annotations() {
         let source = r#"
-import { AlertDialog } from "radix-ui";
+import dynamic from "next/dynamic";
 
-const App = () => {
-  return (
-    <div>
-      <AlertDialog.Root>
-        <AlertDialog.Trigger>Open</AlertDialog.Trigger>
-        <AlertDialog.Content>
-          <AlertDialog.Title>Alert</AlertDialog.Title>
-        </AlertDialog.Content>
-      </AlertDialog.Root>
-    </div>
-  );
-};
+/** @react-boundary client */
+export const Chart = dynamic(() => import("./chart"));
 
-export default App;
-        "#;
+/** @react-boundary ignore */
+export function Skeleton() {
+  return <div />;
+}
 
-        let result = analyze_tsx(source).unwrap();
+export function Card() {
+  return <div />;
+}
+"#;
 
-        // Should detect the AlertDialog import
-        assert_eq!(result.imports.len(), 1);
-        assert_eq!(result.imports[0].identifier[0], "AlertDialog");
-        assert_eq!(result.imports[0].source, "radix-ui");
+        let result = analyze_tsx(source).unwrap();
 
-        // Should detect all 4 member expression usages (Root, Trigger, Content, Title)
-        // All of them should be tracked as "AlertDialog" (the base identifier)
+        let components = result
+            .components
+            .iter()
+            .map(|component| {
+                (
+                    component.name.as_str(),
+                    component.is_client_component,
+                    component.annotation,
+                )
+            })
+            .collect::<Vec<_>>();
         assert_eq!(
-            result.jsx_usages.len(),
-            4,
-            "Should detect 4 AlertDialog member expression usages"
-        );
-        assert!(
-            result
-                .jsx_usages
-                .iter()
-                .all(|u| u.component_name == "AlertDialog")
+            components,
+            vec![
+                ("Card", false, None),
+                ("Chart", true, Some(types::BoundaryAnnotation::Client)),
+            ]
         );
     }
 
     #[test]
-    fn test_analyze_compiled_jsx_sequence_expression() {
-        // Test the compiled pattern found in npm packages like radix-ui:
-        // (0, _jsx)("div", props) - SequenceExpression wrapped in parentheses
+    fn test_analyze_dynamic_boundaries() {
         let source = r#"
-"use client";
-import { jsx as _jsx } from "react/jsx-runtime";
+import dynamic from "next/dynamic";
 
-const AlertDialog = (props) => {
-  return (0, _jsx)("div", {children: "test"});
-};
+const Map = dynamic(() => import("./map"), { ssr: false });
 
-export default AlertDialog;
-        "#;
+export default function Page() {
+  return <Map onSelect={() => {}} />;
+}
+"#;
 
         let result = analyze_tsx(source).unwrap();
 
-        assert_eq!(
-            result.components.len(),
-            1,
-            "Should detect component using compiled jsx runtime pattern"
-        );
-        assert_eq!(result.components[0].name, "AlertDialog");
-        assert!(result.components[0].is_client_component);
+        assert_eq!(result.dynamic_boundaries.len(), 1);
+        let boundary = &result.dynamic_boundaries[0];
+        assert_eq!(boundary.name, "Map");
+        assert_eq!(boundary.source, "./map");
+        assert!(!boundary.ssr);
+        assert_eq!(boundary.source_span.start.line, 3);
+        assert_eq!(boundary.source_span.start.character, 34);
+        // The component isn't imported, so its props aren't diagnosed as crossing the boundary
+        assert!(result.diagnostics.is_empty());
+
+        let result = analyze_tsx(&format!("\"use client\";\n{}", source)).unwrap();
+        assert!(result.dynamic_boundaries.is_empty());
     }
 
     #[test]
-    fn test_analyze_renamed_jsx_import() {
-        // Test that renamed jsx runtime imports work correctly
-        // import { jsx as foobar } from "react/jsx-runtime"
+    fn test_analyze_statistics() {
         let source = r#"
-"use client";
-import { jsx as foobar, jsxs as barfoo } from "react/jsx-runtime";
-
-const MyComponent = () => {
-  return foobar("div", {children: "Hello"});
-};
-
-const MyOtherComponent = () => {
-  return barfoo("div", {children: ["Hello", "World"]});
-};
+import { Row } from "./row";
 
-export { MyComponent, MyOtherComponent };
-        "#;
+export function Table({ rows }) {
+  return <table>{rows.map((row) => <Row key={row.id} />)}</table>;
+}
+"#;
+
+        let options = AnalyzeOptions {
+            include_statistics: true,
+            ..default_options()
+        };
+        let result = analyze_source_with_options(
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            &options,
+            &|| false,
+        )
+        .unwrap();
+
+        let statistics = result.statistics.expect("statistics were requested");
+        assert_eq!(statistics.function_count, 2);
+        assert_eq!(statistics.jsx_element_count, 2);
+        assert_eq!(statistics.call_expression_count, 1);
+        assert!(statistics.max_depth > 0);
+
+        assert!(analyze_tsx(source).unwrap().statistics.is_none());
+    }
 
-        let result = analyze_tsx(source).unwrap();
+    #[test]
+    fn test_analyze_known_client_packages() {
+        let source = r#"
+import { motion } from "framer-motion";
 
+export function Hero() {
+  return <motion.div />;
+}
+"#;
+
+        let options = AnalyzeOptions {
+            client_packages: vec!["framer-motion".to_string()],
+            ..default_options()
+        };
+        let analyze = |source: &str| {
+            analyze_source_with_options(
+                source.as_bytes().to_vec(),
+                "tsx".to_string(),
+                &options,
+                &|| false,
+            )
+            .unwrap()
+        };
+
+        let result = analyze(source);
+        assert_eq!(result.signals.len(), 1);
         assert_eq!(
-            result.components.len(),
-            2,
-            "Should detect both components with renamed jsx imports"
-        );
-        assert!(result.components.iter().any(|c| c.name == "MyComponent"));
-        assert!(
-            result
-                .components
-                .iter()
-                .any(|c| c.name == "MyOtherComponent")
+            result.signals[0].kind,
+            types::SignalKind::ClientPackageImport
         );
-        assert!(result.components.iter().all(|c| c.is_client_component));
+        assert_eq!(result.signals[0].name, "framer-motion");
+        assert!(result.diagnostics.is_empty());
+
+        // Client modules are already on the client side of the boundary
+        let result = analyze(&format!("\"use client\";{}", source));
+        assert!(result.signals.is_empty());
     }
 
     #[test]
-    fn test_analyze_inline_export_default_function() {
-        // Test export default function (inline declaration, not reference)
-        let source = r#"
-"use client";
+    fn test_analyze_known_server_packages_in_client_module() {
+        let source = r#""use client";
+import { Pool } from "pg";
 
-export default function MyComponent() {
-  return <div>Hello</div>;
+export function Users() {
+  return <ul />;
 }
-        "#;
-
-        let result = analyze_tsx(source).unwrap();
-
+"#;
+
+        let options = AnalyzeOptions {
+            server_packages: vec!["pg".to_string()],
+            ..default_options()
+        };
+        let result = analyze_source_with_options(
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            &options,
+            &|| false,
+        )
+        .unwrap();
+
+        assert_eq!(result.signals.len(), 1);
         assert_eq!(
-            result.components.len(),
-            1,
-            "Should detect inline export default function declaration"
+            result.signals[0].kind,
+            types::SignalKind::ServerPackageImport
+        );
+        assert_eq!(result.diagnostics.len(), 1);
+        assert!(
+            result.diagnostics[0]
+                .message
+                .contains("\"pg\" is a server-only package")
         );
-        assert_eq!(result.components[0].name, "MyComponent");
-        assert!(result.components[0].is_client_component);
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn test_analyze_multiple_components_with_inline_default() {
-        // Test file with both named exports and inline default export
+    fn test_analysis_result_serde_round_trip() {
         let source = r#"
 "use client";
-import type { FC } from "react";
-
-export const ClientUsesClientNamedFunction: FC = () => {
-  return <div>Named export</div>;
-};
+import { Button } from "./button";
 
-export default function ClientUsesClientDefaultFunction() {
-  return <div>Default export</div>;
-}
+export const App = () => <Button />;
         "#;
 
         let result = analyze_tsx(source).unwrap();
 
+        let json = serde_json::to_string(&result).unwrap();
+        let decoded: AnalysisResult = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(decoded.imports.len(), 1);
+        assert_eq!(decoded.imports[0].source, "./button");
+        assert_eq!(decoded.components.len(), 1);
+        assert_eq!(decoded.components[0].name, "App");
+        assert_eq!(decoded.jsx_usages.len(), 1);
         assert_eq!(
-            result.components.len(),
-            2,
-            "Should detect both named and inline default export"
+            decoded.jsx_usages[0].range.start.line,
+            result.jsx_usages[0].range.start.line
+        );
+        assert_eq!(
+            decoded.jsx_usages[0].range.start.character,
+            result.jsx_usages[0].range.start.character
         );
-
-        let names: Vec<&str> = result.components.iter().map(|c| c.name.as_str()).collect();
-        assert!(names.contains(&"ClientUsesClientNamedFunction"));
-        assert!(names.contains(&"ClientUsesClientDefaultFunction"));
-
-        // All should be client components
-        assert!(result.components.iter().all(|c| c.is_client_component));
     }
 }
@@ -0,0 +1,98 @@
+use crate::analyze_react_boundary::check::types;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Convert a file location to an LSP `Location`, whose `uri` is a `file://` URI the editor can
+/// open. Paths that already are URIs (`untitled:`, `vscode-vfs://`) are kept as they are
+pub(crate) fn to_lsp_location(location: types::FileLocation) -> types::LspLocation {
+    types::LspLocation {
+        uri: file_uri(&location.path),
+        range: location.range,
+    }
+}
+
+/// Locations of a boundary violation across files: the offending JSX usage first, then every
+/// import of the chain leading to the client module
+pub(crate) fn violation_locations(
+    path: &str,
+    range: types::Range,
+    chain: &[types::ImportHop],
+) -> Vec<types::FileLocation> {
+    std::iter::once(types::FileLocation {
+        path: path.to_string(),
+        range,
+    })
+    .chain(chain.iter().map(|hop| types::FileLocation {
+        path: hop.path.clone(),
+        range: hop.range,
+    }))
+    .collect()
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// `file://` URI of an absolute POSIX or Windows path, percent-encoding everything but unreserved
+/// characters and separators
+fn file_uri(path: &str) -> String {
+    if has_scheme(path) {
+        return path.to_string();
+    }
+
+    let path = path.replace('\\', "/");
+    let mut uri = String::from("file://");
+    // Windows paths start with a drive letter, which the URI path puts after a slash
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+/// Whether `path` starts with a URI scheme longer than a drive letter (`untitled:`, `file://`)
+fn has_scheme(path: &str) -> bool {
+    path.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.len() > 1
+            && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_uri() {
+        assert_eq!(file_uri("/app/page.tsx"), "file:///app/page.tsx");
+        assert_eq!(
+            file_uri("/app/(marketing)/my page.tsx"),
+            "file:///app/%28marketing%29/my%20page.tsx"
+        );
+        assert_eq!(
+            file_uri("C:\\project\\app\\page.tsx"),
+            "file:///C%3A/project/app/page.tsx"
+        );
+        assert_eq!(file_uri("untitled:Untitled-1"), "untitled:Untitled-1");
+        assert_eq!(
+            file_uri("vscode-vfs://github/app/page.tsx"),
+            "vscode-vfs://github/app/page.tsx"
+        );
+    }
+}
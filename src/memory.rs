@@ -0,0 +1,232 @@
+#[cfg(any(target_arch = "wasm32", feature = "tracking-allocator"))]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Cap applied when the host doesn't configure one. Well below the 4 GiB a 32-bit linear memory
+/// can address, so a single pathological file is rejected long before the component traps.
+pub(crate) const DEFAULT_MEMORY_LIMIT: u64 = 1024 * 1024 * 1024;
+
+/// Estimate of the bytes parsing allocates per byte of source, dense minified code being the
+/// worst case. Checked before parsing, since the AST is built in one go and a file that
+/// overshoots the cap would trap before the next checkpoint is reached. Under the default cap,
+/// sources up to 64 MiB are parsed.
+const PARSE_BYTES_PER_SOURCE_BYTE: u64 = 16;
+
+/// Prefix of the error returned when a file exceeds its memory budget. Hosts match on it to tell
+/// a file that is too complex to analyze apart from one that failed to parse.
+pub(crate) const FILE_TOO_COMPLEX: &str = "file-too-complex";
+
+/// Whether allocations are counted. The component always counts them; native builds only with the
+/// `tracking-allocator` feature, since a library can't impose a global allocator on the binaries
+/// linking it. Without it the budget is unavailable and never rejects an analysis
+const TRACKING: bool = cfg!(any(target_arch = "wasm32", feature = "tracking-allocator"));
+
+#[cfg(any(target_arch = "wasm32", feature = "tracking-allocator"))]
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+thread_local! {
+    /// Bytes currently allocated by this thread
+    static ALLOCATED: Cell<u64> = const { Cell::new(0) };
+    /// Highest value of `ALLOCATED` since the current budget started
+    static PEAK: Cell<u64> = const { Cell::new(0) };
+    /// Value of `ALLOCATED` past which the current budget is exceeded
+    static THRESHOLD: Cell<u64> = const { Cell::new(u64::MAX) };
+    /// Whether an allocation crossed `THRESHOLD` since the current budget started
+    static EXCEEDED: Cell<bool> = const { Cell::new(false) };
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Memory budget of a single file's analysis.
+///
+/// Allocations are counted, not refused: refusing one would abort the whole component. Instead
+/// the allocator flags the budget as soon as an allocation crosses it, the JSX walk stops at its
+/// next checkpoint, and the analysis gives up with a [`FILE_TOO_COMPLEX`] error between passes,
+/// so the cap is deterministic for a given file and analyzer build.
+pub(crate) struct MemoryBudget {
+    /// `None` when allocations aren't counted
+    limit: Option<u64>,
+    baseline: u64,
+}
+
+impl MemoryBudget {
+    /// Start counting the allocations of an analysis against `limit` bytes
+    pub(crate) fn start(limit: Option<u64>) -> Self {
+        let baseline = ALLOCATED.with(Cell::get);
+        let limit = TRACKING.then(|| limit.unwrap_or(DEFAULT_MEMORY_LIMIT));
+        PEAK.with(|peak| peak.set(baseline));
+        THRESHOLD.with(|threshold| {
+            threshold.set(limit.map_or(u64::MAX, |limit| baseline.saturating_add(limit)))
+        });
+        EXCEEDED.with(|exceeded| exceeded.set(false));
+        Self { limit, baseline }
+    }
+
+    /// Reject a source the parser can't be expected to fit in the budget
+    pub(crate) fn check_source(&self, source_len: usize) -> Result<(), String> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+        let estimate = (source_len as u64).saturating_mul(PARSE_BYTES_PER_SOURCE_BYTE);
+        if estimate > limit {
+            return Err(too_complex(estimate, limit));
+        }
+        Ok(())
+    }
+
+    /// Return early if the analysis so far has used more than its budget
+    pub(crate) fn check(&self) -> Result<(), String> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+        let used = self.peak();
+        if used > limit {
+            return Err(too_complex(used, limit));
+        }
+        Ok(())
+    }
+
+    /// Most bytes held at once since the budget started
+    pub(crate) fn peak(&self) -> u64 {
+        PEAK.with(Cell::get).saturating_sub(self.baseline)
+    }
+}
+
+impl Drop for MemoryBudget {
+    fn drop(&mut self) {
+        THRESHOLD.with(|threshold| threshold.set(u64::MAX));
+    }
+}
+
+/// Whether an allocation crossed the current budget. Cheap enough for the checkpoints inside a
+/// pass, which stop walking so the analysis can give up at the next check
+pub(crate) fn exceeded() -> bool {
+    EXCEEDED.with(Cell::get)
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn too_complex(bytes: u64, limit: u64) -> String {
+    format!(
+        "{}: analysis needs {} bytes, over the limit of {} bytes",
+        FILE_TOO_COMPLEX, bytes, limit
+    )
+}
+
+/// The system allocator, counting the bytes held by each thread
+#[cfg(any(target_arch = "wasm32", feature = "tracking-allocator"))]
+struct TrackingAllocator;
+
+#[cfg(any(target_arch = "wasm32", feature = "tracking-allocator"))]
+fn record_alloc(size: usize) {
+    // `try_with` so allocations made while thread locals are torn down are still served
+    let _ = ALLOCATED.try_with(|allocated| {
+        let now = allocated.get() + size as u64;
+        allocated.set(now);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(now)));
+        if THRESHOLD
+            .try_with(Cell::get)
+            .is_ok_and(|threshold| now > threshold)
+        {
+            let _ = EXCEEDED.try_with(|exceeded| exceeded.set(true));
+        }
+    });
+}
+
+#[cfg(any(target_arch = "wasm32", feature = "tracking-allocator"))]
+fn record_dealloc(size: usize) {
+    // Memory freed by another thread than the one that allocated it can't be attributed
+    let _ =
+        ALLOCATED.try_with(|allocated| allocated.set(allocated.get().saturating_sub(size as u64)));
+}
+
+#[cfg(any(target_arch = "wasm32", feature = "tracking-allocator"))]
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(any(target_arch = "wasm32", feature = "tracking-allocator"))]
+    fn test_budget_tracks_peak_allocation() {
+        let budget = MemoryBudget::start(Some(1024 * 1024));
+
+        let buffer = vec![0u8; 64 * 1024];
+        drop(buffer);
+
+        assert!(budget.peak() >= 64 * 1024);
+        assert!(budget.check().is_ok());
+
+        assert!(!exceeded());
+        let buffer = vec![0u8; 2 * 1024 * 1024];
+        drop(buffer);
+
+        assert!(exceeded());
+        let error = budget.check().unwrap_err();
+        assert!(error.starts_with(FILE_TOO_COMPLEX), "error: {}", error);
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "wasm32", feature = "tracking-allocator"))]
+    fn test_budget_rejects_oversized_source() {
+        let budget = MemoryBudget::start(Some(128 * 1024));
+
+        assert!(budget.check_source(8 * 1024).is_ok());
+        assert!(
+            budget
+                .check_source(16 * 1024)
+                .unwrap_err()
+                .starts_with(FILE_TOO_COMPLEX)
+        );
+    }
+
+    #[test]
+    #[cfg(not(any(target_arch = "wasm32", feature = "tracking-allocator")))]
+    fn test_budget_unavailable_without_tracking() {
+        let budget = MemoryBudget::start(Some(1));
+
+        assert!(budget.check_source(usize::MAX).is_ok());
+        assert!(budget.check().is_ok());
+        assert!(!exceeded());
+    }
+}
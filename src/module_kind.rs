@@ -0,0 +1,111 @@
+use crate::analyze_react_boundary::check::types;
+use oxc::span::Span;
+
+/// Exports Next.js reads from `middleware.ts`
+const MIDDLEWARE_EXPORTS: [&str; 2] = ["middleware", "config"];
+/// Exports Next.js reads from `instrumentation.ts`
+const INSTRUMENTATION_EXPORTS: [&str; 2] = ["register", "onRequestError"];
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Classify a module by what it exports, so hosts can skip client/server component
+/// classification for modules that never render:
+/// - route handlers export HTTP methods
+/// - middleware exports `middleware` and optionally its `config` matcher
+/// - instrumentation exports `register` and optionally `onRequestError`
+/// - pages router pages define data fetching functions (`getServerSideProps`, ...). They still
+///   render, but are not React Server Components
+///
+/// Middleware and instrumentation require every export to belong to the convention, so a
+/// component module that happens to export a `register` helper stays a component module.
+pub(crate) fn classify_module(
+    exports: &[(String, Span)],
+    route_handlers: &[types::RouteHandler],
+    pages_data_functions: &[types::PagesDataFunction],
+) -> types::ModuleKind {
+    if !route_handlers.is_empty() {
+        return types::ModuleKind::RouteHandler;
+    }
+    if !pages_data_functions.is_empty() {
+        return types::ModuleKind::PagesRoute;
+    }
+
+    let exports_only = |allowed: &[&str], required: &str| {
+        exports.iter().any(|(name, _)| name == required)
+            && exports
+                .iter()
+                .all(|(name, _)| allowed.contains(&name.as_str()))
+    };
+
+    if exports_only(&MIDDLEWARE_EXPORTS, "middleware") {
+        types::ModuleKind::Middleware
+    } else if exports_only(&INSTRUMENTATION_EXPORTS, "register") {
+        types::ModuleKind::Instrumentation
+    } else {
+        types::ModuleKind::Component
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(names: &[&str]) -> types::ModuleKind {
+        let exports = names
+            .iter()
+            .map(|name| (name.to_string(), Span::default()))
+            .collect::<Vec<_>>();
+        classify_module(&exports, &[], &[])
+    }
+
+    #[test]
+    fn test_classify_middleware() {
+        assert_eq!(
+            classify(&["middleware", "config"]),
+            types::ModuleKind::Middleware
+        );
+        assert_eq!(classify(&["middleware"]), types::ModuleKind::Middleware);
+        // A config export alone is a route segment config, not middleware
+        assert_eq!(classify(&["config"]), types::ModuleKind::Component);
+    }
+
+    #[test]
+    fn test_classify_instrumentation() {
+        assert_eq!(
+            classify(&["register", "onRequestError"]),
+            types::ModuleKind::Instrumentation
+        );
+        assert_eq!(
+            classify(&["register", "RegisterForm"]),
+            types::ModuleKind::Component
+        );
+    }
+
+    #[test]
+    fn test_classify_route_handler_wins() {
+        let handler = types::RouteHandler {
+            method: "GET".to_string(),
+            range: types::Range {
+                start: types::Position {
+                    line: 0,
+                    character: 0,
+                },
+                end: types::Position {
+                    line: 0,
+                    character: 3,
+                },
+            },
+        };
+
+        assert_eq!(
+            classify_module(&[("GET".to_string(), Span::default())], &[handler], &[]),
+            types::ModuleKind::RouteHandler
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! Native entry points for using the analyzer from Rust (CLI tools, benchmarks, tests) instead of
+//! through the WASM component interface.
+
+use crate::{AnalyzeReactBoundary, Guest};
+
+pub use crate::analyze_react_boundary::check::types::{
+    AnalysisResult, ComponentAnalysis, ExportKind, FileAnalysis, ImportAnalysis, JsxUsage,
+    Position, Range, SourceFile,
+};
+pub use crate::jsx::collect_jsx_usages;
+pub use crate::range::span_to_range;
+
+/// Analyze a source file, same as the exported WASM `analyze` function
+pub fn analyze(content: &[u8], extension: &str) -> Result<AnalysisResult, String> {
+    AnalyzeReactBoundary::analyze(content.to_vec(), extension.to_string())
+}
+
+/// Analyze a batch of files on up to `workers` threads, partitioned the same way as the exported
+/// WASM `partition-batch` function. Results are returned in the order of `files`.
+pub fn analyze_batch(files: Vec<SourceFile>, workers: u32) -> Vec<FileAnalysis> {
+    let sizes = files
+        .iter()
+        .map(|file| file.content.len() as u64)
+        .collect::<Vec<_>>();
+    let partitions = crate::batch::partition_batch(&sizes, workers);
+
+    let mut files = files.into_iter().map(Some).collect::<Vec<_>>();
+    let mut results: Vec<Option<FileAnalysis>> = (0..files.len()).map(|_| None).collect();
+
+    std::thread::scope(|scope| {
+        let handles = partitions
+            .iter()
+            .map(|partition| {
+                let partition_files = partition
+                    .iter()
+                    .filter_map(|&index| files[index as usize].take())
+                    .collect::<Vec<_>>();
+                scope.spawn(move || {
+                    crate::batch::analyze_batch(partition_files, &|| false, &|_, _, _| {})
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for (partition, handle) in partitions.iter().zip(handles) {
+            let partition_results = handle.join().expect("Batch worker panicked");
+            for (&index, result) in partition.iter().zip(partition_results) {
+                results[index as usize] = Some(result);
+            }
+        }
+    });
+
+    results.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_batch_parallel_keeps_order() {
+        let files = (0..20)
+            .map(|i| SourceFile {
+                path: format!("component{i}.tsx"),
+                content: format!("export const Component{i} = () => <div />;").into_bytes(),
+                extension: "tsx".to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        let results = analyze_batch(files, 4);
+
+        assert_eq!(results.len(), 20);
+        for (i, result) in results.iter().enumerate() {
+            assert_eq!(result.path, format!("component{i}.tsx"));
+            let analysis = result.analysis.as_ref().unwrap();
+            assert_eq!(analysis.components[0].name, format!("Component{i}"));
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use crate::component;
+use oxc::ast::ast::{
+    BindingPatternKind, Declaration, ExportDefaultDeclarationKind, Expression, FunctionBody,
+    Statement,
+};
+use oxc::span::Span;
+use std::collections::HashSet;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// A component declared in the render function of another component
+pub(crate) struct NestedComponent {
+    pub(crate) name: String,
+    /// Span of the nested declaration identifier
+    pub(crate) span: Span,
+    /// Name of the component declaring it
+    pub(crate) parent: String,
+    /// Span of the parent declaration identifier
+    pub(crate) parent_span: Span,
+}
+
+/// Collect the components declared at the top of a top-level component's body
+/// (`const Inner = () => <div />` inside `Outer`). Each render of the parent creates a new
+/// component type, so React remounts the nested one and drops its state every time. Only
+/// function values count, not elements assigned to a capitalized name (`const Icon = <svg />`)
+pub(crate) fn collect_nested_components(
+    statements: &[Statement],
+    jsx_runtime_identifiers: &HashSet<String>,
+    component_types: &[String],
+) -> Vec<NestedComponent> {
+    let mut nested = Vec::new();
+
+    for (parent, parent_span, body) in
+        component_bodies(statements, jsx_runtime_identifiers, component_types)
+    {
+        for (name, span) in
+            component_declarations(&body.statements, jsx_runtime_identifiers, component_types)
+        {
+            nested.push(NestedComponent {
+                name,
+                span,
+                parent: parent.clone(),
+                parent_span,
+            });
+        }
+    }
+
+    nested
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Names, spans and bodies of the top-level function components, exported or not
+fn component_bodies<'s, 'a>(
+    statements: &'s [Statement<'a>],
+    jsx_runtime_identifiers: &HashSet<String>,
+    component_types: &[String],
+) -> Vec<(String, Span, &'s FunctionBody<'a>)> {
+    let mut bodies = Vec::new();
+
+    for statement in statements {
+        let declaration = match statement {
+            Statement::ExportNamedDeclaration(export_decl) => export_decl.declaration.as_ref(),
+            Statement::ExportDefaultDeclaration(export_decl) => {
+                if let ExportDefaultDeclarationKind::FunctionDeclaration(func) =
+                    &export_decl.declaration
+                    && let Some((name, span)) = component::analyze_function_declaration(
+                        func,
+                        jsx_runtime_identifiers,
+                        component_types,
+                    )
+                    && let Some(body) = &func.body
+                {
+                    bodies.push((name, span, &**body));
+                }
+                continue;
+            }
+            statement => statement.as_declaration(),
+        };
+
+        match declaration {
+            Some(Declaration::FunctionDeclaration(func)) => {
+                if let Some((name, span)) = component::analyze_function_declaration(
+                    func,
+                    jsx_runtime_identifiers,
+                    component_types,
+                ) && let Some(body) = &func.body
+                {
+                    bodies.push((name, span, &**body));
+                }
+            }
+            Some(Declaration::VariableDeclaration(var_decl)) => {
+                for declarator in var_decl.declarations.iter() {
+                    let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind else {
+                        continue;
+                    };
+                    if !component::is_react_component(
+                        &ident.name,
+                        &declarator.id,
+                        &declarator.init,
+                        jsx_runtime_identifiers,
+                        component_types,
+                    ) {
+                        continue;
+                    }
+                    let body = match declarator
+                        .init
+                        .as_ref()
+                        .map(|init| init.get_inner_expression())
+                    {
+                        Some(Expression::ArrowFunctionExpression(arrow)) if !arrow.expression => {
+                            &arrow.body
+                        }
+                        Some(Expression::FunctionExpression(func)) => match &func.body {
+                            Some(body) => body,
+                            None => continue,
+                        },
+                        _ => continue,
+                    };
+                    bodies.push((ident.name.to_string(), ident.span, &**body));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    bodies
+}
+
+/// Components declared directly in a function body, as functions or HOC applications
+fn component_declarations(
+    statements: &[Statement],
+    jsx_runtime_identifiers: &HashSet<String>,
+    component_types: &[String],
+) -> Vec<(String, Span)> {
+    let mut declarations = Vec::new();
+
+    for statement in statements {
+        match statement {
+            Statement::FunctionDeclaration(func) => {
+                declarations.extend(component::analyze_function_declaration(
+                    func,
+                    jsx_runtime_identifiers,
+                    component_types,
+                ));
+            }
+            Statement::VariableDeclaration(var_decl) => {
+                for declarator in var_decl.declarations.iter() {
+                    let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind else {
+                        continue;
+                    };
+                    let is_function = matches!(
+                        declarator
+                            .init
+                            .as_ref()
+                            .map(|init| init.get_inner_expression()),
+                        Some(
+                            Expression::ArrowFunctionExpression(_)
+                                | Expression::FunctionExpression(_)
+                                | Expression::CallExpression(_)
+                        )
+                    );
+                    if is_function
+                        && component::is_react_component(
+                            &ident.name,
+                            &declarator.id,
+                            &declarator.init,
+                            jsx_runtime_identifiers,
+                            component_types,
+                        )
+                    {
+                        declarations.push((ident.name.to_string(), ident.span));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    declarations
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_collect_nested_components() {
+        let source = r#"
+export function List({ items }) {
+  const Row = ({ item }) => <li>{item}</li>;
+  const Icon = <svg />;
+  const label = () => "items";
+  return <ul>{items.map((item) => <Row item={item} />)}</ul>;
+}
+const Page = () => {
+  function Header() {
+    return <header />;
+  }
+  const Memoized = memo(() => <footer />);
+  return <Header />;
+};
+export default function App() {
+  return <Page />;
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let nested = collect_nested_components(&ret.program.body, &HashSet::new(), &[]);
+
+        let summary = nested
+            .iter()
+            .map(|component| {
+                (
+                    component.name.as_str(),
+                    component.parent.as_str(),
+                    &source
+                        [component.parent_span.start as usize..component.parent_span.end as usize],
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("Row", "List", "List"),
+                ("Header", "Page", "Page"),
+                ("Memoized", "Page", "Page"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use crate::analyze_react_boundary::check::types;
+use oxc::allocator::Allocator;
+use oxc::ast::ast::{ArrayExpressionElement, Expression, ObjectPropertyKind, PropertyKey};
+use oxc::parser::Parser;
+use oxc::span::SourceType;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Resolve a subpath of a package through its package.json `exports` field, once with the
+/// `react-server` condition and once without, so the host can tell whether the package ships a
+/// separate server build and analyze the right file.
+///
+/// `conditions` are extra conditions active in both resolutions (e.g. `import`, `browser`).
+/// Packages without an `exports` field fall back to `main` for the root subpath.
+pub(crate) fn resolve_package_export(
+    package_json: &str,
+    subpath: &str,
+    conditions: &[String],
+) -> Result<types::ExportResolution, String> {
+    let allocator = Allocator::default();
+    let expression = Parser::new(&allocator, package_json, SourceType::mjs())
+        .parse_expression()
+        .map_err(|errors| {
+            let message = errors
+                .first()
+                .map(|error| error.message.to_string())
+                .unwrap_or_default();
+            format!("Invalid package.json: {}", message)
+        })?;
+    let package = JsonValue::from_expression(&expression);
+
+    let subpath = normalize_subpath(subpath);
+    let conditions = conditions.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let resolve = |extra: &[&str]| -> Option<String> {
+        let active = conditions.iter().chain(extra).copied().collect::<Vec<_>>();
+        match package.get("exports") {
+            Some(exports) => resolve_exports(exports, &subpath, &active),
+            None if subpath == "." => package
+                .get("main")
+                .and_then(JsonValue::as_str)
+                .map(str::to_string),
+            None => None,
+        }
+    };
+
+    let default = resolve(&["default"]);
+    let react_server = resolve(&["react-server", "default"]);
+
+    Ok(types::ExportResolution {
+        has_separate_server_build: react_server.is_some() && react_server != default,
+        react_server,
+        default,
+    })
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// The subset of JSON needed to walk an `exports` field, keeping object key order since
+/// conditions are matched in order
+enum JsonValue {
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+    Other,
+}
+
+impl JsonValue {
+    fn from_expression(expr: &Expression) -> JsonValue {
+        match expr {
+            Expression::StringLiteral(lit) => JsonValue::String(lit.value.to_string()),
+            Expression::ArrayExpression(array) => JsonValue::Array(
+                array
+                    .elements
+                    .iter()
+                    .map(|element| match element {
+                        ArrayExpressionElement::SpreadElement(_)
+                        | ArrayExpressionElement::Elision(_) => JsonValue::Other,
+                        element => element
+                            .as_expression()
+                            .map_or(JsonValue::Other, JsonValue::from_expression),
+                    })
+                    .collect(),
+            ),
+            Expression::ObjectExpression(object) => JsonValue::Object(
+                object
+                    .properties
+                    .iter()
+                    .filter_map(|property| match property {
+                        ObjectPropertyKind::ObjectProperty(prop) => {
+                            let key = match &prop.key {
+                                PropertyKey::StringLiteral(lit) => lit.value.to_string(),
+                                PropertyKey::StaticIdentifier(ident) => ident.name.to_string(),
+                                _ => return None,
+                            };
+                            Some((key, JsonValue::from_expression(&prop.value)))
+                        }
+                        ObjectPropertyKind::SpreadProperty(_) => None,
+                    })
+                    .collect(),
+            ),
+            Expression::ParenthesizedExpression(paren) => {
+                JsonValue::from_expression(&paren.expression)
+            }
+            _ => JsonValue::Other,
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+fn normalize_subpath(subpath: &str) -> String {
+    match subpath {
+        "" | "." | "./" => ".".to_string(),
+        subpath if subpath.starts_with("./") => subpath.to_string(),
+        subpath => format!("./{}", subpath.trim_start_matches('/')),
+    }
+}
+
+/// Resolve a subpath against the `exports` field value
+fn resolve_exports(exports: &JsonValue, subpath: &str, conditions: &[&str]) -> Option<String> {
+    // Subpath keys all start with ".", otherwise the whole field is the "." entry
+    let is_subpath_map = match exports {
+        JsonValue::Object(entries) => entries.iter().any(|(key, _)| key.starts_with('.')),
+        _ => false,
+    };
+
+    if !is_subpath_map {
+        return (subpath == ".")
+            .then(|| resolve_target(exports, "", conditions))
+            .flatten();
+    }
+
+    let JsonValue::Object(entries) = exports else {
+        return None;
+    };
+
+    if let Some((_, target)) = entries.iter().find(|(key, _)| key == subpath) {
+        return resolve_target(target, "", conditions);
+    }
+
+    // Pattern keys: the one with the longest prefix before "*" wins
+    entries
+        .iter()
+        .filter_map(|(key, target)| {
+            let (prefix, suffix) = key.split_once('*')?;
+            let matched = subpath.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            Some((prefix.len(), matched, target))
+        })
+        .max_by_key(|(prefix_len, _, _)| *prefix_len)
+        .and_then(|(_, matched, target)| resolve_target(target, matched, conditions))
+}
+
+/// Resolve a target value, picking the first matching condition in key order
+fn resolve_target(target: &JsonValue, pattern_match: &str, conditions: &[&str]) -> Option<String> {
+    match target {
+        JsonValue::String(path) => Some(path.replace('*', pattern_match)),
+        JsonValue::Array(targets) => targets
+            .iter()
+            .find_map(|target| resolve_target(target, pattern_match, conditions)),
+        JsonValue::Object(entries) => entries
+            .iter()
+            .filter(|(condition, _)| conditions.contains(&condition.as_str()))
+            .find_map(|(_, target)| resolve_target(target, pattern_match, conditions)),
+        JsonValue::Other => None,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(package_json: &str, subpath: &str) -> types::ExportResolution {
+        resolve_package_export(package_json, subpath, &["import".to_string()]).unwrap()
+    }
+
+    #[test]
+    fn test_resolve_react_server_condition() {
+        let package_json = r#"{
+            "name": "ui",
+            "exports": {
+                ".": {
+                    "react-server": "./dist/index.server.js",
+                    "import": "./dist/index.mjs",
+                    "default": "./dist/index.js"
+                }
+            }
+        }"#;
+
+        let resolution = resolve(package_json, ".");
+
+        assert_eq!(
+            resolution.react_server.as_deref(),
+            Some("./dist/index.server.js")
+        );
+        assert_eq!(resolution.default.as_deref(), Some("./dist/index.mjs"));
+        assert!(resolution.has_separate_server_build);
+    }
+
+    #[test]
+    fn test_resolve_condition_order_matters() {
+        // "default" listed before "react-server" always wins
+        let package_json = r#"{
+            "exports": { "default": "./index.js", "react-server": "./server.js" }
+        }"#;
+
+        let resolution = resolve(package_json, ".");
+
+        assert_eq!(resolution.react_server.as_deref(), Some("./index.js"));
+        assert!(!resolution.has_separate_server_build);
+    }
+
+    #[test]
+    fn test_resolve_subpath_and_nested_conditions() {
+        let package_json = r#"{
+            "exports": {
+                ".": "./index.js",
+                "./button": {
+                    "types": "./button.d.ts",
+                    "react-server": { "import": "./button.server.mjs" },
+                    "default": "./button.js"
+                }
+            }
+        }"#;
+
+        let resolution = resolve(package_json, "button");
+
+        assert_eq!(
+            resolution.react_server.as_deref(),
+            Some("./button.server.mjs")
+        );
+        assert_eq!(resolution.default.as_deref(), Some("./button.js"));
+    }
+
+    #[test]
+    fn test_resolve_subpath_pattern() {
+        let package_json = r#"{
+            "exports": {
+                "./*": "./dist/*.js",
+                "./icons/*": { "react-server": "./dist/icons/server/*.js", "default": "./dist/icons/*.js" }
+            }
+        }"#;
+
+        let resolution = resolve(package_json, "./icons/check");
+
+        assert_eq!(
+            resolution.react_server.as_deref(),
+            Some("./dist/icons/server/check.js")
+        );
+        assert_eq!(resolution.default.as_deref(), Some("./dist/icons/check.js"));
+    }
+
+    #[test]
+    fn test_resolve_string_exports_and_main_fallback() {
+        let resolution = resolve(r#"{ "exports": "./index.js" }"#, ".");
+        assert_eq!(resolution.default.as_deref(), Some("./index.js"));
+        assert!(!resolution.has_separate_server_build);
+
+        let resolution = resolve(r#"{ "main": "lib/index.js" }"#, ".");
+        assert_eq!(resolution.default.as_deref(), Some("lib/index.js"));
+
+        let resolution = resolve(r#"{ "main": "lib/index.js" }"#, "./button");
+        assert_eq!(resolution.default, None);
+    }
+
+    #[test]
+    fn test_resolve_unexported_subpath() {
+        let resolution = resolve(r#"{ "exports": { ".": "./index.js" } }"#, "./internal");
+
+        assert_eq!(resolution.default, None);
+        assert_eq!(resolution.react_server, None);
+    }
+
+    #[test]
+    fn test_resolve_invalid_package_json() {
+        assert!(resolve_package_export("{ \"exports\": ", ".", &[]).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+use crate::analyze_react_boundary::check::types;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Scan the BOM, hashbang and directive prologue at the start of a file without parsing it.
+///
+/// Only the prologue is read: the scan stops at the first token that can't belong to it, so the
+/// cost doesn't depend on the size of the file. Directives are reported with their raw text, the
+/// same as the parser's `Directive::directive`.
+pub(crate) fn scan_directives(content: &[u8]) -> types::DirectiveScan {
+    let has_bom = content.starts_with(b"\xEF\xBB\xBF");
+    let mut scanner = Scanner {
+        bytes: content,
+        position: if has_bom { 3 } else { 0 },
+    };
+
+    let has_hashbang = scanner.bytes[scanner.position..].starts_with(b"#!");
+    if has_hashbang {
+        scanner.skip_line();
+    }
+
+    let mut directives = Vec::new();
+    loop {
+        scanner.skip_trivia();
+        let Some(directive) = scanner.string_literal() else {
+            break;
+        };
+        if !scanner.ends_statement() {
+            break;
+        }
+        directives.push(String::from_utf8_lossy(directive).into_owned());
+        if scanner.peek() == Some(b';') {
+            scanner.position += 1;
+        }
+    }
+
+    types::DirectiveScan {
+        has_bom,
+        has_hashbang,
+        directives,
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+struct Scanner<'b> {
+    bytes: &'b [u8],
+    position: usize,
+}
+
+impl<'b> Scanner<'b> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn skip_line(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == b'\n' {
+                break;
+            }
+            self.position += 1;
+        }
+    }
+
+    /// Skip whitespace and comments, returning whether a line break was skipped
+    fn skip_trivia(&mut self) -> bool {
+        let mut line_break = false;
+        loop {
+            match self.peek() {
+                Some(b'\n' | b'\r') => {
+                    line_break = true;
+                    self.position += 1;
+                }
+                Some(c) if c.is_ascii_whitespace() => self.position += 1,
+                Some(b'/') if self.bytes.get(self.position + 1) == Some(&b'/') => self.skip_line(),
+                Some(b'/') if self.bytes.get(self.position + 1) == Some(&b'*') => {
+                    let rest = &self.bytes[self.position + 2..];
+                    let end = rest
+                        .windows(2)
+                        .position(|window| window == b"*/")
+                        .map_or(rest.len(), |end| end + 2);
+                    line_break |= rest[..end].contains(&b'\n');
+                    self.position += 2 + end;
+                }
+                _ => return line_break,
+            }
+        }
+    }
+
+    /// Read a string literal at the current position, returning its raw contents
+    fn string_literal(&mut self) -> Option<&'b [u8]> {
+        let quote = self.peek().filter(|c| matches!(c, b'"' | b'\''))?;
+        let start = self.position + 1;
+        let mut end = start;
+        while let Some(&c) = self.bytes.get(end) {
+            match c {
+                b'\\' => end += 2,
+                b'\n' | b'\r' => return None,
+                c if c == quote => {
+                    self.position = end + 1;
+                    return Some(&self.bytes[start..end]);
+                }
+                _ => end += 1,
+            }
+        }
+        None
+    }
+
+    /// Whether the string literal just read is a whole expression statement, either terminated
+    /// explicitly or by a line break that automatic semicolon insertion would end it at
+    fn ends_statement(&mut self) -> bool {
+        let line_break = self.skip_trivia();
+        match self.peek() {
+            None | Some(b';' | b'}') => true,
+            // These continue the expression on the next line (`"a"\n.length`)
+            Some(
+                b'.' | b'[' | b'(' | b'`' | b'+' | b'-' | b'*' | b'/' | b'%' | b',' | b'?' | b'='
+                | b'<' | b'>' | b'&' | b'|' | b'^',
+            ) => false,
+            Some(_) => line_break && !self.continues_with_keyword(),
+        }
+    }
+
+    /// `"a"\ninstanceof B` and `"a"\nin b` continue the expression too
+    fn continues_with_keyword(&self) -> bool {
+        let rest = &self.bytes[self.position..];
+        [&b"instanceof"[..], &b"in"[..]].iter().any(|keyword| {
+            rest.starts_with(keyword)
+                && !rest
+                    .get(keyword.len())
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, b'_' | b'$'))
+        })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    /// Directives as the full parser sees them
+    fn parsed_directives(source: &str) -> Vec<String> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        ret.program
+            .directives
+            .iter()
+            .map(|directive| directive.directive.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_scan_directives_matches_parser() {
+        let sources = [
+            "\"use client\";\nexport const A = 1;",
+            "#!/usr/bin/env node\n// comment\n/* block */ 'use strict'\n\"use server\"\nfoo();",
+            "\u{feff}'use client'",
+            "\"use client\".length;",
+            "\"use client\"\n+ 1;",
+            "\"use client\"\ninstanceof A;",
+            "import a from 'a';\n\"use client\";",
+            "'use\\x20client'; 'use client' /* trailing */ ; export {}",
+            "",
+        ];
+
+        for source in sources {
+            assert_eq!(
+                scan_directives(source.as_bytes()).directives,
+                parsed_directives(source),
+                "source: {:?}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_scan_bom_and_hashbang() {
+        let scan = scan_directives("\u{feff}#!/usr/bin/env node\n\"use client\";".as_bytes());
+
+        assert!(scan.has_bom);
+        assert!(scan.has_hashbang);
+        assert_eq!(scan.directives, vec!["use client"]);
+
+        let scan = scan_directives(b"export {}");
+        assert!(!scan.has_bom);
+        assert!(!scan.has_hashbang);
+        assert!(scan.directives.is_empty());
+    }
+}
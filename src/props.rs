@@ -0,0 +1,233 @@
+use oxc::ast::ast::{
+    Argument, BindingPatternKind, Declaration, ExportDefaultDeclarationKind, Expression,
+    FormalParameters, Statement, TSType, TSTypeName,
+};
+use oxc::span::Span;
+use std::collections::HashMap;
+
+/// Utility types wrapping the props type (`PropsWithChildren<ButtonProps>`), which are looked
+/// through so the reference points at the props declaration itself
+const PROPS_WRAPPERS: [&str; 3] = ["PropsWithChildren", "PropsWithRef", "Readonly"];
+
+/// Component types whose first type argument is the props type (`FC<ButtonProps>`)
+const PROPS_COMPONENT_TYPES: [&str; 6] = [
+    "FC",
+    "FunctionComponent",
+    "VFC",
+    "ComponentType",
+    "NamedExoticComponent",
+    "MemoExoticComponent",
+];
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Collect the props type of the top-level declarations, keyed by the span of the declaration
+/// identifier. The props type is taken from the declaration's type (`const Button: FC<Props>`),
+/// from the type arguments of `forwardRef<Ref, Props>(...)`, or from the annotation of the first
+/// parameter, looking through `memo(...)` and `forwardRef(...)`. Only named types are collected,
+/// as (type name, span of the type name); inline object types have no declaration to go to.
+pub(crate) fn collect_props_types(statements: &[Statement]) -> HashMap<Span, (String, Span)> {
+    let mut props_types = HashMap::new();
+
+    for statement in statements {
+        let declaration = match statement {
+            Statement::ExportNamedDeclaration(export_decl) => export_decl.declaration.as_ref(),
+            Statement::ExportDefaultDeclaration(export_decl) => {
+                if let ExportDefaultDeclarationKind::FunctionDeclaration(func) =
+                    &export_decl.declaration
+                    && let Some(id) = &func.id
+                    && let Some(props_type) = parameter_props_type(&func.params)
+                {
+                    props_types.insert(id.span, props_type);
+                }
+                continue;
+            }
+            statement => statement.as_declaration(),
+        };
+
+        match declaration {
+            Some(Declaration::VariableDeclaration(var_decl)) => {
+                for declarator in var_decl.declarations.iter() {
+                    let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind else {
+                        continue;
+                    };
+                    let props_type = declarator
+                        .id
+                        .type_annotation
+                        .as_ref()
+                        .and_then(|annotation| component_type_props(&annotation.type_annotation))
+                        .or_else(|| declarator.init.as_ref().and_then(expression_props_type));
+                    if let Some(props_type) = props_type {
+                        props_types.insert(ident.span, props_type);
+                    }
+                }
+            }
+            Some(Declaration::FunctionDeclaration(func)) => {
+                if let Some(id) = &func.id
+                    && let Some(props_type) = parameter_props_type(&func.params)
+                {
+                    props_types.insert(id.span, props_type);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    props_types
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Props type of a component type annotation (`FC<Props>`, `React.ComponentType<Props>`)
+fn component_type_props(ts_type: &TSType) -> Option<(String, Span)> {
+    let TSType::TSTypeReference(type_ref) = ts_type else {
+        return None;
+    };
+    let name = match &type_ref.type_name {
+        TSTypeName::IdentifierReference(ident) => ident.name.as_str(),
+        TSTypeName::QualifiedName(qualified) => qualified.right.name.as_str(),
+        TSTypeName::ThisExpression(_) => return None,
+    };
+    if !PROPS_COMPONENT_TYPES.contains(&name) {
+        return None;
+    }
+    named_type(type_ref.type_arguments.as_ref()?.params.first()?)
+}
+
+/// Props type of a component function, or of the component a `memo(...)` or `forwardRef(...)`
+/// call wraps
+fn expression_props_type(expr: &Expression) -> Option<(String, Span)> {
+    match expr.get_inner_expression() {
+        Expression::ArrowFunctionExpression(arrow) => parameter_props_type(&arrow.params),
+        Expression::FunctionExpression(func) => parameter_props_type(&func.params),
+        Expression::CallExpression(call) => {
+            let callee = match call.callee.get_inner_expression() {
+                Expression::Identifier(ident) => ident.name.as_str(),
+                Expression::StaticMemberExpression(member) => member.property.name.as_str(),
+                _ => return None,
+            };
+            // `forwardRef<HTMLButtonElement, ButtonProps>(...)`
+            if callee == "forwardRef"
+                && let Some(props_type) = call
+                    .type_arguments
+                    .as_ref()
+                    .and_then(|arguments| arguments.params.get(1))
+                    .and_then(named_type)
+            {
+                return Some(props_type);
+            }
+            if !matches!(callee, "memo" | "forwardRef") {
+                return None;
+            }
+            call.arguments
+                .first()
+                .and_then(Argument::as_expression)
+                .and_then(expression_props_type)
+        }
+        _ => None,
+    }
+}
+
+/// Props type of the first parameter's annotation (`function Button(props: ButtonProps)`)
+fn parameter_props_type(params: &FormalParameters) -> Option<(String, Span)> {
+    let annotation = params.items.first()?.pattern.type_annotation.as_ref()?;
+    named_type(&annotation.type_annotation)
+}
+
+/// Name and span of a type reference, looking through the props wrappers
+fn named_type(ts_type: &TSType) -> Option<(String, Span)> {
+    let TSType::TSTypeReference(type_ref) = ts_type else {
+        return None;
+    };
+    let name = type_name(&type_ref.type_name)?;
+    if PROPS_WRAPPERS.contains(&name.as_str())
+        && let Some(inner) = type_ref
+            .type_arguments
+            .as_ref()
+            .and_then(|arguments| arguments.params.first())
+    {
+        return named_type(inner);
+    }
+    Some((name, type_name_span(&type_ref.type_name)))
+}
+
+/// Dotted name of a type name (`ButtonProps`, `UI.ButtonProps`)
+fn type_name(name: &TSTypeName) -> Option<String> {
+    match name {
+        TSTypeName::IdentifierReference(ident) => Some(ident.name.to_string()),
+        TSTypeName::QualifiedName(qualified) => Some(format!(
+            "{}.{}",
+            type_name(&qualified.left)?,
+            qualified.right.name
+        )),
+        TSTypeName::ThisExpression(_) => None,
+    }
+}
+
+fn type_name_span(name: &TSTypeName) -> Span {
+    match name {
+        TSTypeName::IdentifierReference(ident) => ident.span,
+        TSTypeName::QualifiedName(qualified) => qualified.span,
+        TSTypeName::ThisExpression(this) => this.span,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_collect_props_types() {
+        let source = r#"
+const Button: FC<ButtonProps> = (props) => <button />;
+export const Card = ({ title }: UI.CardProps) => <div />;
+export function Dialog(props: PropsWithChildren<DialogProps>) { return <dialog />; }
+const Input = forwardRef<HTMLInputElement, InputProps>((props, ref) => <input />);
+const Avatar = memo(function Avatar(props: AvatarProps) { return <img />; });
+const Inline = (props: { label: string }) => <span />;
+const Plain = (props) => <div />;
+export default function Page({ params }: PageProps) { return <main />; }
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let mut props_types = collect_props_types(&ret.program.body)
+            .into_iter()
+            .map(|(declaration, (name, span))| {
+                (
+                    declaration.start,
+                    name,
+                    &source[span.start as usize..span.end as usize],
+                )
+            })
+            .collect::<Vec<_>>();
+        props_types.sort();
+
+        let names = props_types
+            .iter()
+            .map(|(_, name, text)| (name.as_str(), *text))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                ("ButtonProps", "ButtonProps"),
+                ("UI.CardProps", "UI.CardProps"),
+                ("DialogProps", "DialogProps"),
+                ("InputProps", "InputProps"),
+                ("AvatarProps", "AvatarProps"),
+                ("PageProps", "PageProps"),
+            ]
+        );
+    }
+}
@@ -1,8 +1,90 @@
 use crate::analyze_react_boundary::check::types;
 use oxc::span::Span;
 
-/// Convert a byte offset to line and column position
+/// Convert a byte offset to line and column position, with columns in code points
 fn offset_to_position(source: &str, offset: u32) -> types::Position {
+    position_at(source, offset, types::PositionEncoding::Utf32)
+}
+
+/// Convert a Span to a Range
+pub fn span_to_range(source: &str, span: Span) -> types::Range {
+    types::Range {
+        start: offset_to_position(source, span.start),
+        end: offset_to_position(source, span.end),
+    }
+}
+
+/// Convert a string literal Span to a Range positioned inside the string (after the opening quote)
+/// This is useful for import sources where we need the position inside the quoted string
+///
+/// Falls back to the raw span when the source doesn't have a matching pair of quotes at the
+/// span edges (degenerate spans, or spans that don't point at a quoted literal)
+pub(crate) fn string_literal_to_range(source: &str, span: Span) -> types::Range {
+    if !is_quoted(source, span) {
+        return span_to_range(source, span);
+    }
+
+    types::Range {
+        start: offset_to_position(source, span.start + 1), // +1 to skip the opening quote
+        end: offset_to_position(source, span.end - 1),     // -1 to skip the closing quote
+    }
+}
+
+/// Converts spans to ranges for one source, either as lines and columns or, in byte span mode, as
+/// raw byte offsets carried in `character` with `line` always 0. Hosts that keep their own line
+/// index use byte spans to skip the conversion, which scans the source for every position.
+///
+/// Lines and columns are 0-based like LSP positions, or 1-based for reporters that show them to
+/// people (CLI output, SARIF). Byte offsets are always 0-based
+pub(crate) struct RangeConverter<'s> {
+    source: &'s str,
+    byte_spans: bool,
+    one_based: bool,
+}
+
+impl<'s> RangeConverter<'s> {
+    pub(crate) fn new(source: &'s str, byte_spans: bool, one_based: bool) -> Self {
+        Self {
+            source,
+            byte_spans,
+            one_based,
+        }
+    }
+
+    pub(crate) fn span(&self, span: Span) -> types::Range {
+        if self.byte_spans {
+            return byte_range(span);
+        }
+        self.based(span_to_range(self.source, span))
+    }
+
+    /// Like `string_literal_to_range`, excluding the quotes in both modes
+    pub(crate) fn string_literal(&self, span: Span) -> types::Range {
+        if !self.byte_spans {
+            return self.based(string_literal_to_range(self.source, span));
+        }
+        if !is_quoted(self.source, span) {
+            return byte_range(span);
+        }
+        byte_range(Span::new(span.start + 1, span.end - 1))
+    }
+
+    fn based(&self, range: types::Range) -> types::Range {
+        if self.one_based {
+            return to_one_based(range);
+        }
+        range
+    }
+}
+
+/// Position of a byte offset, counting columns in `encoding` units. Lines are split on `\n` only,
+/// so the `\r` of a CRLF line ending counts as the last character of its line, as in the ranges
+/// the analyzer reports. Offsets past the end are clamped to the end of the source
+pub(crate) fn position_at(
+    source: &str,
+    offset: u32,
+    encoding: types::PositionEncoding,
+) -> types::Position {
     let mut line = 0;
     let mut character = 0;
 
@@ -14,28 +96,90 @@ fn offset_to_position(source: &str, offset: u32) -> types::Position {
             line += 1;
             character = 0;
         } else {
-            character += 1;
+            character += char_width(ch, encoding);
         }
     }
 
     types::Position { line, character }
 }
 
-/// Convert a Span to a Range
-pub(crate) fn span_to_range(source: &str, span: Span) -> types::Range {
+/// Byte offset of a position whose columns count `encoding` units, the inverse of
+/// [`position_at`]. Columns past the end of a line are clamped to the line ending, lines past the
+/// end to the end of the source, and columns inside a character to its start
+pub(crate) fn offset_at(
+    source: &str,
+    position: types::Position,
+    encoding: types::PositionEncoding,
+) -> u32 {
+    let line_start = if position.line == 0 {
+        Some(0)
+    } else {
+        source
+            .match_indices('\n')
+            .nth(position.line as usize - 1)
+            .map(|(index, _)| index + 1)
+    };
+    let Some(line_start) = line_start else {
+        return source.len() as u32;
+    };
+
+    let mut character = 0;
+    for (i, ch) in source[line_start..].char_indices() {
+        if ch == '\n' || character >= position.character {
+            return (line_start + i) as u32;
+        }
+        character += char_width(ch, encoding);
+        if character > position.character {
+            return (line_start + i) as u32;
+        }
+    }
+    source.len() as u32
+}
+
+fn char_width(ch: char, encoding: types::PositionEncoding) -> u32 {
+    match encoding {
+        types::PositionEncoding::Utf8 => ch.len_utf8() as u32,
+        types::PositionEncoding::Utf16 => ch.len_utf16() as u32,
+        types::PositionEncoding::Utf32 => 1,
+    }
+}
+
+/// Shift a 0-based line/column range to 1-based lines and columns
+fn to_one_based(range: types::Range) -> types::Range {
+    let shift = |position: types::Position| types::Position {
+        line: position.line + 1,
+        character: position.character + 1,
+    };
     types::Range {
-        start: offset_to_position(source, span.start),
-        end: offset_to_position(source, span.end),
+        start: shift(range.start),
+        end: shift(range.end),
     }
 }
 
-/// Convert a string literal Span to a Range positioned inside the string (after the opening quote)
-/// This is useful for import sources where we need the position inside the quoted string
-pub(crate) fn string_literal_to_range(source: &str, span: Span) -> types::Range {
+fn byte_range(span: Span) -> types::Range {
     types::Range {
-        start: offset_to_position(source, span.start + 1), // +1 to skip the opening quote
-        end: offset_to_position(source, span.end - 1),     // -1 to skip the closing quote
+        start: types::Position {
+            line: 0,
+            character: span.start,
+        },
+        end: types::Position {
+            line: 0,
+            character: span.end,
+        },
+    }
+}
+
+/// Check that the span starts and ends with the same quote character
+fn is_quoted(source: &str, span: Span) -> bool {
+    let bytes = source.as_bytes();
+    let (start, end) = (span.start as usize, span.end as usize);
+
+    if end < start + 2 || end > bytes.len() {
+        return false;
     }
+
+    let (open, close) = (bytes[start], bytes[end - 1]);
+    open == close && matches!(open, b'"' | b'\'' | b'`')
 }
 
 #[cfg(test)]
@@ -142,7 +286,7 @@ mod tests {
     fn test_string_literal_to_range_multi_line() {
         let source = "const code = `\n  ./path\n`;";
         // Multi-line template literal
-        let span = Span::new(13, 24); // `\n  ./path\n`
+        let span = Span::new(13, 25); // `\n  ./path\n`
 
         let range = string_literal_to_range(source, span);
 
@@ -150,4 +294,148 @@ mod tests {
         assert_eq!(range.start.line, 0);
         assert_eq!(range.start.character, 14);
     }
+
+    #[test]
+    fn test_string_literal_to_range_zero_length_span() {
+        let source = "import X from '';";
+        let span = Span::new(0, 0);
+
+        let range = string_literal_to_range(source, span);
+
+        // Should fall back to the raw span instead of underflowing
+        assert_eq!(range.start.character, 0);
+        assert_eq!(range.end.character, 0);
+    }
+
+    #[test]
+    fn test_string_literal_to_range_unquoted_span() {
+        let source = "import X from client;";
+        let span = Span::new(14, 20); // client
+
+        let range = string_literal_to_range(source, span);
+
+        // Should keep the raw span since there are no quotes to skip
+        assert_eq!(range.start.character, 14);
+        assert_eq!(range.end.character, 20);
+    }
+
+    #[test]
+    fn test_string_literal_to_range_mismatched_quotes() {
+        let source = r#"const x = "abc';"#;
+        let span = Span::new(10, 15);
+
+        let range = string_literal_to_range(source, span);
+
+        assert_eq!(range.start.character, 10);
+        assert_eq!(range.end.character, 15);
+    }
+
+    #[test]
+    fn test_string_literal_to_range_empty_string() {
+        let source = "import X from '';";
+        let span = Span::new(14, 16);
+
+        let range = string_literal_to_range(source, span);
+
+        // Start and end collapse to the position between the quotes
+        assert_eq!(range.start.character, 15);
+        assert_eq!(range.end.character, 15);
+    }
+
+    #[test]
+    fn test_range_converter_byte_spans() {
+        let source = "import X from \"./client\";\nconst y = 20;";
+        let converter = RangeConverter::new(source, true, false);
+
+        let range = converter.span(Span::new(32, 33));
+        assert_eq!((range.start.line, range.start.character), (0, 32));
+        assert_eq!((range.end.line, range.end.character), (0, 33));
+
+        let range = converter.string_literal(Span::new(14, 24));
+        assert_eq!((range.start.character, range.end.character), (15, 23));
+
+        let range = RangeConverter::new(source, false, false).span(Span::new(32, 33));
+        assert_eq!((range.start.line, range.start.character), (1, 6));
+    }
+
+    #[test]
+    fn test_range_converter_one_based() {
+        let source = "import X from \"./client\";\nconst y = 20;";
+        let converter = RangeConverter::new(source, false, true);
+
+        let range = converter.span(Span::new(32, 33));
+        assert_eq!((range.start.line, range.start.character), (2, 7));
+        assert_eq!((range.end.line, range.end.character), (2, 8));
+
+        let range = converter.string_literal(Span::new(14, 24));
+        assert_eq!((range.start.line, range.start.character), (1, 16));
+
+        // Byte offsets are not positions, so they stay as they are
+        let range = RangeConverter::new(source, true, true).span(Span::new(32, 33));
+        assert_eq!((range.start.line, range.start.character), (0, 32));
+    }
+
+    #[test]
+    fn test_position_at_encodings() {
+        let source = "const a = '😀';\r\nconst b = 1;";
+        let b = source.find('b').unwrap() as u32;
+
+        let position = position_at(source, 15, types::PositionEncoding::Utf8);
+        assert_eq!((position.line, position.character), (0, 15));
+        let position = position_at(source, 15, types::PositionEncoding::Utf16);
+        assert_eq!((position.line, position.character), (0, 13));
+        let position = position_at(source, 15, types::PositionEncoding::Utf32);
+        assert_eq!((position.line, position.character), (0, 12));
+
+        // The analyzer's own ranges count characters and keep `\r` on its line
+        let position = position_at(source, b, types::PositionEncoding::Utf32);
+        let analyzed = offset_to_position(source, b);
+        assert_eq!(
+            (position.line, position.character),
+            (analyzed.line, analyzed.character)
+        );
+        assert_eq!((position.line, position.character), (1, 6));
+
+        let position = position_at(source, 1000, types::PositionEncoding::Utf16);
+        assert_eq!((position.line, position.character), (1, 12));
+    }
+
+    #[test]
+    fn test_offset_at_round_trips() {
+        let source = "const a = '😀';\r\nconst b = 1;";
+        let encodings = [
+            types::PositionEncoding::Utf8,
+            types::PositionEncoding::Utf16,
+            types::PositionEncoding::Utf32,
+        ];
+
+        for (offset, _) in source.char_indices() {
+            for encoding in encodings {
+                let position = position_at(source, offset as u32, encoding);
+                assert_eq!(offset_at(source, position, encoding), offset as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn test_offset_at_clamps() {
+        let source = "a😀b\nline";
+        let position = |line, character| types::Position { line, character };
+
+        // Inside the surrogate pair of the emoji
+        assert_eq!(
+            offset_at(source, position(0, 2), types::PositionEncoding::Utf16),
+            1
+        );
+        // Past the end of the first line
+        assert_eq!(
+            offset_at(source, position(0, 40), types::PositionEncoding::Utf16),
+            6
+        );
+        // Past the last line
+        assert_eq!(
+            offset_at(source, position(5, 0), types::PositionEncoding::Utf16),
+            source.len() as u32
+        );
+    }
 }
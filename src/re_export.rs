@@ -0,0 +1,119 @@
+use oxc::ast::ast::Statement;
+use oxc::span::{GetSpan, Span};
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// A binding re-exported from another module
+pub(crate) struct ReExport {
+    pub(crate) source: String,
+    /// Span of the source string literal
+    pub(crate) source_span: Span,
+    /// Name imported from the source, `*` for `export *` and `export * as ns`
+    pub(crate) imported: String,
+    /// Name the binding is exported as, `None` for `export *`
+    pub(crate) exported: Option<String>,
+    /// `export type { X } from`, `export { type X } from` or `export type * from`. These create
+    /// no runtime edge between the modules
+    pub(crate) type_only: bool,
+    /// Span of the specifier, or of the statement for `export *`
+    pub(crate) span: Span,
+}
+
+/// Collect the re-exports of a module, one entry per re-exported binding
+pub(crate) fn collect_re_exports(statements: &[Statement]) -> Vec<ReExport> {
+    let mut re_exports = Vec::new();
+
+    for statement in statements {
+        match statement {
+            Statement::ExportNamedDeclaration(export_decl) => {
+                let Some(source) = &export_decl.source else {
+                    continue;
+                };
+                for specifier in export_decl.specifiers.iter() {
+                    re_exports.push(ReExport {
+                        source: source.value.to_string(),
+                        source_span: source.span,
+                        imported: crate::module_export_name(&specifier.local),
+                        exported: Some(crate::module_export_name(&specifier.exported)),
+                        type_only: export_decl.export_kind.is_type()
+                            || specifier.export_kind.is_type(),
+                        span: specifier.span(),
+                    });
+                }
+            }
+            Statement::ExportAllDeclaration(export_all) => {
+                re_exports.push(ReExport {
+                    source: export_all.source.value.to_string(),
+                    source_span: export_all.source.span,
+                    imported: "*".to_string(),
+                    exported: export_all.exported.as_ref().map(crate::module_export_name),
+                    type_only: export_all.export_kind.is_type(),
+                    span: export_all.span,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    re_exports
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_collect_re_exports_with_type_modifiers() {
+        let source = r#"
+export { Button, type ButtonProps } from "./button";
+export type { CardProps } from "./card";
+export * from "./icons";
+export * as utils from "./utils";
+export type * from "./types";
+export { Local };
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        let re_exports = collect_re_exports(&ret.program.body)
+            .into_iter()
+            .map(|re_export| {
+                (
+                    re_export.source,
+                    re_export.imported,
+                    re_export.exported,
+                    re_export.type_only,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let entry = |source: &str, imported: &str, exported: Option<&str>, type_only| {
+            (
+                source.to_string(),
+                imported.to_string(),
+                exported.map(str::to_string),
+                type_only,
+            )
+        };
+        assert_eq!(
+            re_exports,
+            vec![
+                entry("./button", "Button", Some("Button"), false),
+                entry("./button", "ButtonProps", Some("ButtonProps"), true),
+                entry("./card", "CardProps", Some("CardProps"), true),
+                entry("./icons", "*", None, false),
+                entry("./utils", "*", Some("utils"), false),
+                entry("./types", "*", None, true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use crate::analyze_react_boundary::check::types;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Compute the client reference of every export of a "use client" module, as the React Flight
+/// runtime registers it: the module's `file://` URL and the export name joined by `#`
+/// (`file:///app/button.tsx#Button`). Manifests list client references by this id, so tooling
+/// can match them against the source.
+///
+/// Modules without "use client" have no client references. `export * from` is skipped, since
+/// the names it forwards are only known to the target module.
+pub(crate) fn client_references(
+    path: &str,
+    content: Vec<u8>,
+    extension: String,
+    is_cancelled: &dyn Fn() -> bool,
+) -> Result<Vec<types::ClientReference>, String> {
+    let analysis = crate::analyze_source(content, extension, is_cancelled)?;
+    let module_id = module_id(path);
+
+    Ok(analysis
+        .client_exports
+        .into_iter()
+        .filter(|export| export.name != "*")
+        .map(|export| types::ClientReference {
+            id: format!("{}#{}", module_id, export.name),
+            export_name: export.name,
+            kind: export.kind,
+            range: export.range,
+        })
+        .collect())
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// `file://` URL of an absolute POSIX or Windows path, encoded the way Node's
+/// `url.pathToFileURL` encodes it. Unlike editor URIs, the drive letter's colon is kept
+fn module_id(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut url = String::from("file://");
+    if !path.starts_with('/') {
+        url.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'%' | b'"' | b'#' | b'<' | b'>' | b'?' | b'`' | b'{' | b'}' | b' ' => {
+                url.push_str(&format!("%{:02X}", byte))
+            }
+            0x00..=0x1F | 0x7F..=0xFF => url.push_str(&format!("%{:02X}", byte)),
+            _ => url.push(byte as char),
+        }
+    }
+    url
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_id() {
+        assert_eq!(module_id("/app/button.tsx"), "file:///app/button.tsx");
+        assert_eq!(
+            module_id("/app/(shop)/[id]/my cart.tsx"),
+            "file:///app/(shop)/[id]/my%20cart.tsx"
+        );
+        assert_eq!(
+            module_id("C:\\project\\app\\button.tsx"),
+            "file:///C:/project/app/button.tsx"
+        );
+        assert_eq!(
+            module_id("/app/café#1.tsx"),
+            "file:///app/caf%C3%A9%231.tsx"
+        );
+    }
+
+    #[test]
+    fn test_client_references() {
+        let source = r#"
+"use client";
+export function Button() { return <button />; }
+export const formatPrice = (price) => `$${price}`;
+export default function Cart() { return <div />; }
+export { Icon as CartIcon } from "./icon";
+export * from "./shared";
+        "#;
+
+        let references = client_references(
+            "/app/cart.tsx",
+            source.as_bytes().to_vec(),
+            "tsx".to_string(),
+            &|| false,
+        )
+        .unwrap();
+
+        let ids = references
+            .iter()
+            .map(|reference| reference.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                "file:///app/cart.tsx#Button",
+                "file:///app/cart.tsx#formatPrice",
+                "file:///app/cart.tsx#default",
+                "file:///app/cart.tsx#CartIcon",
+            ]
+        );
+        assert_eq!(references[0].kind, types::ClientExportKind::Component);
+
+        let references = client_references(
+            "/app/page.tsx",
+            b"export default function Page() { return <div />; }".to_vec(),
+            "tsx".to_string(),
+            &|| false,
+        )
+        .unwrap();
+        assert!(references.is_empty());
+    }
+}
@@ -0,0 +1,232 @@
+use crate::analyze_react_boundary::check::types;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Expand a specifier through a tsconfig-style `paths` map, returning candidate paths in the
+/// order they should be tried.
+///
+/// Follows TypeScript's matching rules: an exact pattern wins, otherwise the wildcard pattern
+/// with the longest prefix is used. Targets are joined to `base_url` when it is given. Non-relative
+/// specifiers that match no pattern resolve against `base_url` itself. Relative and absolute
+/// specifiers are never aliased.
+pub(crate) fn resolve_path_alias(
+    paths: &[types::PathAlias],
+    base_url: Option<&str>,
+    specifier: &str,
+) -> Vec<String> {
+    if is_relative_or_absolute(specifier) {
+        return Vec::new();
+    }
+
+    let targets = match best_match(paths, specifier) {
+        Some((alias, wildcard)) => alias
+            .targets
+            .iter()
+            .map(|target| target.replacen('*', wildcard, 1))
+            .collect::<Vec<_>>(),
+        None => match base_url {
+            Some(_) => vec![specifier.to_string()],
+            None => return Vec::new(),
+        },
+    };
+
+    targets
+        .into_iter()
+        .map(|target| match base_url {
+            Some(base_url) if !target.starts_with('/') => {
+                normalize(&format!("{}/{}", base_url.trim_end_matches('/'), target))
+            }
+            // Without a base URL, targets stay relative to the tsconfig: keep them `./`-prefixed
+            _ => match normalize(&target) {
+                path if target.starts_with("./") && !path.starts_with("../") => {
+                    format!("./{}", path)
+                }
+                path => path,
+            },
+        })
+        .collect()
+}
+
+/// Collapse `.` and `..` segments and duplicate separators. Shared by every module keying files
+/// by path, so a path normalizes to the same key everywhere. A leading `.` is dropped rather than
+/// kept, so `./../shared` becomes `../shared` and `./button` becomes `button`
+pub(crate) fn normalize(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "." => {}
+            "" if !segments.is_empty() => {}
+            ".." if segments
+                .last()
+                .is_some_and(|last| !matches!(*last, "" | "..")) =>
+            {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn is_relative_or_absolute(specifier: &str) -> bool {
+    specifier == "."
+        || specifier == ".."
+        || specifier.starts_with("./")
+        || specifier.starts_with("../")
+        || specifier.starts_with('/')
+}
+
+/// Find the pattern matching the specifier, along with the text the wildcard matched
+fn best_match<'a, 'b>(
+    paths: &'a [types::PathAlias],
+    specifier: &'b str,
+) -> Option<(&'a types::PathAlias, &'b str)> {
+    if let Some(alias) = paths.iter().find(|alias| alias.pattern == specifier) {
+        return Some((alias, ""));
+    }
+
+    paths
+        .iter()
+        .filter_map(|alias| {
+            let (prefix, suffix) = alias.pattern.split_once('*')?;
+            let wildcard = specifier
+                .strip_prefix(prefix)?
+                .strip_suffix(suffix)
+                .filter(|_| specifier.len() >= prefix.len() + suffix.len())?;
+            Some((alias, prefix.len(), wildcard))
+        })
+        .max_by_key(|(_, prefix_len, _)| *prefix_len)
+        .map(|(alias, _, wildcard)| (alias, wildcard))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alias(pattern: &str, targets: &[&str]) -> types::PathAlias {
+        types::PathAlias {
+            pattern: pattern.to_string(),
+            targets: targets.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_wildcard_alias() {
+        let paths = vec![alias("@/*", &["./src/*"])];
+
+        let candidates = resolve_path_alias(&paths, Some("/project"), "@/components/button");
+
+        assert_eq!(candidates, vec!["/project/src/components/button"]);
+    }
+
+    #[test]
+    fn test_resolve_exact_alias_wins() {
+        let paths = vec![
+            alias("ui/*", &["./packages/ui/src/*"]),
+            alias("ui/button", &["./packages/button/index"]),
+        ];
+
+        let candidates = resolve_path_alias(&paths, Some("/project"), "ui/button");
+
+        assert_eq!(candidates, vec!["/project/packages/button/index"]);
+    }
+
+    #[test]
+    fn test_resolve_longest_prefix_wins() {
+        let paths = vec![
+            alias("@app/*", &["./app/*"]),
+            alias("@app/components/*", &["./ui/*"]),
+        ];
+
+        let candidates = resolve_path_alias(&paths, Some("/project"), "@app/components/card");
+
+        assert_eq!(candidates, vec!["/project/ui/card"]);
+    }
+
+    #[test]
+    fn test_resolve_multiple_targets_in_order() {
+        let paths = vec![alias("#lib/*", &["./src/lib/*", "./generated/*"])];
+
+        let candidates = resolve_path_alias(&paths, Some("/project/"), "#lib/api");
+
+        assert_eq!(
+            candidates,
+            vec!["/project/src/lib/api", "/project/generated/api"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_wildcard_with_suffix() {
+        let paths = vec![alias("*.css", &["./styles/*.css"])];
+
+        let candidates = resolve_path_alias(&paths, Some("/project"), "theme.css");
+
+        assert_eq!(candidates, vec!["/project/styles/theme.css"]);
+    }
+
+    #[test]
+    fn test_resolve_base_url_fallback() {
+        let candidates = resolve_path_alias(&[], Some("/project/src"), "components/button");
+
+        assert_eq!(candidates, vec!["/project/src/components/button"]);
+    }
+
+    #[test]
+    fn test_resolve_without_base_url_keeps_targets_relative() {
+        let paths = vec![alias("@/*", &["./src/*"])];
+
+        assert_eq!(
+            resolve_path_alias(&paths, None, "@/button"),
+            vec!["./src/button"]
+        );
+        assert!(resolve_path_alias(&paths, None, "react").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_ignores_relative_specifiers() {
+        let paths = vec![alias("*", &["./src/*"])];
+
+        assert!(resolve_path_alias(&paths, Some("/project"), "./button").is_empty());
+        assert!(resolve_path_alias(&paths, Some("/project"), "../button").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_parent_segments() {
+        let paths = vec![alias("shared/*", &["../shared/src/*"])];
+
+        let candidates = resolve_path_alias(&paths, Some("/repo/app"), "shared/utils");
+
+        assert_eq!(candidates, vec!["/repo/shared/src/utils"]);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("/app//components/./button"),
+            "/app/components/button"
+        );
+        assert_eq!(normalize("/app/./../lib/utils"), "/lib/utils");
+        assert_eq!(normalize("../shared/./src"), "../shared/src");
+        assert_eq!(normalize("./../shared/button"), "../shared/button");
+        assert_eq!(normalize("./button"), "button");
+    }
+
+    #[test]
+    fn test_resolve_dot_base_url_parent_target() {
+        let paths = vec![alias("@shared/*", &["../shared/*"])];
+
+        let candidates = resolve_path_alias(&paths, Some("."), "@shared/button");
+
+        assert_eq!(candidates, vec!["../shared/button"]);
+    }
+}
@@ -0,0 +1,172 @@
+use oxc::ast::ast::{AssignmentTarget, BindingPatternKind, Declaration, Expression, Statement};
+use oxc::span::{GetSpan, Span};
+
+/// HTTP methods Next.js routes to exported handlers
+const HTTP_METHODS: [&str; 7] = ["GET", "HEAD", "POST", "PUT", "DELETE", "PATCH", "OPTIONS"];
+
+/// Data fetching functions of the pages router
+const PAGES_DATA_FUNCTIONS: [&str; 4] = [
+    "getServerSideProps",
+    "getStaticProps",
+    "getStaticPaths",
+    "getInitialProps",
+];
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Collect exported HTTP method handlers (`export async function GET`, `export const POST = ...`,
+/// `export { handler as GET }`) with the span of the exported name
+pub(crate) fn collect_route_handlers(statements: &[Statement]) -> Vec<(String, Span)> {
+    let mut handlers = exported_bindings(statements);
+    handlers.retain(|(name, _)| HTTP_METHODS.contains(&name.as_str()));
+    handlers
+}
+
+/// Collect the pages router data fetching functions: exported `getServerSideProps`,
+/// `getStaticProps` and `getStaticPaths`, and `getInitialProps` assigned to a component
+/// (`Page.getInitialProps = ...`) or exported. Spans are those of the function names
+pub(crate) fn collect_pages_data_functions(statements: &[Statement]) -> Vec<(String, Span)> {
+    let mut functions = exported_bindings(statements);
+    functions.retain(|(name, _)| PAGES_DATA_FUNCTIONS.contains(&name.as_str()));
+
+    for statement in statements {
+        if let Statement::ExpressionStatement(expr_stmt) = statement
+            && let Expression::AssignmentExpression(assignment) = &expr_stmt.expression
+            && let AssignmentTarget::StaticMemberExpression(member) = &assignment.left
+            && member.property.name == "getInitialProps"
+        {
+            functions.push((member.property.name.to_string(), member.property.span));
+        }
+    }
+
+    functions
+}
+
+/// Collect the names of value exports from `export` declarations and export lists, without the
+/// `export default` declaration
+pub(crate) fn exported_bindings(statements: &[Statement]) -> Vec<(String, Span)> {
+    let mut bindings = Vec::new();
+
+    for statement in statements {
+        let Statement::ExportNamedDeclaration(export_decl) = statement else {
+            continue;
+        };
+        if export_decl.export_kind.is_type() {
+            continue;
+        }
+
+        match &export_decl.declaration {
+            Some(Declaration::FunctionDeclaration(func_decl)) => {
+                if let Some(id) = &func_decl.id {
+                    bindings.push((id.name.to_string(), id.span));
+                }
+            }
+            Some(Declaration::VariableDeclaration(var_decl)) => {
+                for declarator in var_decl.declarations.iter() {
+                    if let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind {
+                        bindings.push((ident.name.to_string(), ident.span));
+                    }
+                }
+            }
+            Some(_) => {}
+            None => {
+                for specifier in export_decl.specifiers.iter() {
+                    if !specifier.export_kind.is_type() {
+                        bindings.push((
+                            crate::module_export_name(&specifier.exported),
+                            specifier.exported.span(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    bindings
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    fn handler_names(source: &str) -> Vec<String> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        collect_route_handlers(&ret.program.body)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    #[test]
+    fn test_collect_route_handlers() {
+        let source = r#"
+export async function GET(request: Request) { return Response.json({}); }
+export const POST = async () => new Response(null, { status: 201 });
+const remove = () => new Response(null);
+export { remove as DELETE };
+export const dynamic = "force-dynamic";
+        "#;
+
+        assert_eq!(handler_names(source), vec!["GET", "POST", "DELETE"]);
+    }
+
+    #[test]
+    fn test_collect_route_handlers_ignores_non_methods() {
+        let source = r#"
+export function Get() {}
+export function handler() {}
+export type GET = () => void;
+        "#;
+
+        assert!(handler_names(source).is_empty());
+    }
+
+    #[test]
+    fn test_collect_route_handlers_span_covers_name() {
+        let source = "export async function PATCH() {}";
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+
+        let handlers = collect_route_handlers(&ret.program.body);
+
+        assert_eq!(handlers.len(), 1);
+        assert_eq!(handlers[0].1.source_text(source), "PATCH");
+    }
+
+    #[test]
+    fn test_collect_pages_data_functions() {
+        let source = r#"
+export default function Post({ post }) { return null; }
+export async function getStaticPaths() { return { paths: [], fallback: true }; }
+export const getStaticProps = async () => ({ props: {} });
+Post.getInitialProps = async () => ({});
+export function getServerSidePropsLater() {}
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        let functions = collect_pages_data_functions(&ret.program.body);
+
+        let names = functions
+            .iter()
+            .map(|(name, span)| {
+                assert_eq!(&source[span.start as usize..span.end as usize], name);
+                name.as_str()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["getStaticPaths", "getStaticProps", "getInitialProps"]
+        );
+    }
+}
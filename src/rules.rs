@@ -0,0 +1,209 @@
+use crate::analyze_react_boundary::check::types;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Rules the diagnostics of an analysis are reported under. Hosts refer to them by id to change
+/// their severity or turn them off
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Rule {
+    /// Server-only React API used in a "use client" module
+    ServerOnlyApi,
+    /// Server-only entry point imported in a "use client" module
+    ServerOnlyImport,
+    /// Known server-only package imported in a "use client" module
+    ServerPackageImport,
+    /// Database client instantiated in a "use client" module
+    DatabaseClient,
+    /// Export of a "use client" module referencing a server-only binding
+    ClientExportServerReference,
+    /// Context provider rendered in a module without "use client"
+    ContextProviderInServer,
+    /// Inline function passed to an imported component from a module without "use client"
+    FunctionPropToClient,
+    /// Stylesheet imported in a "use server" module
+    StylesheetInServerAction,
+    /// Component declared inside the render function of another component
+    NestedComponent,
+    /// App router route handler marked "use client"
+    RouteHandlerClient,
+    /// App router route handler default exporting a component
+    RouteHandlerComponent,
+    /// App router `error` file without "use client"
+    ErrorBoundaryNotClient,
+    /// App router component entry without a default exported component
+    MissingDefaultExport,
+}
+
+const RULES: [(Rule, &str, types::Severity); 13] = [
+    (
+        Rule::ServerOnlyApi,
+        "server-only-api",
+        types::Severity::Error,
+    ),
+    (
+        Rule::ServerOnlyImport,
+        "server-only-import",
+        types::Severity::Error,
+    ),
+    (
+        Rule::ServerPackageImport,
+        "server-package-import",
+        types::Severity::Error,
+    ),
+    (
+        Rule::DatabaseClient,
+        "database-client",
+        types::Severity::Error,
+    ),
+    (
+        Rule::ClientExportServerReference,
+        "client-export-server-reference",
+        types::Severity::Error,
+    ),
+    (
+        Rule::ContextProviderInServer,
+        "context-provider-in-server",
+        types::Severity::Error,
+    ),
+    (
+        Rule::FunctionPropToClient,
+        "function-prop-to-client",
+        types::Severity::Warning,
+    ),
+    (
+        Rule::StylesheetInServerAction,
+        "stylesheet-in-server-action",
+        types::Severity::Warning,
+    ),
+    (
+        Rule::NestedComponent,
+        "nested-component",
+        types::Severity::Warning,
+    ),
+    (
+        Rule::RouteHandlerClient,
+        "route-handler-client",
+        types::Severity::Error,
+    ),
+    (
+        Rule::RouteHandlerComponent,
+        "route-handler-component",
+        types::Severity::Error,
+    ),
+    (
+        Rule::ErrorBoundaryNotClient,
+        "error-boundary-not-client",
+        types::Severity::Error,
+    ),
+    (
+        Rule::MissingDefaultExport,
+        "missing-default-export",
+        types::Severity::Error,
+    ),
+];
+
+impl Rule {
+    /// Id hosts configure the rule by
+    pub(crate) fn id(self) -> &'static str {
+        RULES
+            .iter()
+            .find(|(rule, _, _)| *rule == self)
+            .map_or("", |(_, id, _)| id)
+    }
+
+    /// Severity of the rule's diagnostics given the host's settings, or `None` if the host turned
+    /// the rule off. The last setting of a rule wins
+    pub(crate) fn severity(self, settings: &[types::RuleSetting]) -> Option<types::Severity> {
+        let configured = settings
+            .iter()
+            .rev()
+            .find(|setting| setting.rule == self.id())
+            .map(|setting| setting.level);
+        match configured {
+            Some(types::RuleLevel::Off) => None,
+            Some(types::RuleLevel::Hint) => Some(types::Severity::Hint),
+            Some(types::RuleLevel::Warning) => Some(types::Severity::Warning),
+            Some(types::RuleLevel::Error) => Some(types::Severity::Error),
+            None => RULES
+                .iter()
+                .find(|(rule, _, _)| *rule == self)
+                .map(|(_, _, severity)| *severity),
+        }
+    }
+}
+
+/// Check rule settings, returning one message per setting that names an unknown rule. Analyses
+/// ignore such settings, so hosts validate their configuration up front to report typos
+pub(crate) fn validate_rules(settings: &[types::RuleSetting]) -> Vec<String> {
+    settings
+        .iter()
+        .filter(|setting| !RULES.iter().any(|(_, id, _)| *id == setting.rule))
+        .map(|setting| {
+            format!(
+                "Unknown rule \"{}\". Known rules: {}",
+                setting.rule,
+                RULES
+                    .iter()
+                    .map(|(_, id, _)| *id)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setting(rule: &str, level: types::RuleLevel) -> types::RuleSetting {
+        types::RuleSetting {
+            rule: rule.to_string(),
+            level,
+        }
+    }
+
+    #[test]
+    fn test_rule_severity() {
+        assert_eq!(
+            Rule::ServerOnlyApi.severity(&[]),
+            Some(types::Severity::Error)
+        );
+        assert_eq!(
+            Rule::FunctionPropToClient.severity(&[]),
+            Some(types::Severity::Warning)
+        );
+
+        let settings = [
+            setting("server-only-api", types::RuleLevel::Off),
+            setting("function-prop-to-client", types::RuleLevel::Error),
+            setting("function-prop-to-client", types::RuleLevel::Hint),
+        ];
+        assert_eq!(Rule::ServerOnlyApi.severity(&settings), None);
+        assert_eq!(
+            Rule::FunctionPropToClient.severity(&settings),
+            Some(types::Severity::Hint)
+        );
+        assert_eq!(
+            Rule::DatabaseClient.severity(&settings),
+            Some(types::Severity::Error)
+        );
+    }
+
+    #[test]
+    fn test_validate_rules() {
+        let messages = validate_rules(&[
+            setting("server-only-api", types::RuleLevel::Warning),
+            setting("server-only-apis", types::RuleLevel::Off),
+        ]);
+
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].starts_with("Unknown rule \"server-only-apis\". Known rules: "));
+    }
+}
@@ -0,0 +1,303 @@
+use crate::analyze_react_boundary::check::types;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+
+/// Snapshot format version. Bump it whenever `AnalysisResult` or the heuristics producing it
+/// change, so snapshots persisted by older builds are refused instead of serving stale results.
+const SCHEMA_VERSION: u32 = 2;
+
+/// Most results kept in the session cache. The oldest entries are evicted first
+const MAX_ENTRIES: usize = 512;
+
+/// Cached analysis of one file. The content length is checked on lookup, so a hash collision
+/// between files of different lengths can't serve another file's result
+#[derive(Clone)]
+struct CachedResult {
+    content_length: u32,
+    analysis: types::AnalysisResult,
+}
+
+/// Cached results by content hash, with the hashes in insertion order for eviction
+#[derive(Default)]
+struct Cache {
+    results: HashMap<u64, CachedResult>,
+    order: VecDeque<u64>,
+}
+
+impl Cache {
+    fn get(&self, hash: u64, content_length: u32) -> Option<&types::AnalysisResult> {
+        self.results
+            .get(&hash)
+            .filter(|cached| cached.content_length == content_length)
+            .map(|cached| &cached.analysis)
+    }
+
+    fn insert(&mut self, hash: u64, cached: CachedResult) {
+        if self.results.insert(hash, cached).is_none() {
+            self.order.push_back(hash);
+        }
+        while self.order.len() > MAX_ENTRIES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.results.remove(&oldest);
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.results.clear();
+        self.order.clear();
+    }
+}
+
+thread_local! {
+    /// Session cache of analysis results by content hash. A component instance is one session;
+    /// hosts that want independent caches instantiate the module more than once.
+    static CACHE: RefCell<Cache> = RefCell::new(Cache::default());
+    static INITIALIZED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Exercises every pass once during `initialize`, so the first real file doesn't pay for
+/// first-use costs like linear memory growth and the allocator's first chunks
+const WARM_UP_SOURCE: &str = r#""use client";
+import { useState } from "react";
+import { Button } from "./button";
+
+export default function WarmUp({ label }: { label: string }) {
+  const [open, setOpen] = useState(false);
+  return <Button onClick={() => setOpen(!open)}>{label}</Button>;
+}
+"#;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Analyze a file, reusing the cached result if the same content was analyzed before
+pub(crate) fn analyze_cached(
+    content: Vec<u8>,
+    extension: String,
+    is_cancelled: &dyn Fn() -> bool,
+) -> Result<types::AnalysisResult, String> {
+    let hash = content_hash(&content, &extension);
+    let content_length = content.len() as u32;
+    if let Some(result) = CACHE.with_borrow(|cache| cache.get(hash, content_length).cloned()) {
+        return Ok(result);
+    }
+
+    // Only successful results are cached, errors are cheap to reproduce
+    let result = crate::analyze_source(content, extension, is_cancelled)?;
+    CACHE.with_borrow_mut(|cache| {
+        cache.insert(
+            hash,
+            CachedResult {
+                content_length,
+                analysis: result.clone(),
+            },
+        )
+    });
+    Ok(result)
+}
+
+/// Warm up the session ahead of the first analysis. Only the first call does any work; returns
+/// whether this call did it. The warm-up result is not cached.
+pub(crate) fn initialize() -> bool {
+    if INITIALIZED.replace(true) {
+        return false;
+    }
+
+    CACHE.with_borrow_mut(|cache| cache.results.reserve(64));
+    let _ = crate::analyze_source(
+        WARM_UP_SOURCE.as_bytes().to_vec(),
+        "tsx".to_string(),
+        &|| false,
+    );
+    true
+}
+
+/// Dump the session cache so the host can persist it between editor restarts
+pub(crate) fn dump_cache() -> types::CacheSnapshot {
+    let mut entries = CACHE.with_borrow(|cache| {
+        cache
+            .results
+            .iter()
+            .map(|(&hash, cached)| types::CacheEntry {
+                hash,
+                content_length: cached.content_length,
+                analysis: cached.analysis.clone(),
+            })
+            .collect::<Vec<_>>()
+    });
+    // Stable output so persisted snapshots don't churn
+    entries.sort_by_key(|entry| entry.hash);
+
+    types::CacheSnapshot {
+        version: SCHEMA_VERSION.to_string(),
+        entries,
+    }
+}
+
+/// Restore a dumped cache. Returns false (and restores nothing) if the snapshot was produced with
+/// another schema version.
+pub(crate) fn restore_cache(snapshot: types::CacheSnapshot) -> bool {
+    if snapshot.version != SCHEMA_VERSION.to_string() {
+        return false;
+    }
+
+    CACHE.with_borrow_mut(|cache| {
+        for entry in snapshot.entries {
+            cache.insert(
+                entry.hash,
+                CachedResult {
+                    content_length: entry.content_length,
+                    analysis: entry.analysis,
+                },
+            );
+        }
+    });
+    true
+}
+
+pub(crate) fn clear_cache() {
+    CACHE.with_borrow_mut(|cache| cache.clear());
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Cache key for a file. The extension is part of the key since it changes how the content is
+/// parsed.
+fn content_hash(content: &[u8], extension: &str) -> u64 {
+    let mut key = Vec::with_capacity(extension.len() + 1 + content.len());
+    key.extend_from_slice(extension.as_bytes());
+    key.push(0);
+    key.extend_from_slice(content);
+    crate::fnv1a_hash(&key)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+"use client";
+export const Button = () => <button>Click</button>;
+    "#;
+
+    fn analyze(content: &str, extension: &str) -> Result<types::AnalysisResult, String> {
+        analyze_cached(content.as_bytes().to_vec(), extension.to_string(), &|| {
+            false
+        })
+    }
+
+    #[test]
+    fn test_analyze_cached_reuses_results() {
+        clear_cache();
+
+        let first = analyze(SOURCE, "tsx").unwrap();
+        // A cancelled analysis would fail, so the second result must come from the cache
+        let second =
+            analyze_cached(SOURCE.as_bytes().to_vec(), "tsx".to_string(), &|| true).unwrap();
+
+        assert_eq!(first.components[0].id, second.components[0].id);
+        assert_eq!(dump_cache().entries.len(), 1);
+    }
+
+    #[test]
+    fn test_analyze_cached_does_not_cache_errors() {
+        clear_cache();
+
+        assert!(analyze("const x = {{{", "tsx").is_err());
+        assert!(dump_cache().entries.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_cached_extension_is_part_of_key() {
+        clear_cache();
+
+        analyze(SOURCE, "tsx").unwrap();
+        analyze(SOURCE, "jsx").unwrap();
+
+        assert_eq!(dump_cache().entries.len(), 2);
+    }
+
+    #[test]
+    fn test_dump_and_restore_cache() {
+        clear_cache();
+        analyze(SOURCE, "tsx").unwrap();
+        let snapshot = dump_cache();
+
+        clear_cache();
+        assert!(dump_cache().entries.is_empty());
+        assert!(restore_cache(snapshot));
+
+        // The restored entry is served without analyzing again
+        let restored = dump_cache();
+        assert_eq!(restored.entries.len(), 1);
+        assert_eq!(
+            restored.entries[0].hash,
+            content_hash(SOURCE.as_bytes(), "tsx")
+        );
+        assert!(analyze_cached(SOURCE.as_bytes().to_vec(), "tsx".to_string(), &|| true).is_ok());
+    }
+
+    #[test]
+    fn test_analyze_cached_checks_content_length() {
+        clear_cache();
+        analyze(SOURCE, "tsx").unwrap();
+
+        // An entry colliding with another file's hash is not served for it
+        let mut snapshot = dump_cache();
+        snapshot.entries[0].content_length += 1;
+        clear_cache();
+        assert!(restore_cache(snapshot));
+        assert!(analyze_cached(SOURCE.as_bytes().to_vec(), "tsx".to_string(), &|| true).is_err());
+    }
+
+    #[test]
+    fn test_analyze_cached_evicts_oldest_entries() {
+        clear_cache();
+
+        for index in 0..=MAX_ENTRIES {
+            analyze(&format!("export const value = {};", index), "ts").unwrap();
+        }
+
+        let snapshot = dump_cache();
+        assert_eq!(snapshot.entries.len(), MAX_ENTRIES);
+        let oldest = content_hash(b"export const value = 0;", "ts");
+        assert!(snapshot.entries.iter().all(|entry| entry.hash != oldest));
+    }
+
+    #[test]
+    fn test_restore_cache_rejects_other_versions() {
+        clear_cache();
+
+        let restored = restore_cache(types::CacheSnapshot {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            entries: Vec::new(),
+        });
+
+        assert!(!restored);
+    }
+
+    #[test]
+    fn test_initialize_runs_once_without_caching() {
+        clear_cache();
+
+        assert!(initialize());
+        assert!(!initialize());
+        assert!(dump_cache().entries.is_empty());
+        assert!(
+            crate::analyze_source(
+                WARM_UP_SOURCE.as_bytes().to_vec(),
+                "tsx".to_string(),
+                &|| { false }
+            )
+            .is_ok_and(|result| result.components.len() == 1)
+        );
+    }
+}
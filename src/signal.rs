@@ -0,0 +1,973 @@
+use crate::analyze_react_boundary::check::types;
+use crate::rules::Rule;
+use oxc::ast::ast::{
+    Argument, ArrowFunctionExpression, BindingPatternKind, Declaration,
+    ExportDefaultDeclarationKind, Expression, Function, FunctionBody, ImportDeclarationSpecifier,
+    Statement, UnaryOperator,
+};
+use oxc::ast_visit::{Visit, walk};
+use oxc::span::Span;
+use oxc::syntax::scope::ScopeFlags;
+
+/// React APIs that only exist in the react-server build
+const SERVER_ONLY_REACT_APIS: [&str; 5] = [
+    "experimental_taintObjectReference",
+    "experimental_taintUniqueValue",
+    "taintObjectReference",
+    "taintUniqueValue",
+    "createServerContext",
+];
+
+/// Entry points that can only be imported on the server. Matched exactly or as a prefix followed
+/// by "." (e.g. `react-dom/server.edge`)
+const SERVER_ONLY_MODULES: [&str; 6] = [
+    "server-only",
+    "react-dom/server",
+    "react-server-dom-webpack/server",
+    "react-server-dom-turbopack/server",
+    "react-server-dom-parcel/server",
+    "react-server-dom-esm/server",
+];
+
+/// Hooks that only work in client components, by category
+const CLIENT_HOOKS: [(&str, types::HookCategory); 15] = [
+    ("useState", types::HookCategory::State),
+    ("useReducer", types::HookCategory::State),
+    ("useContext", types::HookCategory::State),
+    ("useRef", types::HookCategory::State),
+    ("useSyncExternalStore", types::HookCategory::State),
+    ("useEffect", types::HookCategory::Effect),
+    ("useLayoutEffect", types::HookCategory::Effect),
+    ("useInsertionEffect", types::HookCategory::Effect),
+    ("useImperativeHandle", types::HookCategory::Effect),
+    ("useTransition", types::HookCategory::Transition),
+    ("useDeferredValue", types::HookCategory::Transition),
+    ("useOptimistic", types::HookCategory::Transition),
+    ("useActionState", types::HookCategory::Form),
+    ("useFormState", types::HookCategory::Form),
+    ("useFormStatus", types::HookCategory::Form),
+];
+
+/// Database client constructors and factories, by the module exporting them. Instantiating one
+/// opens connections with server credentials, so it can only happen on the server
+const DATABASE_CLIENTS: [(&str, &[&str]); 13] = [
+    ("@prisma/client", &["PrismaClient"]),
+    ("pg", &["Pool", "Client"]),
+    ("mysql2", &["createPool", "createConnection"]),
+    ("mysql2/promise", &["createPool", "createConnection"]),
+    ("mongodb", &["MongoClient"]),
+    ("drizzle-orm/node-postgres", &["drizzle"]),
+    ("drizzle-orm/postgres-js", &["drizzle"]),
+    ("drizzle-orm/mysql2", &["drizzle"]),
+    ("drizzle-orm/neon-http", &["drizzle"]),
+    ("drizzle-orm/vercel-postgres", &["drizzle"]),
+    ("drizzle-orm/planetscale-serverless", &["drizzle"]),
+    ("drizzle-orm/better-sqlite3", &["drizzle"]),
+    ("drizzle-orm/libsql", &["drizzle"]),
+];
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Signals found in a module, and diagnostics for signals that contradict the module's directive
+#[derive(Default)]
+pub(crate) struct SignalCollection {
+    pub(crate) signals: Vec<(types::SignalKind, String, Span)>,
+    pub(crate) diagnostics: Vec<(Rule, String, Span)>,
+}
+
+/// Collect signals hinting at which side of the boundary a module runs on
+pub(crate) fn collect_signals(statements: &[Statement], use_client: bool) -> SignalCollection {
+    let mut collection = SignalCollection::default();
+
+    collect_server_import_signals(statements, &mut collection);
+    collect_server_api_signals(statements, &mut collection);
+    collect_hydration_signals(statements, &mut collection);
+    collect_client_hook_signals(statements, &mut collection);
+    collect_database_client_signals(statements, &mut collection);
+    collect_dynamic_code_signals(statements, &mut collection);
+    collect_vite_client_signals(statements, &mut collection);
+
+    if use_client {
+        collection.diagnostics = collection
+            .signals
+            .iter()
+            .filter_map(|(kind, name, span)| {
+                let (rule, message) = match kind {
+                    types::SignalKind::HydrationSensitive
+                    | types::SignalKind::SensitiveProp
+                    | types::SignalKind::ClientHook
+                    | types::SignalKind::ClientPackageImport
+                    | types::SignalKind::ServerPackageImport
+                    | types::SignalKind::DynamicCode
+                    | types::SignalKind::ViteClientApi => {
+                        return None;
+                    }
+                    types::SignalKind::ServerOnlyApi => (
+                        Rule::ServerOnlyApi,
+                        format!(
+                            "{} is a server-only API and cannot be used in a \"use client\" module",
+                            name
+                        ),
+                    ),
+                    types::SignalKind::DatabaseClient => (
+                        Rule::DatabaseClient,
+                        format!(
+                            "{} creates a database client and cannot be used in a \"use client\" \
+                             module",
+                            name
+                        ),
+                    ),
+                    types::SignalKind::ServerOnlyImport => (
+                        Rule::ServerOnlyImport,
+                        format!(
+                            "\"{}\" is a server-only entry point and cannot be imported in a \
+                             \"use client\" module",
+                            name
+                        ),
+                    ),
+                };
+                Some((rule, message, *span))
+            })
+            .collect();
+    }
+
+    collection
+}
+
+/// Category of a client hook signal's hook
+pub(crate) fn hook_category(hook: &str) -> Option<types::HookCategory> {
+    CLIENT_HOOKS
+        .iter()
+        .find(|(name, _)| *name == hook)
+        .map(|(_, category)| *category)
+}
+
+/// Imports of packages the host knows to be client-only (`framer-motion`, `react-chartjs-2`), or
+/// of their subpaths, in a module without "use client". Such a module can only render on the
+/// client, which the host can point out without analyzing the package itself
+pub(crate) fn collect_client_package_signals(
+    statements: &[Statement],
+    packages: &[String],
+    collection: &mut SignalCollection,
+) {
+    for (source, span) in package_imports(statements, packages) {
+        collection.signals.push((
+            types::SignalKind::ClientPackageImport,
+            source.to_string(),
+            span,
+        ));
+    }
+}
+
+/// Imports of packages the host knows to be server-only (`pg`, `@prisma/client`), or of their
+/// subpaths. In a "use client" module they conflict with the directive and are diagnosed
+pub(crate) fn collect_server_package_signals(
+    statements: &[Statement],
+    packages: &[String],
+    use_client: bool,
+    collection: &mut SignalCollection,
+) {
+    for (source, span) in package_imports(statements, packages) {
+        collection.signals.push((
+            types::SignalKind::ServerPackageImport,
+            source.to_string(),
+            span,
+        ));
+        if use_client {
+            collection.diagnostics.push((
+                Rule::ServerPackageImport,
+                format!(
+                    "\"{}\" is a server-only package and cannot be imported in a \"use client\" \
+                     module",
+                    source
+                ),
+                span,
+            ));
+        }
+    }
+}
+
+/// Whether `source` is one of the server-only entry points
+pub(crate) fn is_server_only_module(source: &str) -> bool {
+    SERVER_ONLY_MODULES.iter().any(|module| {
+        source
+            .strip_prefix(module)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    })
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Imports (including type-only ones, which still mark server code) of server-only entry points
+fn collect_server_import_signals(statements: &[Statement], collection: &mut SignalCollection) {
+    for statement in statements {
+        let Statement::ImportDeclaration(import_decl) = statement else {
+            continue;
+        };
+        let source = import_decl.source.value.as_str();
+        if is_server_only_module(source) {
+            collection.signals.push((
+                types::SignalKind::ServerOnlyImport,
+                source.to_string(),
+                import_decl.source.span,
+            ));
+        }
+    }
+}
+
+/// Value imports of any of `packages` or of their subpaths, as (source, span of the source)
+fn package_imports<'s>(
+    statements: &'s [Statement],
+    packages: &'s [String],
+) -> impl Iterator<Item = (&'s str, Span)> {
+    statements.iter().filter_map(move |statement| {
+        let Statement::ImportDeclaration(import_decl) = statement else {
+            return None;
+        };
+        if import_decl.import_kind.is_type() {
+            return None;
+        }
+        let source = import_decl.source.value.as_str();
+        packages
+            .iter()
+            .any(|package| {
+                source
+                    .strip_prefix(package.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+            .then_some((source, import_decl.source.span))
+    })
+}
+
+/// Server-only API imports from "react" and their call sites, either through the imported binding
+/// or through a namespace (`React.experimental_taintUniqueValue(...)`)
+fn collect_server_api_signals(statements: &[Statement], collection: &mut SignalCollection) {
+    let (bindings, namespaces) = imported_apis(statements, &["react"], &SERVER_ONLY_REACT_APIS);
+
+    for (_, api, span) in bindings.iter() {
+        collection
+            .signals
+            .push((types::SignalKind::ServerOnlyApi, api.clone(), *span));
+    }
+
+    for (api, span) in find_api_calls(statements, &bindings, &namespaces, &SERVER_ONLY_REACT_APIS) {
+        collection
+            .signals
+            .push((types::SignalKind::ServerOnlyApi, api, span));
+    }
+}
+
+/// Call sites of the interactivity hooks of "react" and "react-dom" (`useState`,
+/// `useTransition`, ...), which need the module to run on the client
+fn collect_client_hook_signals(statements: &[Statement], collection: &mut SignalCollection) {
+    let hooks = CLIENT_HOOKS.map(|(hook, _)| hook);
+    let (bindings, namespaces) = imported_apis(statements, &["react", "react-dom"], &hooks);
+
+    for (hook, span) in find_api_calls(statements, &bindings, &namespaces, &hooks) {
+        collection
+            .signals
+            .push((types::SignalKind::ClientHook, hook, span));
+    }
+}
+
+/// Instantiations of database clients (`new PrismaClient()`, `createPool(...)`, `drizzle(...)`)
+fn collect_database_client_signals(statements: &[Statement], collection: &mut SignalCollection) {
+    for (module, apis) in DATABASE_CLIENTS {
+        let (bindings, namespaces) = imported_apis(statements, &[module], apis);
+
+        for (api, span) in find_api_calls(statements, &bindings, &namespaces, apis) {
+            collection
+                .signals
+                .push((types::SignalKind::DatabaseClient, api, span));
+        }
+    }
+}
+
+/// Code evaluated from strings (`eval(...)`, `new Function(...)`, `Function(...)`). Bundlers can't
+/// follow it across the boundary, and it is blocked by a Content-Security-Policy without
+/// `unsafe-eval`
+fn collect_dynamic_code_signals(statements: &[Statement], collection: &mut SignalCollection) {
+    let mut finder = DynamicCodeFinder::default();
+    for statement in statements {
+        finder.visit_statement(statement);
+    }
+    collection.signals.extend(
+        finder
+            .found
+            .into_iter()
+            .map(|(name, span)| (types::SignalKind::DynamicCode, name, span)),
+    );
+}
+
+#[derive(Default)]
+struct DynamicCodeFinder {
+    found: Vec<(String, Span)>,
+}
+
+impl<'a> Visit<'a> for DynamicCodeFinder {
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        let signal = match expr {
+            Expression::CallExpression(call) => match call.callee.get_inner_expression() {
+                Expression::Identifier(ident)
+                    if matches!(ident.name.as_str(), "eval" | "Function") =>
+                {
+                    Some((ident.name.to_string(), call.span))
+                }
+                _ => None,
+            },
+            Expression::NewExpression(new) => match new.callee.get_inner_expression() {
+                Expression::Identifier(ident) if ident.name == "Function" => {
+                    Some(("new Function".to_string(), new.span))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        self.found.extend(signal);
+        walk::walk_expression(self, expr);
+    }
+}
+
+/// Uses of Vite's client-only `import.meta.hot` API, which only exists in the browser during
+/// development
+fn collect_vite_client_signals(statements: &[Statement], collection: &mut SignalCollection) {
+    for access in crate::import_meta::collect_import_meta_accesses(statements) {
+        if access.property == "hot" {
+            collection.signals.push((
+                types::SignalKind::ViteClientApi,
+                "import.meta.hot".to_string(),
+                access.span,
+            ));
+        }
+    }
+}
+
+/// Find the imports of `apis` from `modules`. Returns the bindings as (local name, API name,
+/// specifier span), and the local names of the modules' namespaces (default and namespace
+/// imports). Type-only imports are skipped
+pub(crate) fn imported_apis(
+    statements: &[Statement],
+    modules: &[&str],
+    apis: &[&str],
+) -> (Vec<(String, String, Span)>, Vec<String>) {
+    let mut bindings = Vec::new();
+    let mut namespaces = Vec::new();
+
+    for statement in statements {
+        let Statement::ImportDeclaration(import_decl) = statement else {
+            continue;
+        };
+        if !modules.contains(&import_decl.source.value.as_str())
+            || import_decl.import_kind.is_type()
+        {
+            continue;
+        }
+
+        for specifier in import_decl.specifiers.iter().flatten() {
+            match specifier {
+                ImportDeclarationSpecifier::ImportSpecifier(spec) => {
+                    let imported = crate::module_export_name(&spec.imported);
+                    if !spec.import_kind.is_type() && apis.contains(&imported.as_str()) {
+                        bindings.push((spec.local.name.to_string(), imported, spec.span));
+                    }
+                }
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                    namespaces.push(spec.local.name.to_string());
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
+                    namespaces.push(spec.local.name.to_string());
+                }
+            }
+        }
+    }
+
+    (bindings, namespaces)
+}
+
+/// API name and call span of every call (or `new` expression) of an imported API, either through
+/// its binding or through a namespace
+fn find_api_calls(
+    statements: &[Statement],
+    bindings: &[(String, String, Span)],
+    namespaces: &[String],
+    apis: &[&str],
+) -> Vec<(String, Span)> {
+    if bindings.is_empty() && namespaces.is_empty() {
+        return Vec::new();
+    }
+
+    let mut finder = ApiCallFinder {
+        bindings,
+        namespaces,
+        apis,
+        calls: Vec::new(),
+    };
+    for statement in statements {
+        finder.visit_statement(statement);
+    }
+    finder.calls
+}
+
+struct ApiCallFinder<'c> {
+    bindings: &'c [(String, String, Span)],
+    namespaces: &'c [String],
+    apis: &'c [&'c str],
+    calls: Vec<(String, Span)>,
+}
+
+impl<'a> Visit<'a> for ApiCallFinder<'_> {
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        let call = match expr {
+            Expression::CallExpression(call) => Some((&call.callee, call.span)),
+            Expression::NewExpression(new) => Some((&new.callee, new.span)),
+            _ => None,
+        };
+        if let Some((callee, span)) = call {
+            let api = match callee.get_inner_expression() {
+                Expression::Identifier(ident) => self
+                    .bindings
+                    .iter()
+                    .find(|(local, _, _)| local == ident.name.as_str())
+                    .map(|(_, api, _)| api.clone()),
+                Expression::StaticMemberExpression(member) => match &member.object {
+                    Expression::Identifier(object)
+                        if self.namespaces.iter().any(|ns| ns == object.name.as_str())
+                            && self.apis.contains(&member.property.name.as_str()) =>
+                    {
+                        Some(member.property.name.to_string())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(api) = api {
+                self.calls.push((api, span));
+            }
+        }
+        walk::walk_expression(self, expr);
+    }
+}
+
+/// Render-time code whose result can differ between the server render and hydration:
+/// `useSyncExternalStore`, `Date.now()`, `Math.random()` and `typeof window` checks. Only the
+/// render path of components is searched, since effects and event handlers never run on the server
+fn collect_hydration_signals(statements: &[Statement], collection: &mut SignalCollection) {
+    let mut finder = HydrationFinder::default();
+    for body in component_bodies(statements) {
+        finder.visit_function_body(body);
+    }
+    collection.signals.extend(
+        finder
+            .found
+            .into_iter()
+            .map(|(name, span)| (types::SignalKind::HydrationSensitive, name, span)),
+    );
+}
+
+/// Finds hydration-sensitive code on the render path of a component body, without entering the
+/// functions nested in it
+#[derive(Default)]
+struct HydrationFinder {
+    found: Vec<(String, Span)>,
+}
+
+impl<'a> Visit<'a> for HydrationFinder {
+    fn visit_function(&mut self, _func: &Function<'a>, _flags: ScopeFlags) {}
+
+    fn visit_arrow_function_expression(&mut self, _arrow: &ArrowFunctionExpression<'a>) {}
+
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        let signal = match expr {
+            Expression::CallExpression(call) => match call.callee.get_inner_expression() {
+                Expression::Identifier(ident) if ident.name == "useSyncExternalStore" => {
+                    Some(("useSyncExternalStore".to_string(), call.span))
+                }
+                Expression::StaticMemberExpression(member) => match &member.object {
+                    Expression::Identifier(object)
+                        if matches!(
+                            (object.name.as_str(), member.property.name.as_str()),
+                            ("Date", "now") | ("Math", "random")
+                        ) =>
+                    {
+                        Some((
+                            format!("{}.{}", object.name, member.property.name),
+                            call.span,
+                        ))
+                    }
+                    _ if member.property.name == "useSyncExternalStore" => {
+                        Some(("useSyncExternalStore".to_string(), call.span))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            },
+            Expression::UnaryExpression(unary)
+                if unary.operator == UnaryOperator::Typeof
+                    && matches!(&unary.argument, Expression::Identifier(ident) if ident.name == "window") =>
+            {
+                Some(("typeof window".to_string(), unary.span))
+            }
+            _ => None,
+        };
+        self.found.extend(signal);
+        walk::walk_expression(self, expr);
+    }
+}
+
+/// Bodies of the module's top-level components: PascalCase functions, optionally wrapped in a
+/// call like `memo(...)`, and default exported functions
+fn component_bodies<'s, 'a>(statements: &'s [Statement<'a>]) -> Vec<&'s FunctionBody<'a>> {
+    fn is_component_name(name: &str) -> bool {
+        name.starts_with(|c: char| c.is_ascii_uppercase())
+    }
+
+    fn expression_body<'s, 'a>(expr: &'s Expression<'a>) -> Option<&'s FunctionBody<'a>> {
+        match expr.get_inner_expression() {
+            Expression::ArrowFunctionExpression(arrow) => Some(&arrow.body),
+            Expression::FunctionExpression(func) => func.body.as_deref(),
+            Expression::CallExpression(call) => match call.arguments.first() {
+                Some(Argument::SpreadElement(_)) | None => None,
+                Some(argument) => argument.as_expression().and_then(expression_body),
+            },
+            _ => None,
+        }
+    }
+
+    let mut bodies = Vec::new();
+    let visit_declaration =
+        |declaration: &'s Declaration<'a>, bodies: &mut Vec<_>| match declaration {
+            Declaration::FunctionDeclaration(func) => {
+                if func
+                    .id
+                    .as_ref()
+                    .is_some_and(|id| is_component_name(&id.name))
+                    && let Some(body) = &func.body
+                {
+                    bodies.push(body.as_ref());
+                }
+            }
+            Declaration::VariableDeclaration(var_decl) => {
+                for declarator in var_decl.declarations.iter() {
+                    if let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind
+                        && is_component_name(&ident.name)
+                        && let Some(body) = declarator.init.as_ref().and_then(expression_body)
+                    {
+                        bodies.push(body);
+                    }
+                }
+            }
+            _ => {}
+        };
+
+    for statement in statements {
+        match statement {
+            Statement::ExportNamedDeclaration(export_decl) => {
+                if let Some(declaration) = &export_decl.declaration {
+                    visit_declaration(declaration, &mut bodies);
+                }
+            }
+            Statement::ExportDefaultDeclaration(export_decl) => match &export_decl.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                    if let Some(body) = &func.body {
+                        bodies.push(body.as_ref());
+                    }
+                }
+                declaration => {
+                    if let Some(body) = declaration.as_expression().and_then(expression_body) {
+                        bodies.push(body);
+                    }
+                }
+            },
+            statement => {
+                if let Some(declaration) = statement.as_declaration() {
+                    visit_declaration(declaration, &mut bodies);
+                }
+            }
+        }
+    }
+
+    bodies
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    fn collect(source: &str) -> SignalCollection {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let use_client = ret
+            .program
+            .directives
+            .iter()
+            .any(|directive| directive.directive == "use client");
+        collect_signals(&ret.program.body, use_client)
+    }
+
+    #[test]
+    fn test_collect_taint_imports_and_calls() {
+        let source = r#"
+import { experimental_taintObjectReference as taintRef } from "react";
+import * as React from "react";
+
+export async function getUser() {
+  const user = await db.user();
+  taintRef("Do not pass the user object to the client", user);
+  React.experimental_taintUniqueValue("Do not pass the token", user, user.token);
+  return user;
+}
+        "#;
+
+        let collection = collect(source);
+
+        let names = collection
+            .signals
+            .iter()
+            .map(|(_, name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "experimental_taintObjectReference",
+                "experimental_taintObjectReference",
+                "experimental_taintUniqueValue"
+            ]
+        );
+        assert!(collection.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_collect_taint_in_client_module_is_diagnosed() {
+        let source = r#""use client";
+import { experimental_taintUniqueValue } from "react";
+experimental_taintUniqueValue("secret", globalThis, process.env.KEY);
+        "#;
+
+        let collection = collect(source);
+
+        assert_eq!(collection.signals.len(), 2);
+        assert_eq!(collection.diagnostics.len(), 2);
+        assert!(collection.diagnostics[0].1.contains("server-only API"));
+    }
+
+    #[test]
+    fn test_collect_ignores_unrelated_calls() {
+        let source = r#"
+import { useState } from "./state";
+import { experimental_taintUniqueValue } from "./local";
+experimental_taintUniqueValue();
+useState();
+        "#;
+
+        assert!(collect(source).signals.is_empty());
+    }
+
+    #[test]
+    fn test_collect_server_only_imports() {
+        let source = r#""use client";
+import "server-only";
+import { renderToReadableStream } from "react-dom/server.edge";
+import { createFromFetch } from "react-server-dom-webpack/client";
+import { hydrateRoot } from "react-dom/client";
+        "#;
+
+        let collection = collect(source);
+
+        let names = collection
+            .signals
+            .iter()
+            .map(|(kind, name, _)| {
+                assert_eq!(*kind, types::SignalKind::ServerOnlyImport);
+                name.as_str()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["server-only", "react-dom/server.edge"]);
+        assert_eq!(
+            collection.diagnostics[0].1,
+            "\"server-only\" is a server-only entry point and cannot be imported in a \"use client\" module"
+        );
+    }
+
+    #[test]
+    fn test_collect_create_server_context() {
+        let source = r#"
+import { createServerContext } from "react";
+export const Theme = createServerContext("theme", "light");
+        "#;
+
+        let collection = collect(source);
+
+        assert_eq!(collection.signals.len(), 2);
+        assert!(
+            collection
+                .signals
+                .iter()
+                .all(|(_, name, _)| name == "createServerContext")
+        );
+    }
+
+    #[test]
+    fn test_collect_hydration_signals_in_render_path() {
+        let source = r#"
+import { useEffect, useSyncExternalStore } from "react";
+
+export default function Clock() {
+  const online = useSyncExternalStore(subscribe, getSnapshot, getServerSnapshot);
+  const id = Math.random();
+  const isBrowser = typeof window !== "undefined";
+  useEffect(() => {
+    console.log(Date.now(), typeof window);
+  }, []);
+  return <button onClick={() => Math.random()}>{Date.now()}</button>;
+}
+
+export const Badge = memo(() => <span>{Math.random()}</span>);
+
+function helper() {
+  return Date.now();
+}
+        "#;
+
+        let collection = collect(source);
+
+        let names = collection
+            .signals
+            .iter()
+            .filter(|(kind, _, _)| *kind == types::SignalKind::HydrationSensitive)
+            .map(|(_, name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "useSyncExternalStore",
+                "Math.random",
+                "typeof window",
+                "Date.now",
+                "Math.random"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hydration_signals_are_not_diagnosed() {
+        let source = r#""use client";
+export const Now = () => <time>{Date.now()}</time>;
+        "#;
+
+        let collection = collect(source);
+
+        assert_eq!(collection.signals.len(), 1);
+        assert!(collection.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_collect_client_package_imports() {
+        let source = r#"
+import { motion } from "framer-motion";
+import { animate } from "framer-motion/dom";
+import type { Variants } from "framer-motion";
+import { Line } from "react-chartjs-2";
+import { helper } from "framer-motion-utils";
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let mut collection = SignalCollection::default();
+        collect_client_package_signals(
+            &ret.program.body,
+            &["framer-motion".to_string()],
+            &mut collection,
+        );
+
+        let names = collection
+            .signals
+            .iter()
+            .map(|(kind, name, _)| {
+                assert_eq!(*kind, types::SignalKind::ClientPackageImport);
+                name.as_str()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["framer-motion", "framer-motion/dom"]);
+    }
+
+    #[test]
+    fn test_collect_server_package_imports_in_client_module() {
+        let source = r#"
+"use client";
+import { PrismaClient } from "@prisma/client";
+import type { Pool } from "pg";
+import { Pool as PgPool } from "pg";
+import { readFile } from "fs-extra/esm";
+import { pgTable } from "pgx";
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let mut collection = SignalCollection::default();
+        collect_server_package_signals(
+            &ret.program.body,
+            &[
+                "pg".to_string(),
+                "@prisma/client".to_string(),
+                "fs-extra".to_string(),
+            ],
+            true,
+            &mut collection,
+        );
+
+        let names = collection
+            .signals
+            .iter()
+            .map(|(kind, name, _)| {
+                assert_eq!(*kind, types::SignalKind::ServerPackageImport);
+                name.as_str()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["@prisma/client", "pg", "fs-extra/esm"]);
+        assert_eq!(collection.diagnostics.len(), 3);
+        assert_eq!(
+            collection.diagnostics[0].1,
+            "\"@prisma/client\" is a server-only package and cannot be imported in a \"use client\" module"
+        );
+    }
+
+    #[test]
+    fn test_collect_database_client_instantiations() {
+        let source = r#"
+"use client";
+import { PrismaClient } from "@prisma/client";
+import pg from "pg";
+import { createPool } from "mysql2/promise";
+import { drizzle } from "drizzle-orm/node-postgres";
+import { Pool } from "./pool";
+
+const prisma = new PrismaClient();
+const pool = new pg.Pool({ connectionString: process.env.DATABASE_URL });
+const mysql = createPool({ host: "localhost" });
+const db = drizzle(pool);
+const local = new Pool();
+        "#;
+
+        let collection = collect(source);
+        let names = collection
+            .signals
+            .iter()
+            .map(|(kind, name, _)| {
+                assert_eq!(*kind, types::SignalKind::DatabaseClient);
+                name.as_str()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["PrismaClient", "Pool", "createPool", "drizzle"]);
+        assert_eq!(collection.diagnostics.len(), 4);
+        assert_eq!(
+            collection.diagnostics[0].1,
+            "PrismaClient creates a database client and cannot be used in a \"use client\" module"
+        );
+    }
+
+    #[test]
+    fn test_collect_dynamic_code() {
+        let source = r#""use client";
+const run = (code) => eval(code);
+export function Calculator() {
+  const add = new Function("a", "b", "return a + b");
+  const sub = Function("a", "b", "return a - b");
+  return <output>{run("1 + 1")}</output>;
+}
+@register(eval("plugin"))
+class Plugin {}
+results[eval("key")] = 1;
+        "#;
+
+        let collection = collect(source);
+        let names = collection
+            .signals
+            .iter()
+            .map(|(kind, name, _)| {
+                assert_eq!(*kind, types::SignalKind::DynamicCode);
+                name.as_str()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["eval", "new Function", "Function", "eval", "eval"]
+        );
+        assert!(collection.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_collect_vite_client_api() {
+        let source = r#"
+export function Counter() {
+  if (import.meta.hot) {
+    import.meta.hot.accept();
+  }
+  return <div>{import.meta.env.MODE}</div>;
+}
+        "#;
+
+        let collection = collect(source);
+        let signals = collection
+            .signals
+            .iter()
+            .map(|(kind, name, _)| (*kind, name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            signals,
+            vec![
+                (types::SignalKind::ViteClientApi, "import.meta.hot"),
+                (types::SignalKind::ViteClientApi, "import.meta.hot"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_client_hooks_by_category() {
+        let source = r#""use client";
+import React, { useState as useLocalState, useTransition, useOptimistic } from "react";
+import { useFormStatus } from "react-dom";
+import { useQuery } from "./query";
+
+export function Form({ items }) {
+  const [value, setValue] = useLocalState("");
+  const [isPending, startTransition] = useTransition();
+  const [optimistic, addOptimistic] = useOptimistic(items);
+  const { pending } = useFormStatus();
+  React.useEffect(() => {}, []);
+  const data = useQuery();
+  return <input value={value} />;
+}
+        "#;
+
+        let collection = collect(source);
+
+        let hooks = collection
+            .signals
+            .iter()
+            .filter(|(kind, _, _)| *kind == types::SignalKind::ClientHook)
+            .map(|(_, name, _)| (name.as_str(), hook_category(name)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            hooks,
+            vec![
+                ("useState", Some(types::HookCategory::State)),
+                ("useTransition", Some(types::HookCategory::Transition)),
+                ("useOptimistic", Some(types::HookCategory::Transition)),
+                ("useFormStatus", Some(types::HookCategory::Form)),
+                ("useEffect", Some(types::HookCategory::Effect)),
+            ]
+        );
+        assert!(collection.diagnostics.is_empty());
+    }
+}
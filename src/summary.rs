@@ -0,0 +1,171 @@
+use crate::analyze_react_boundary::check::types;
+use crate::graph::ModuleGraph;
+use std::collections::{HashSet, VecDeque};
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Summarize the client boundary of a file set for a project-level overview:
+/// - how many files are client entries (`"use client"`) and how many stay on the server
+/// - the client entry pulling the most files of the set into the client bundle
+/// - the server files importing a client module directly
+/// - the exported components no file of the set renders
+///
+/// App router entries (`page`, `layout`, ...) are rendered by the framework, so they never count
+/// as unrendered. Files that fail to analyze are left out of the summary.
+pub(crate) fn summarize(
+    files: Vec<types::SourceFile>,
+    is_cancelled: &dyn Fn() -> bool,
+) -> types::ProjectSummary {
+    let graph = ModuleGraph::new(&files);
+    let analyses = crate::unused::analyze_modules(files, &graph, is_cancelled);
+
+    let mut client_entries = graph
+        .modules
+        .iter()
+        .filter(|(_, module)| module.is_client)
+        .map(|(path, _)| path.as_str())
+        .collect::<Vec<_>>();
+    client_entries.sort_unstable();
+
+    let largest_client_subtree = client_entries
+        .iter()
+        .map(|path| types::ClientSubtree {
+            path: path.to_string(),
+            file_count: subtree_size(&graph, path),
+        })
+        // Ties go to the first entry by path
+        .reduce(|largest, subtree| {
+            if subtree.file_count > largest.file_count {
+                subtree
+            } else {
+                largest
+            }
+        });
+
+    let mut server_files_importing_client = graph
+        .modules
+        .iter()
+        .filter(|(path, module)| {
+            !module.is_client
+                && graph.dependencies(path).iter().any(|dependency| {
+                    graph
+                        .modules
+                        .get(dependency)
+                        .is_some_and(|dependency| dependency.is_client)
+                })
+        })
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<_>>();
+    server_files_importing_client.sort();
+
+    let unrendered_components = crate::unused::unused_components(&graph, &analyses);
+
+    types::ProjectSummary {
+        file_count: graph.modules.len() as u32,
+        client_entry_count: client_entries.len() as u32,
+        server_file_count: (graph.modules.len() - client_entries.len()) as u32,
+        component_count: analyses
+            .iter()
+            .map(|(_, analysis)| analysis.components.len() as u32)
+            .sum(),
+        largest_client_subtree,
+        server_files_importing_client,
+        unrendered_components,
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Number of files of the set reachable from `path` through imports, `path` included
+fn subtree_size(graph: &ModuleGraph, path: &str) -> u32 {
+    let mut visited = HashSet::from([path.to_string()]);
+    let mut queue = VecDeque::from([path.to_string()]);
+    while let Some(path) = queue.pop_front() {
+        for dependency in graph.dependencies(&path) {
+            if visited.insert(dependency.clone()) {
+                queue.push_back(dependency);
+            }
+        }
+    }
+    visited.len() as u32
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::source_file;
+
+    #[test]
+    fn test_summarize() {
+        let files = vec![
+            source_file(
+                "/app/page.tsx",
+                r#"
+import { Toolbar } from "./components";
+import { Card } from "./card";
+export default function Page() {
+  return <Toolbar />;
+}
+                "#,
+            ),
+            source_file(
+                "/app/components.ts",
+                r#"export { Toolbar } from "./toolbar";"#,
+            ),
+            source_file(
+                "/app/toolbar.tsx",
+                r#""use client";
+import { Button } from "./button";
+import { format } from "./format";
+export const Toolbar = () => <Button label={format("Save")} />;
+                "#,
+            ),
+            source_file(
+                "/app/button.tsx",
+                r#""use client"; export const Button = () => <button />;"#,
+            ),
+            source_file(
+                "/app/format.ts",
+                r#"export const format = (label) => label;"#,
+            ),
+            source_file(
+                "/app/card.tsx",
+                r#"
+export const Card = () => <div />;
+export const CardHeader = () => <header />;
+                "#,
+            ),
+            source_file("/app/broken.tsx", "export const = ;"),
+        ];
+
+        let summary = summarize(files, &|| false);
+
+        assert_eq!(summary.client_entry_count, 2);
+        assert_eq!(summary.server_file_count, 4);
+        let largest = summary.largest_client_subtree.unwrap();
+        assert_eq!(largest.path, "/app/toolbar.tsx");
+        assert_eq!(largest.file_count, 3);
+        // The page reaches the toolbar only through the barrel
+        assert_eq!(
+            summary.server_files_importing_client,
+            vec!["/app/components.ts"]
+        );
+        let unrendered = summary
+            .unrendered_components
+            .iter()
+            .map(|component| (component.path.as_str(), component.name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            unrendered,
+            vec![("/app/card.tsx", "Card"), ("/app/card.tsx", "CardHeader")]
+        );
+    }
+}
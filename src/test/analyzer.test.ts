@@ -27,6 +27,11 @@ suite('Document Analysis', () => {
       log: (_msg: string) => {
         // Silent in tests
       },
+      isCancelled: () => false,
+      now: () => BigInt(Math.floor(performance.now())),
+      reportProgress: (_done: number, _total: number, _currentPath: string) => {},
+      resolve: (_specifier: string, _from: string) => undefined,
+      readFile: (_path: string) => undefined,
     };
 
     const wasmContext = new WasmContext.Default();
@@ -63,6 +63,11 @@ suite('WASM Module Integration', () => {
       log: (_msg: string) => {
         // Silent in tests
       },
+      isCancelled: () => false,
+      now: () => BigInt(Math.floor(performance.now())),
+      reportProgress: (_done: number, _total: number, _currentPath: string) => {},
+      resolve: (_specifier: string, _from: string) => undefined,
+      readFile: (_path: string) => undefined,
     };
 
     const wasmContext = new WasmContext.Default();
@@ -473,6 +478,11 @@ suite('WASM Binding Functions', () => {
       log: (_msg: string) => {
         // Silent in tests
       },
+      isCancelled: () => false,
+      now: () => BigInt(Math.floor(performance.now())),
+      reportProgress: (_done: number, _total: number, _currentPath: string) => {},
+      resolve: (_specifier: string, _from: string) => undefined,
+      readFile: (_path: string) => undefined,
     };
 
     const wasmContext = new WasmContext.Default();
@@ -507,6 +517,11 @@ suite('WASM Binding Functions', () => {
       log: (_msg: string) => {
         // Silent in tests
       },
+      isCancelled: () => false,
+      now: () => BigInt(Math.floor(performance.now())),
+      reportProgress: (_done: number, _total: number, _currentPath: string) => {},
+      resolve: (_specifier: string, _from: string) => undefined,
+      readFile: (_path: string) => undefined,
     };
 
     // The module-level bind function is auto-generated code from WASM component model tooling.
@@ -552,6 +567,11 @@ suite('Example Files Integration', () => {
       log: (_msg: string) => {
         // Silent in tests
       },
+      isCancelled: () => false,
+      now: () => BigInt(Math.floor(performance.now())),
+      reportProgress: (_done: number, _total: number, _currentPath: string) => {},
+      resolve: (_specifier: string, _from: string) => undefined,
+      readFile: (_path: string) => undefined,
     };
 
     const wasmContext = new WasmContext.Default();
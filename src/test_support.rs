@@ -0,0 +1,17 @@
+//! Fixtures shared by the unit tests of several modules.
+
+use crate::analyze_react_boundary::check::types;
+
+/// Extension of a fixture path, which decides how its content is parsed
+pub(crate) fn extension(path: &str) -> String {
+    path.rsplit('.').next().unwrap_or_default().to_string()
+}
+
+/// A source file with the extension of its path
+pub(crate) fn source_file(path: &str, content: &str) -> types::SourceFile {
+    types::SourceFile {
+        path: path.to_string(),
+        content: content.as_bytes().to_vec(),
+        extension: extension(path),
+    }
+}
@@ -0,0 +1,221 @@
+use crate::analyze_react_boundary::check::types;
+use oxc::ast::ast::{Declaration, PropertyKey, Statement, TSSignature, TSType, TSTypeName};
+use oxc::span::Span;
+
+/// Types whose values can't be passed from a server component to a client component as props
+const NON_SERIALIZABLE_TYPES: [&str; 7] = [
+    "Function", "Date", "Map", "Set", "WeakMap", "WeakSet", "RegExp",
+];
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// A member of an exported interface or object type
+pub(crate) struct TypeMember {
+    pub(crate) name: String,
+    pub(crate) optional: bool,
+    /// Whether values of the member's type obviously can't cross the client boundary: functions,
+    /// methods, and the types of `NON_SERIALIZABLE_TYPES`
+    pub(crate) non_serializable: bool,
+    pub(crate) span: Span,
+}
+
+/// An exported `interface` or `type` declaration
+pub(crate) struct TypeExport {
+    pub(crate) name: String,
+    pub(crate) kind: types::TypeExportKind,
+    /// Members of the interface, or of the object types the alias consists of. Members inherited
+    /// through `extends` or other referenced types are not followed
+    pub(crate) members: Vec<TypeMember>,
+    /// Span of the declaration identifier
+    pub(crate) span: Span,
+}
+
+/// Collect the interfaces and type aliases a module exports directly (`export interface Props`,
+/// `export type Props = ...`), with their members, so props types can be audited for values that
+/// can't be serialized across the client boundary
+pub(crate) fn collect_type_exports(statements: &[Statement]) -> Vec<TypeExport> {
+    let mut exports = Vec::new();
+
+    for statement in statements {
+        let Statement::ExportNamedDeclaration(export_decl) = statement else {
+            continue;
+        };
+        match &export_decl.declaration {
+            Some(Declaration::TSInterfaceDeclaration(interface)) => exports.push(TypeExport {
+                name: interface.id.name.to_string(),
+                kind: types::TypeExportKind::Interface,
+                members: interface
+                    .body
+                    .body
+                    .iter()
+                    .filter_map(signature_member)
+                    .collect(),
+                span: interface.id.span,
+            }),
+            Some(Declaration::TSTypeAliasDeclaration(alias)) => {
+                let mut members = Vec::new();
+                collect_literal_members(&alias.type_annotation, &mut members);
+                exports.push(TypeExport {
+                    name: alias.id.name.to_string(),
+                    kind: types::TypeExportKind::Alias,
+                    members,
+                    span: alias.id.span,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    exports
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Members of the object type literals of `ts_type`, looking into intersections
+/// (`BaseProps & { onSelect: () => void }`)
+fn collect_literal_members(ts_type: &TSType, members: &mut Vec<TypeMember>) {
+    match ts_type {
+        TSType::TSTypeLiteral(literal) => {
+            members.extend(literal.members.iter().filter_map(signature_member));
+        }
+        TSType::TSIntersectionType(intersection) => {
+            for part in intersection.types.iter() {
+                collect_literal_members(part, members);
+            }
+        }
+        TSType::TSParenthesizedType(parenthesized) => {
+            collect_literal_members(&parenthesized.type_annotation, members);
+        }
+        _ => {}
+    }
+}
+
+fn signature_member(signature: &TSSignature) -> Option<TypeMember> {
+    match signature {
+        TSSignature::TSPropertySignature(property) => Some(TypeMember {
+            name: key_name(&property.key)?,
+            optional: property.optional,
+            non_serializable: property
+                .type_annotation
+                .as_ref()
+                .is_some_and(|annotation| is_non_serializable(&annotation.type_annotation)),
+            span: property.span,
+        }),
+        TSSignature::TSMethodSignature(method) => Some(TypeMember {
+            name: key_name(&method.key)?,
+            optional: method.optional,
+            non_serializable: true,
+            span: method.span,
+        }),
+        _ => None,
+    }
+}
+
+fn key_name(key: &PropertyKey) -> Option<String> {
+    match key {
+        PropertyKey::StaticIdentifier(ident) => Some(ident.name.to_string()),
+        PropertyKey::StringLiteral(literal) => Some(literal.value.to_string()),
+        _ => None,
+    }
+}
+
+/// Whether a type obviously holds values that can't be serialized, alone or as part of a union
+/// (`(() => void) | undefined`)
+fn is_non_serializable(ts_type: &TSType) -> bool {
+    match ts_type {
+        TSType::TSFunctionType(_) | TSType::TSConstructorType(_) | TSType::TSSymbolKeyword(_) => {
+            true
+        }
+        TSType::TSTypeReference(type_ref) => match &type_ref.type_name {
+            TSTypeName::IdentifierReference(ident) => {
+                NON_SERIALIZABLE_TYPES.contains(&ident.name.as_str())
+            }
+            _ => false,
+        },
+        TSType::TSUnionType(union) => union.types.iter().any(is_non_serializable),
+        TSType::TSParenthesizedType(parenthesized) => {
+            is_non_serializable(&parenthesized.type_annotation)
+        }
+        _ => false,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_collect_type_exports() {
+        let source = r#"
+export interface ButtonProps {
+  label: string;
+  onClick?: () => void;
+  createdAt: Date;
+  format(value: number): string;
+}
+export type CardProps = BaseProps & {
+  "data-id": string;
+  cache: Map<string, number> | undefined;
+};
+type Internal = { secret: string };
+export type Variant = "primary" | "secondary";
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let exports = collect_type_exports(&ret.program.body);
+
+        let summary = exports
+            .iter()
+            .map(|export| {
+                (
+                    export.name.as_str(),
+                    export.kind,
+                    export
+                        .members
+                        .iter()
+                        .map(|member| {
+                            (
+                                member.name.as_str(),
+                                member.optional,
+                                member.non_serializable,
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "ButtonProps",
+                    types::TypeExportKind::Interface,
+                    vec![
+                        ("label", false, false),
+                        ("onClick", true, true),
+                        ("createdAt", false, true),
+                        ("format", false, true),
+                    ]
+                ),
+                (
+                    "CardProps",
+                    types::TypeExportKind::Alias,
+                    vec![("data-id", false, false), ("cache", false, true)]
+                ),
+                ("Variant", types::TypeExportKind::Alias, vec![]),
+            ]
+        );
+    }
+}
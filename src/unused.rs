@@ -0,0 +1,247 @@
+use crate::analyze_react_boundary::check::types;
+use crate::graph::ModuleGraph;
+use std::collections::HashSet;
+
+/// Export name recorded for a module imported as a namespace, which reaches all of its exports
+const NAMESPACE: &str = "*";
+
+/// `(path, exported name)` pairs
+type Exports = HashSet<(String, String)>;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Find the exported components of a file set that no file of the set renders, either because
+/// nothing imports them or because their importers never render them. In a "use client" module
+/// such a component still is a client entry point the bundler has to keep, so it is worth
+/// pruning.
+///
+/// App router entries (`page`, `layout`, ...) are rendered by the framework, so they are never
+/// reported. Files that fail to parse are left out.
+pub(crate) fn find_unused_components(
+    files: Vec<types::SourceFile>,
+    is_cancelled: &dyn Fn() -> bool,
+) -> Vec<types::UnusedComponent> {
+    let graph = ModuleGraph::new(&files);
+    let analyses = analyze_modules(files, &graph, is_cancelled);
+    unused_components(&graph, &analyses)
+}
+
+/// Analyze the files that are modules of `graph`, keeping each analysis with the file's path
+pub(crate) fn analyze_modules(
+    files: Vec<types::SourceFile>,
+    graph: &ModuleGraph,
+    is_cancelled: &dyn Fn() -> bool,
+) -> Vec<(String, types::AnalysisResult)> {
+    files
+        .into_iter()
+        .filter(|file| graph.modules.contains_key(&file.path))
+        .filter_map(|file| {
+            if is_cancelled() {
+                return None;
+            }
+            let analysis =
+                crate::analyze_source(file.content, file.extension, is_cancelled).ok()?;
+            Some((file.path, analysis))
+        })
+        .collect()
+}
+
+/// [`find_unused_components`] over modules that are already analyzed
+pub(crate) fn unused_components(
+    graph: &ModuleGraph,
+    analyses: &[(String, types::AnalysisResult)],
+) -> Vec<types::UnusedComponent> {
+    let (imported, rendered) = used_exports(graph);
+    let mut unused = Vec::new();
+
+    for (path, analysis) in analyses {
+        if crate::entry::classify_entry(path).is_some() {
+            continue;
+        }
+        let local_usages = graph
+            .modules
+            .get(path)
+            .map(|module| {
+                module
+                    .jsx_usages
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .collect::<HashSet<_>>()
+            })
+            .unwrap_or_default();
+        let is_client = analysis.use_client_directive.is_some();
+
+        for component in &analysis.components {
+            let public_name = component
+                .exported_name
+                .as_deref()
+                .unwrap_or(&component.name);
+            let exported_as = match component.export_kind {
+                types::ExportKind::Default => vec!["default"],
+                types::ExportKind::Named => vec![public_name],
+                types::ExportKind::Both => vec!["default", public_name],
+            };
+            let used_in = |exports: &Exports| {
+                exported_as
+                    .iter()
+                    .chain(std::iter::once(&NAMESPACE))
+                    .any(|name| exports.contains(&(path.clone(), name.to_string())))
+            };
+            if local_usages.contains(component.name.as_str()) || used_in(&rendered) {
+                continue;
+            }
+
+            let is_imported = used_in(&imported);
+            let reason = if is_imported {
+                "is imported but never rendered"
+            } else {
+                "is exported but never imported"
+            };
+            let message = if is_client {
+                format!(
+                    "Component {} {} by the analyzed files, yet it remains a \"use client\" entry \
+                     point",
+                    component.name, reason
+                )
+            } else {
+                format!(
+                    "Component {} {} by the analyzed files",
+                    component.name, reason
+                )
+            };
+            unused.push(types::UnusedComponent {
+                path: path.clone(),
+                name: component.name.clone(),
+                range: component.range,
+                export_range: component.export_range,
+                is_client,
+                is_imported,
+                message,
+            });
+        }
+    }
+
+    unused.sort_by(|a, b| {
+        (&a.path, a.range.start.line, a.range.start.character).cmp(&(
+            &b.path,
+            b.range.start.line,
+            b.range.start.character,
+        ))
+    });
+    unused
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Every export some file imports, and of every export some file
+/// renders, following re-exports back to the modules that may declare them
+fn used_exports(graph: &ModuleGraph) -> (Exports, Exports) {
+    let mut imported = HashSet::new();
+    let mut rendered = HashSet::new();
+
+    for (path, module) in &graph.modules {
+        for import in &module.imports {
+            let Some(target) = graph.resolve(path, &import.source) else {
+                continue;
+            };
+            let is_rendered = module
+                .jsx_usages
+                .iter()
+                .any(|(local, _)| *local == import.local);
+            match &import.imported {
+                Some(name) => {
+                    graph.origins(&target, name, &mut imported);
+                    if is_rendered {
+                        graph.origins(&target, name, &mut rendered);
+                    }
+                }
+                None => {
+                    imported.insert((target.clone(), NAMESPACE.to_string()));
+                    if is_rendered {
+                        rendered.insert((target, NAMESPACE.to_string()));
+                    }
+                }
+            }
+        }
+    }
+
+    (imported, rendered)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::source_file;
+
+    #[test]
+    fn test_find_unused_components() {
+        let files = vec![
+            source_file(
+                "/app/page.tsx",
+                r#"
+import { Dialog, Tooltip } from "./ui";
+import * as Icons from "./icons";
+export default function Page() {
+  return <Dialog><Icons.Close /></Dialog>;
+}
+                "#,
+            ),
+            source_file("/app/ui.ts", r#"export * from "./dialog";"#),
+            source_file(
+                "/app/dialog.tsx",
+                r#""use client";
+export const Dialog = ({ children }) => <dialog>{children}</dialog>;
+export const Tooltip = () => <div role="tooltip" />;
+export const Popover = () => <div />;
+                "#,
+            ),
+            source_file("/app/icons.tsx", r#"export const Close = () => <svg />;"#),
+            source_file(
+                "/app/card.tsx",
+                r#"
+const CardBody = () => <div />;
+export const Card = () => <CardBody />;
+export { CardBody };
+                "#,
+            ),
+        ];
+
+        let unused = find_unused_components(files, &|| false);
+
+        let reported = unused
+            .iter()
+            .map(|component| {
+                (
+                    component.path.as_str(),
+                    component.name.as_str(),
+                    component.is_imported,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reported,
+            vec![
+                ("/app/card.tsx", "Card", false),
+                ("/app/dialog.tsx", "Tooltip", true),
+                ("/app/dialog.tsx", "Popover", false),
+            ]
+        );
+        assert_eq!(
+            unused[1].message,
+            "Component Tooltip is imported but never rendered by the analyzed files, yet it \
+             remains a \"use client\" entry point"
+        );
+        assert_eq!(
+            unused[0].message,
+            "Component Card is exported but never imported by the analyzed files"
+        );
+    }
+}
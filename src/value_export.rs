@@ -0,0 +1,216 @@
+use crate::analyze_react_boundary::check::types;
+use oxc::ast::ast::{
+    BindingPatternKind, Declaration, ExportDefaultDeclarationKind, Expression, Statement,
+};
+use oxc::span::{GetSpan, Span};
+use std::collections::HashMap;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Collect the exports of a module that are not components (`export const metadata = {...}`,
+/// `export const revalidate = 60`, helper functions), as (exported name, kind, span of the
+/// exported name). Type-only exports and re-exports are skipped.
+///
+/// `components` are the exported names of the components found in the module.
+pub(crate) fn collect_value_exports(
+    statements: &[Statement],
+    components: &[String],
+) -> Vec<(String, types::ValueKind, Span)> {
+    let local_kinds = local_value_kinds(statements);
+    let mut exports = Vec::new();
+
+    let mut push = |name: String, kind: types::ValueKind, span: Span| {
+        if !components.contains(&name) {
+            exports.push((name, kind, span));
+        }
+    };
+
+    for statement in statements {
+        match statement {
+            Statement::ExportNamedDeclaration(export_decl) => {
+                if export_decl.export_kind.is_type() || export_decl.source.is_some() {
+                    continue;
+                }
+                match &export_decl.declaration {
+                    Some(Declaration::VariableDeclaration(var_decl)) => {
+                        for declarator in var_decl.declarations.iter() {
+                            if let BindingPatternKind::BindingIdentifier(ident) =
+                                &declarator.id.kind
+                            {
+                                let kind = declarator
+                                    .init
+                                    .as_ref()
+                                    .map_or(types::ValueKind::Other, value_kind);
+                                push(ident.name.to_string(), kind, ident.span);
+                            }
+                        }
+                    }
+                    Some(Declaration::FunctionDeclaration(func)) => {
+                        if let Some(id) = &func.id {
+                            push(id.name.to_string(), types::ValueKind::Function, id.span);
+                        }
+                    }
+                    Some(Declaration::ClassDeclaration(class)) => {
+                        if let Some(id) = &class.id {
+                            push(id.name.to_string(), types::ValueKind::Class, id.span);
+                        }
+                    }
+                    Some(_) => {}
+                    None => {
+                        for specifier in export_decl.specifiers.iter() {
+                            if specifier.export_kind.is_type() {
+                                continue;
+                            }
+                            let local = crate::module_export_name(&specifier.local);
+                            push(
+                                crate::module_export_name(&specifier.exported),
+                                local_kinds
+                                    .get(&local)
+                                    .copied()
+                                    .unwrap_or(types::ValueKind::Other),
+                                specifier.exported.span(),
+                            );
+                        }
+                    }
+                }
+            }
+            Statement::ExportDefaultDeclaration(export_decl) => {
+                let kind = match &export_decl.declaration {
+                    ExportDefaultDeclarationKind::FunctionDeclaration(_) => {
+                        types::ValueKind::Function
+                    }
+                    ExportDefaultDeclarationKind::ClassDeclaration(_) => types::ValueKind::Class,
+                    ExportDefaultDeclarationKind::Identifier(ident) => local_kinds
+                        .get(ident.name.as_str())
+                        .copied()
+                        .unwrap_or(types::ValueKind::Other),
+                    declaration => declaration
+                        .as_expression()
+                        .map_or(types::ValueKind::Other, value_kind),
+                };
+                push("default".to_string(), kind, export_decl.span);
+            }
+            _ => {}
+        }
+    }
+
+    exports
+}
+
+/// Whether an export name follows the hook naming convention (`useDebounce`, `use3D`), as the
+/// rules of hooks define it
+pub(crate) fn is_hook_name(name: &str) -> bool {
+    name.strip_prefix("use").is_some_and(|rest| {
+        rest.starts_with(|c: char| c.is_ascii_uppercase() || c.is_ascii_digit())
+    })
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Shape of the value an expression evaluates to, looking through `as const` and `satisfies`
+fn value_kind(expr: &Expression) -> types::ValueKind {
+    match expr.get_inner_expression() {
+        Expression::ObjectExpression(_) => types::ValueKind::Object,
+        Expression::ArrayExpression(_) => types::ValueKind::Array,
+        Expression::StringLiteral(_) | Expression::TemplateLiteral(_) => types::ValueKind::String,
+        Expression::NumericLiteral(_) | Expression::BigIntLiteral(_) => types::ValueKind::Number,
+        Expression::BooleanLiteral(_) => types::ValueKind::Boolean,
+        Expression::ArrowFunctionExpression(_) | Expression::FunctionExpression(_) => {
+            types::ValueKind::Function
+        }
+        Expression::ClassExpression(_) => types::ValueKind::Class,
+        _ => types::ValueKind::Other,
+    }
+}
+
+/// Kinds of the top-level bindings, for exports listed in `export { ... }` and `export default X`
+fn local_value_kinds(statements: &[Statement]) -> HashMap<String, types::ValueKind> {
+    let mut kinds = HashMap::new();
+
+    for statement in statements {
+        let declaration = match statement {
+            Statement::ExportNamedDeclaration(export_decl) => export_decl.declaration.as_ref(),
+            statement => statement.as_declaration(),
+        };
+        match declaration {
+            Some(Declaration::VariableDeclaration(var_decl)) => {
+                for declarator in var_decl.declarations.iter() {
+                    if let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind {
+                        let kind = declarator
+                            .init
+                            .as_ref()
+                            .map_or(types::ValueKind::Other, value_kind);
+                        kinds.insert(ident.name.to_string(), kind);
+                    }
+                }
+            }
+            Some(Declaration::FunctionDeclaration(func)) => {
+                if let Some(id) = &func.id {
+                    kinds.insert(id.name.to_string(), types::ValueKind::Function);
+                }
+            }
+            Some(Declaration::ClassDeclaration(class)) => {
+                if let Some(id) = &class.id {
+                    kinds.insert(id.name.to_string(), types::ValueKind::Class);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    kinds
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_collect_value_exports_beside_components() {
+        let source = r#"
+import type { Metadata } from "next";
+
+export const metadata: Metadata = { title: "Dashboard" };
+export const revalidate = 60;
+export const runtime = "edge";
+export const routes = ["/", "/settings"] as const;
+export function generateStaticParams() { return []; }
+export type Props = { id: string };
+export { Button } from "./button";
+const theme = { dark: true };
+export { theme as defaultTheme };
+export default function Page() { return <main />; }
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let exports = collect_value_exports(&ret.program.body, &["default".to_string()])
+            .into_iter()
+            .map(|(name, kind, _)| (name, kind))
+            .collect::<Vec<_>>();
+
+        let entry = |name: &str, kind| (name.to_string(), kind);
+        assert_eq!(
+            exports,
+            vec![
+                entry("metadata", types::ValueKind::Object),
+                entry("revalidate", types::ValueKind::Number),
+                entry("runtime", types::ValueKind::String),
+                entry("routes", types::ValueKind::Array),
+                entry("generateStaticParams", types::ValueKind::Function),
+                entry("defaultTheme", types::ValueKind::Object),
+            ]
+        );
+    }
+}
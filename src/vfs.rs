@@ -0,0 +1,351 @@
+use crate::analyze_react_boundary::check::types;
+use oxc::allocator::Allocator;
+use oxc::parser::Parser;
+use oxc::span::SourceType;
+use std::collections::HashMap;
+
+/// Host-provided file access used to chase imports across files
+pub(crate) trait FileSystem {
+    /// Resolve an import specifier relative to the importing file
+    fn resolve(&self, specifier: &str, from: &str) -> Option<String>;
+    fn read_file(&self, path: &str) -> Option<Vec<u8>>;
+}
+
+/// File system backed by the `resolve` / `read-file` WIT imports
+pub(crate) struct HostFileSystem;
+
+impl FileSystem for HostFileSystem {
+    fn resolve(&self, specifier: &str, from: &str) -> Option<String> {
+        #[cfg(target_arch = "wasm32")]
+        return crate::resolve(specifier, from);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = (specifier, from);
+            None
+        }
+    }
+
+    fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        #[cfg(target_arch = "wasm32")]
+        return crate::read_file(path);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = path;
+            None
+        }
+    }
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Analyze a file and follow its relative imports through the host file system, reporting
+/// findings that need the imported module's directives:
+/// - JSX usages of components imported from "use client" modules
+/// - `action` / `formAction` bindings, verified against the imported module's "use server"
+pub(crate) fn analyze_with_imports(
+    path: &str,
+    content: Vec<u8>,
+    extension: String,
+    fs: &dyn FileSystem,
+    is_cancelled: &dyn Fn() -> bool,
+) -> Result<types::CrossFileAnalysis, String> {
+    let action_bindings = collect_action_bindings(&content, &extension)?;
+    let analysis = crate::analyze_source(content, extension, is_cancelled)?;
+
+    let mut modules = ModuleCache::default();
+    let mut findings = Vec::new();
+
+    for import in analysis
+        .imports
+        .iter()
+        .filter(|import| is_relative(&import.source) && import.asset.is_none())
+    {
+        crate::check_cancelled(is_cancelled)?;
+
+        let usages = analysis
+            .jsx_usages
+            .iter()
+            .filter(|usage| !usage.is_fragment && import.identifier.contains(&usage.component_name))
+            .map(|usage| (usage.component_name.as_str(), usage.range))
+            .collect::<Vec<_>>();
+        let actions = action_bindings
+            .iter()
+            .filter(|(name, _)| import.identifier.contains(name))
+            .map(|(name, range)| (name.as_str(), *range))
+            .collect::<Vec<_>>();
+
+        if usages.is_empty() && actions.is_empty() {
+            continue;
+        }
+
+        let Some((resolved_path, directives)) = modules.load(fs, &import.source, path) else {
+            findings.push(types::CrossFileFinding {
+                kind: types::CrossFileFindingKind::UnresolvedImport,
+                identifier: import.identifier.join(", "),
+                source: import.source.clone(),
+                resolved_path: None,
+                range: import.source_span,
+            });
+            continue;
+        };
+
+        let is_client = directives.iter().any(|d| d == "use client");
+        let is_server = directives.iter().any(|d| d == "use server");
+
+        if is_client {
+            for (identifier, range) in usages {
+                findings.push(types::CrossFileFinding {
+                    kind: types::CrossFileFindingKind::ClientComponent,
+                    identifier: identifier.to_string(),
+                    source: import.source.clone(),
+                    resolved_path: Some(resolved_path.clone()),
+                    range,
+                });
+            }
+        }
+
+        for (identifier, range) in actions {
+            findings.push(types::CrossFileFinding {
+                kind: if is_server {
+                    types::CrossFileFindingKind::ServerAction
+                } else {
+                    types::CrossFileFindingKind::ActionNotServer
+                },
+                identifier: identifier.to_string(),
+                source: import.source.clone(),
+                resolved_path: Some(resolved_path.clone()),
+                range,
+            });
+        }
+    }
+
+    Ok(types::CrossFileAnalysis { analysis, findings })
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn is_relative(specifier: &str) -> bool {
+    specifier.starts_with("./") || specifier.starts_with("../")
+}
+
+/// Parse the entry file for the action bindings, which are not part of `AnalysisResult`
+fn collect_action_bindings(
+    content: &[u8],
+    extension: &str,
+) -> Result<Vec<(String, types::Range)>, String> {
+    let source_text =
+        std::str::from_utf8(content).map_err(|e| format!("Invalid UTF-8 source: {}", e))?;
+    let source_type = SourceType::from_extension(extension)
+        .map_err(|e| format!("{}: {}", e.to_string().replace("\"", ""), extension))?;
+
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+
+    Ok(crate::jsx::collect_jsx(&ret.program.body)
+        .action_bindings
+        .into_iter()
+        .map(|(name, span)| (name, crate::range::span_to_range(source_text, span)))
+        .collect())
+}
+
+/// Imported modules loaded so far, so a module imported twice is only read once
+#[derive(Default)]
+struct ModuleCache {
+    directives: HashMap<String, Option<(String, Vec<String>)>>,
+}
+
+impl ModuleCache {
+    /// Resolve and read an imported module, returning its path and directives
+    fn load(
+        &mut self,
+        fs: &dyn FileSystem,
+        specifier: &str,
+        from: &str,
+    ) -> Option<&(String, Vec<String>)> {
+        self.directives
+            .entry(specifier.to_string())
+            .or_insert_with(|| {
+                let resolved_path = fs.resolve(specifier, from)?;
+                let content = fs.read_file(&resolved_path)?;
+                let extension = resolved_path.rsplit('.').next()?.to_string();
+                let directives = module_directives(&content, &extension)?;
+                Some((resolved_path, directives))
+            })
+            .as_ref()
+    }
+}
+
+/// Read the directive prologue of a module
+fn module_directives(content: &[u8], extension: &str) -> Option<Vec<String>> {
+    let source_text = std::str::from_utf8(content).ok()?;
+    let source_type = SourceType::from_extension(extension).ok()?;
+
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+
+    Some(
+        ret.program
+            .directives
+            .iter()
+            .map(|directive| directive.directive.to_string())
+            .collect(),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// In-memory file system resolving specifiers relative to the importing file's directory
+    struct MemoryFileSystem {
+        files: HashMap<&'static str, &'static str>,
+    }
+
+    impl FileSystem for MemoryFileSystem {
+        fn resolve(&self, specifier: &str, from: &str) -> Option<String> {
+            let dir = from.rsplit_once('/').map_or("", |(dir, _)| dir);
+            let path = format!("{}/{}.tsx", dir, specifier.trim_start_matches("./"));
+            self.files.contains_key(path.as_str()).then_some(path)
+        }
+
+        fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+            self.files
+                .get(path)
+                .map(|content| content.as_bytes().to_vec())
+        }
+    }
+
+    fn analyze(fs: &MemoryFileSystem, path: &str) -> types::CrossFileAnalysis {
+        let content = fs.read_file(path).unwrap();
+        analyze_with_imports(path, content, "tsx".to_string(), fs, &|| false).unwrap()
+    }
+
+    #[test]
+    fn test_client_component_import() {
+        let fs = MemoryFileSystem {
+            files: HashMap::from([
+                (
+                    "/app/page.tsx",
+                    r#"
+import { Button } from "./button";
+import { Card } from "./card";
+export default function Page() {
+  return <Card><Button /></Card>;
+}
+                    "#,
+                ),
+                (
+                    "/app/button.tsx",
+                    r#""use client"; export const Button = () => <button />;"#,
+                ),
+                ("/app/card.tsx", r#"export const Card = () => <div />;"#),
+            ]),
+        };
+
+        let result = analyze(&fs, "/app/page.tsx");
+
+        assert_eq!(result.findings.len(), 1);
+        let finding = &result.findings[0];
+        assert_eq!(finding.kind, types::CrossFileFindingKind::ClientComponent);
+        assert_eq!(finding.identifier, "Button");
+        assert_eq!(finding.resolved_path.as_deref(), Some("/app/button.tsx"));
+    }
+
+    #[test]
+    fn test_action_verified_against_use_server() {
+        let fs = MemoryFileSystem {
+            files: HashMap::from([
+                (
+                    "/app/page.tsx",
+                    r#"
+import { createPost } from "./actions";
+import { deletePost } from "./helpers";
+export default function Page() {
+  return (
+    <form action={createPost}>
+      <button formAction={deletePost}>Delete</button>
+    </form>
+  );
+}
+                    "#,
+                ),
+                (
+                    "/app/actions.tsx",
+                    r#""use server"; export async function createPost() {}"#,
+                ),
+                (
+                    "/app/helpers.tsx",
+                    r#"export async function deletePost() {}"#,
+                ),
+            ]),
+        };
+
+        let result = analyze(&fs, "/app/page.tsx");
+
+        assert_eq!(result.findings.len(), 2);
+        assert_eq!(
+            result.findings[0].kind,
+            types::CrossFileFindingKind::ServerAction
+        );
+        assert_eq!(result.findings[0].identifier, "createPost");
+        assert_eq!(
+            result.findings[1].kind,
+            types::CrossFileFindingKind::ActionNotServer
+        );
+        assert_eq!(result.findings[1].identifier, "deletePost");
+        // Range points at the formAction attribute
+        assert_eq!(result.findings[1].range.start.line, 6);
+    }
+
+    #[test]
+    fn test_unresolved_relative_import() {
+        let fs = MemoryFileSystem {
+            files: HashMap::from([(
+                "/app/page.tsx",
+                r#"
+import { Missing } from "./missing";
+import { Link } from "next/link";
+import Logo from "./logo.svg";
+export default function Page() {
+  return <div><Missing /><Link /><Logo /></div>;
+}
+                "#,
+            )]),
+        };
+
+        let result = analyze(&fs, "/app/page.tsx");
+
+        // Package imports and assets are never chased, so only the relative module is reported
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(
+            result.findings[0].kind,
+            types::CrossFileFindingKind::UnresolvedImport
+        );
+        assert_eq!(result.findings[0].source, "./missing");
+    }
+
+    #[test]
+    fn test_unused_imports_are_not_read() {
+        let fs = MemoryFileSystem {
+            files: HashMap::from([(
+                "/app/page.tsx",
+                r#"
+import { helper } from "./missing";
+export const value = helper();
+                "#,
+            )]),
+        };
+
+        let result = analyze(&fs, "/app/page.tsx");
+
+        assert!(result.findings.is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+use oxc::ast::ast::{CallExpression, Expression, FunctionBody, JSXElement, Statement};
+use oxc::ast_visit::{Visit, walk};
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Node counts of a module, as reported in the AST statistics
+#[derive(Default)]
+pub(crate) struct NodeCounts {
+    /// Function bodies, arrow functions and methods included
+    pub(crate) functions: u32,
+    pub(crate) jsx_elements: u32,
+    /// Call expressions, optional calls included
+    pub(crate) calls: u32,
+    /// Deepest nesting of expressions, function bodies and JSX elements
+    pub(crate) max_depth: u32,
+}
+
+/// Count the nodes of the statements, type-only syntax excluded
+pub(crate) fn count_nodes(statements: &[Statement]) -> NodeCounts {
+    let mut counter = NodeCounter::default();
+    for statement in statements {
+        counter.visit_statement(statement);
+    }
+    counter.counts
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+#[derive(Default)]
+struct NodeCounter {
+    counts: NodeCounts,
+    depth: u32,
+}
+
+impl NodeCounter {
+    fn enter(&mut self) {
+        self.depth += 1;
+        self.counts.max_depth = self.counts.max_depth.max(self.depth);
+    }
+
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+}
+
+impl<'a> Visit<'a> for NodeCounter {
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        self.enter();
+        walk::walk_expression(self, expr);
+        self.leave();
+    }
+
+    fn visit_function_body(&mut self, body: &FunctionBody<'a>) {
+        self.counts.functions += 1;
+        self.enter();
+        walk::walk_function_body(self, body);
+        self.leave();
+    }
+
+    fn visit_jsx_element(&mut self, element: &JSXElement<'a>) {
+        self.counts.jsx_elements += 1;
+        self.enter();
+        walk::walk_jsx_element(self, element);
+        self.leave();
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        self.counts.calls += 1;
+        walk::walk_call_expression(self, call);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    fn counts(source: &str) -> NodeCounts {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        count_nodes(&ret.program.body)
+    }
+
+    #[test]
+    fn test_count_nested_calls() {
+        let source = r#"
+export default function Page({ items }) {
+  const value = compute(load());
+  if (check()) {
+    items.forEach((item) => track(item));
+  }
+  return <List render={() => renderItem()}>{format(value)}</List>;
+}
+class Store { field = init(); method() { run(); } }
+        "#;
+
+        // compute, load, check, forEach, track, renderItem, format, init, run
+        assert_eq!(counts(source).calls, 9);
+    }
+
+    #[test]
+    fn test_count_nodes() {
+        let source = r#"
+function Page({ items }) {
+  return <List>{items.map((item) => <Item key={item.id} value={format(item)} />)}</List>;
+}
+class Store { load() { return fetch?.(); } }
+        "#;
+
+        let counts = counts(source);
+        assert_eq!(counts.functions, 3);
+        assert_eq!(counts.jsx_elements, 2);
+        assert_eq!(counts.calls, 3);
+        // Page body > <List> expression > <List> > items.map(...) > arrow > body > <Item>
+        // expression > <Item> > format(item) > item
+        assert_eq!(counts.max_depth, 10);
+    }
+
+    #[test]
+    fn test_count_assignment_targets_updates_and_decorators() {
+        let source = r#"
+cache[key()] = value();
+counters[index()]++;
+@register(track())
+class Store {}
+        "#;
+
+        // key, value, index, register, track
+        assert_eq!(counts(source).calls, 5);
+    }
+
+    #[test]
+    fn test_count_skips_type_only_syntax() {
+        let source = r#"
+type Result = ReturnType<typeof compute>;
+interface Props { run(): void }
+const value = compute() as Result;
+        "#;
+
+        assert_eq!(counts(source).calls, 1);
+    }
+}
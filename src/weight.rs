@@ -0,0 +1,169 @@
+use crate::analyze_react_boundary::check::types;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Rough size metrics of a "use client" module, which becomes a client bundle entry point.
+/// Only runtime imports are expected, since type imports are erased before bundling
+pub(crate) fn client_weight(
+    source_text: &str,
+    imports: &[types::ImportAnalysis],
+) -> types::ClientWeight {
+    types::ClientWeight {
+        byte_length: source_text.len() as u32,
+        import_count: imports.len() as u32,
+        third_party_import_count: imports
+            .iter()
+            .filter(|import| is_package_specifier(&import.source))
+            .count() as u32,
+    }
+}
+
+/// Whether a specifier names an npm package rather than a project file. Common project aliases
+/// (`@/`, `~/`, `#` subpath imports), Node.js builtins (`fs`, `path/posix`) and specifiers with a
+/// scheme (`node:`, `bun:`, `virtual:`) are not packages
+pub(crate) fn is_package_specifier(specifier: &str) -> bool {
+    !(specifier.is_empty()
+        || specifier.starts_with('.')
+        || specifier.starts_with('/')
+        || specifier.starts_with("@/")
+        || specifier.starts_with("~/")
+        || specifier.starts_with('#')
+        || has_scheme(specifier)
+        || is_node_builtin(specifier))
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Modules built into Node.js, importable without the `node:` prefix
+const NODE_BUILTINS: [&str; 51] = [
+    "assert",
+    "async_hooks",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "diagnostics_channel",
+    "dns",
+    "domain",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "inspector",
+    "module",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "string_decoder",
+    "sys",
+    "timers",
+    "tls",
+    "trace_events",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "wasi",
+    "worker_threads",
+    "zlib",
+    "_http_agent",
+    "_http_client",
+    "_http_common",
+    "_http_incoming",
+    "_http_outgoing",
+    "_http_server",
+    "_stream_readable",
+    "_stream_writable",
+    "_tls_wrap",
+];
+
+/// Whether the first segment of a specifier is a Node.js builtin (`fs/promises`)
+fn is_node_builtin(specifier: &str) -> bool {
+    let module = specifier.split('/').next().unwrap_or(specifier);
+    NODE_BUILTINS.contains(&module)
+}
+
+/// Whether a specifier starts with a URL-like scheme (`node:fs`, `bun:sqlite`, `virtual:icons`)
+fn has_scheme(specifier: &str) -> bool {
+    specifier.split_once(':').is_some_and(|(scheme, _)| {
+        !scheme.is_empty()
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '+' || c == '.')
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(source: &str) -> types::ImportAnalysis {
+        let position = types::Position {
+            line: 0,
+            character: 0,
+        };
+        let range = types::Range {
+            start: position,
+            end: position,
+        };
+        types::ImportAnalysis {
+            identifier: Vec::new(),
+            source: source.to_string(),
+            source_span: range,
+            range,
+            specifier_ranges: Vec::new(),
+            asset: None,
+        }
+    }
+
+    #[test]
+    fn test_client_weight_counts_imports() {
+        let imports = [
+            import("react"),
+            import("@radix-ui/react-dialog"),
+            import("./button"),
+            import("@/lib/utils"),
+            import("node:path"),
+        ];
+
+        let weight = client_weight("\"use client\";\n", &imports);
+
+        assert_eq!(weight.byte_length, 14);
+        assert_eq!(weight.import_count, 5);
+        assert_eq!(weight.third_party_import_count, 2);
+    }
+
+    #[test]
+    fn test_is_package_specifier() {
+        assert!(is_package_specifier("lodash/debounce"));
+        assert!(is_package_specifier("@tanstack/react-query"));
+        assert!(!is_package_specifier("../utils"));
+        assert!(!is_package_specifier("~/components/card"));
+        assert!(!is_package_specifier("#internal"));
+        assert!(!is_package_specifier("fs"));
+        assert!(!is_package_specifier("path/posix"));
+        assert!(!is_package_specifier("bun:sqlite"));
+        assert!(!is_package_specifier("virtual:icons"));
+        assert!(is_package_specifier("pathe"));
+    }
+}
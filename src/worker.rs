@@ -0,0 +1,157 @@
+use crate::analyze_react_boundary::check::types;
+use oxc::ast::ast::{Argument, Expression, NewExpression, Statement};
+use oxc::ast_visit::{Visit, walk};
+use oxc::span::Span;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// A `new Worker(...)` or `new SharedWorker(...)` construction loading a module
+pub(crate) struct WorkerReference {
+    pub(crate) kind: types::WorkerKind,
+    /// Specifier of the worker module
+    pub(crate) source: String,
+    pub(crate) source_span: Span,
+    /// Span of the `new` expression
+    pub(crate) span: Span,
+}
+
+/// Collect the worker constructions whose script is a module specifier:
+/// `new Worker(new URL("./worker.ts", import.meta.url))`, the form bundlers follow, or a plain
+/// string (`new SharedWorker("./shared.js")`). The worker module is bundled as another entry, so
+/// it is a module edge of its own. Scripts computed at runtime are skipped
+pub(crate) fn collect_workers(statements: &[Statement]) -> Vec<WorkerReference> {
+    let mut collector = WorkerCollector::default();
+    for statement in statements {
+        collector.visit_statement(statement);
+    }
+    collector.workers
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+#[derive(Default)]
+struct WorkerCollector {
+    workers: Vec<WorkerReference>,
+}
+
+impl<'a> Visit<'a> for WorkerCollector {
+    fn visit_new_expression(&mut self, new: &NewExpression<'a>) {
+        let kind = match new.callee.get_inner_expression() {
+            Expression::Identifier(ident) if ident.name == "Worker" => {
+                Some(types::WorkerKind::Worker)
+            }
+            Expression::Identifier(ident) if ident.name == "SharedWorker" => {
+                Some(types::WorkerKind::SharedWorker)
+            }
+            _ => None,
+        };
+        if let Some(kind) = kind
+            && let Some((source, source_span)) = new
+                .arguments
+                .first()
+                .and_then(Argument::as_expression)
+                .and_then(script_specifier)
+        {
+            self.workers.push(WorkerReference {
+                kind,
+                source,
+                source_span,
+                span: new.span,
+            });
+        }
+        walk::walk_new_expression(self, new);
+    }
+}
+
+/// Specifier of a worker script: a string literal, or `new URL("...", import.meta.url)`
+fn script_specifier(script: &Expression) -> Option<(String, Span)> {
+    match script.get_inner_expression() {
+        Expression::StringLiteral(literal) => Some((literal.value.to_string(), literal.span)),
+        Expression::NewExpression(new) => {
+            let Expression::Identifier(callee) = new.callee.get_inner_expression() else {
+                return None;
+            };
+            let base = new.arguments.get(1)?.as_expression()?;
+            if callee.name != "URL" || !is_import_meta_url(base) {
+                return None;
+            }
+            match new
+                .arguments
+                .first()?
+                .as_expression()?
+                .get_inner_expression()
+            {
+                Expression::StringLiteral(literal) => {
+                    Some((literal.value.to_string(), literal.span))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_import_meta_url(expr: &Expression) -> bool {
+    matches!(expr.get_inner_expression(), Expression::StaticMemberExpression(member)
+        if member.property.name == "url"
+            && matches!(&member.object, Expression::MetaProperty(meta)
+                if meta.meta.name == "import" && meta.property.name == "meta"))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_collect_workers() {
+        let source = r#"
+const worker = new Worker(new URL("./resize.worker.ts", import.meta.url), { type: "module" });
+export function useSync() {
+  const shared = new SharedWorker("./sync.js");
+}
+const other = new Worker(new URL("./other.ts", location.href));
+const computed = new Worker(scriptUrl);
+        "#;
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+        let workers = collect_workers(&ret.program.body);
+
+        let summary = workers
+            .iter()
+            .map(|worker| {
+                (
+                    worker.kind,
+                    worker.source.as_str(),
+                    &source[worker.source_span.start as usize..worker.source_span.end as usize],
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    types::WorkerKind::Worker,
+                    "./resize.worker.ts",
+                    "\"./resize.worker.ts\""
+                ),
+                (
+                    types::WorkerKind::SharedWorker,
+                    "./sync.js",
+                    "\"./sync.js\""
+                ),
+            ]
+        );
+    }
+}